@@ -0,0 +1,108 @@
+use std::fs::File;
+
+use anyhow::{anyhow, Context, Result};
+use bytes::Buf;
+use falcon_core::network::ConnectionState;
+use falcon_core::server::config::FalconConfig;
+use falcon_core::ShutdownHandle;
+use falcon_logic::connection::capture::{CaptureReader, Direction};
+use falcon_logic::server::ServerWrapper;
+use falcon_logic::FalconConnection;
+use itertools::Itertools;
+use tokio::sync::mpsc::unbounded_channel;
+use uuid::Uuid;
+
+const USAGE: &str = "Usage:
+    falcon-capture print <capture> [--hex]
+    falcon-capture replay <capture> [config]";
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let file_name = args.get(1).ok_or_else(|| anyhow!("Missing capture file\n{}", USAGE))?;
+    let reader = CaptureReader::new(File::open(file_name).with_context(|| format!("Could not open \"{}\"", file_name))?)
+        .with_context(|| format!("Could not read \"{}\"", file_name))?;
+
+    match args[0].as_str() {
+        "print" => print(reader, args.iter().any(|arg| arg == "--hex")),
+        "replay" => {
+            let failed = replay(reader, args.get(2).map(String::as_str).unwrap_or("config/falcon.toml")).await?;
+            if failed > 0 {
+                std::process::exit(1);
+            }
+            Ok(())
+        },
+        _ => Err(anyhow!(USAGE)),
+    }
+}
+
+/// Pretty-prints every record of the capture.
+fn print(reader: CaptureReader<File>, hex: bool) -> Result<()> {
+    println!("Capture started at {} (unix ms)", reader.unix_start());
+    for record in reader {
+        let record = record?;
+        println!(
+            "[{:>12.6}s] {} {:<12} protocol={:<4} id={:#04X} len={}",
+            record.time.as_secs_f64(),
+            record.direction,
+            format!("{:?}", record.state),
+            record.protocol,
+            record.packet_id,
+            record.frame.len(),
+        );
+        if hex {
+            for line in &record.data().iter().chunks(32) {
+                println!("    {:02x}", line.format(" "));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Feeds every inbound record to the packet handlers and returns
+/// how many of them errored.
+async fn replay(reader: CaptureReader<File>, config: &str) -> Result<usize> {
+    FalconConfig::init_config(config).with_context(|| format!("Could not load config \"{}\"", config))?;
+
+    let (shutdown, _finished_rx) = ShutdownHandle::new();
+    let (server_tx, mut server_rx) = unbounded_channel();
+    let mut connection = FalconConnection::new(shutdown, "127.0.0.1:0".parse()?, ServerWrapper::new(server_tx)).await;
+
+    let (mut handled, mut unhandled, mut failed) = (0, 0, 0);
+    for record in reader {
+        let record = record?;
+        if record.direction != Direction::Inbound {
+            continue;
+        }
+        // replay in the recorded state, the server side of the
+        // connection is not simulated
+        let state = connection.state_mut();
+        state.connection_state = record.state;
+        state.protocol_id = record.protocol;
+        if record.state == ConnectionState::Play && state.uuid.is_none() {
+            state.uuid = Some(Uuid::nil());
+        }
+
+        let mut data = record.data();
+        match falcon_receive::falcon_process_packet(record.packet_id, &mut data, &mut connection) {
+            Ok(true) => {
+                handled += 1;
+                if data.has_remaining() {
+                    println!("[{:>12.6}s] {:#04X}: {} trailing bytes", record.time.as_secs_f64(), record.packet_id, data.remaining());
+                }
+            },
+            Ok(false) => unhandled += 1,
+            Err(error) => {
+                failed += 1;
+                println!("[{:>12.6}s] {:#04X} ({:?}|{}): {:#}", record.time.as_secs_f64(), record.packet_id, record.state, record.protocol, error);
+            },
+        }
+    }
+
+    let mut server_tasks = 0;
+    while server_rx.try_recv().is_ok() {
+        server_tasks += 1;
+    }
+    println!("Replayed {} packets: {} unknown, {} failed, {} server tasks queued", handled + unhandled + failed, unhandled, failed, server_tasks);
+    Ok(failed)
+}
@@ -10,14 +10,24 @@ pub enum FalconCoreError {
     IoError(#[from] std::io::Error),
     #[error("The packet length was longer than 21 bits")]
     PacketTooLong,
+    #[error("Packet of {0} bytes exceeds the maximum of {1} bytes in this state")]
+    PacketTooLarge(usize, usize),
+    #[error("Compressed packet declared an invalid uncompressed size of {0} bytes")]
+    InvalidUncompressedSize(i32),
+    #[error("Could not decompress packet data")]
+    InvalidCompression,
     #[error("Schematic version {0} is not supported")]
     InvalidSchematic(i32),
-    #[error("Invalid schematic data version, should be {0} instead of {1}")]
-    WrongDataVersion(i32, i32),
+    #[error("Data version {0} is not supported")]
+    UnsupportedDataVersion(i32),
     #[error("Invalid schematic data was found: {0}")]
     InvalidData(String),
     #[error("Could not find the correct schematic blockdata")]
     MissingData,
+    #[error("Could not read nbt data")]
+    FastNbtError(#[from] fastnbt::error::Error),
     #[error("Error while reading block data")]
     ParseBlockError(#[from] ParseBlockError),
+    #[error("Could not read json data")]
+    JsonError(#[from] serde_json::Error),
 }
@@ -7,6 +7,12 @@ use uuid::Uuid;
 pub mod util;
 
 pub const UNKNOWN_PROTOCOL: i32 = -1;
+/// The largest frame the protocol allows, a packet length
+/// is at most a 3 byte VarInt (2^21 - 1).
+pub const MAX_PACKET_LEN: usize = (1 << 21) - 1;
+/// The largest size a compressed packet is allowed to declare
+/// for its uncompressed data (8 MiB).
+pub const MAX_UNCOMPRESSED_LEN: usize = 1 << 23;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct PacketHandlerState {
@@ -39,3 +45,18 @@ pub enum ConnectionState {
     Play,
     Disconnected,
 }
+
+impl ConnectionState {
+    /// The maximum frame length a client is allowed to send
+    /// while in this state.
+    pub fn max_packet_len(&self) -> usize {
+        match self {
+            // handshake address strings can carry proxy forwarding data
+            ConnectionState::Handshake => 2048,
+            ConnectionState::Status => 64,
+            ConnectionState::Login => 4096,
+            ConnectionState::Play => MAX_PACKET_LEN,
+            ConnectionState::Disconnected => 0,
+        }
+    }
+}
@@ -0,0 +1,759 @@
+//! MCEdit / WorldEdit `.schematic` files from before 1.13, these store
+//! numeric block ids and metadata instead of block states
+//! (`Materials: Alpha`).
+
+use std::collections::HashMap;
+
+use ahash::AHashMap;
+use fastnbt::borrow::ByteArray;
+use fastnbt::Value;
+use serde::Deserialize;
+
+use crate::error::FalconCoreError;
+use crate::schematic::{write_var_i32, SchematicData, REQUIRED_DATA_VERSION};
+use crate::world::block_entity::BlockEntity;
+use crate::world::blocks::Blocks;
+use crate::world::upgrade::BlockStateUpgrader;
+
+const ALPHA_MATERIALS: &str = "Alpha";
+
+const COLORS: [&str; 16] =
+    ["white", "orange", "magenta", "light_blue", "yellow", "lime", "pink", "gray", "light_gray", "cyan", "purple", "blue", "brown", "green", "red", "black"];
+const WOODS: [&str; 6] = ["oak", "spruce", "birch", "jungle", "acacia", "dark_oak"];
+const STONE_SLABS: [&str; 8] = ["smooth_stone", "sandstone", "petrified_oak", "cobblestone", "brick", "stone_brick", "nether_brick", "quartz"];
+/// Floor and wall skulls by their `SkullType`.
+const SKULLS: [(&str, &str); 6] = [
+    ("skeleton_skull", "skeleton_wall_skull"),
+    ("wither_skeleton_skull", "wither_skeleton_wall_skull"),
+    ("zombie_head", "zombie_wall_head"),
+    ("player_head", "player_wall_head"),
+    ("creeper_head", "creeper_wall_head"),
+    ("dragon_head", "dragon_wall_head"),
+];
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LegacySchematicRaw<'a> {
+    width: i16,
+    height: i16,
+    length: i16,
+    materials: String,
+    #[serde(borrow)]
+    blocks: ByteArray<'a>,
+    #[serde(borrow)]
+    data: ByteArray<'a>,
+    /// Upper 4 bits of block ids above 255, two blocks per byte.
+    #[serde(borrow)]
+    add_blocks: Option<ByteArray<'a>>,
+    #[serde(rename = "WEOffsetX")]
+    offset_x: Option<i32>,
+    #[serde(rename = "WEOffsetY")]
+    offset_y: Option<i32>,
+    #[serde(rename = "WEOffsetZ")]
+    offset_z: Option<i32>,
+    tile_entities: Option<Vec<Value>>,
+}
+
+/// A legacy schematic converted to block states, it owns the
+/// varint encoded block data that [`SchematicData`] borrows.
+pub struct LegacySchematic {
+    width: u16,
+    height: u16,
+    length: u16,
+    offset: [i32; 3],
+    palette: AHashMap<i32, Blocks>,
+    block_data: Vec<i8>,
+    block_entities: Vec<BlockEntity>,
+}
+
+impl LegacySchematic {
+    /// Converts a raw legacy schematic, id and metadata pairs without
+    /// an equivalent are replaced by `fallback`.
+    pub fn from_raw(raw: LegacySchematicRaw, fallback: Blocks) -> Result<Self, FalconCoreError> {
+        if raw.materials != ALPHA_MATERIALS {
+            return Err(FalconCoreError::InvalidData(format!("Unsupported schematic materials \"{}\"", raw.materials)));
+        }
+        let (width, height, length) = (raw.width as u16, raw.height as u16, raw.length as u16);
+        let volume = width as usize * height as usize * length as usize;
+        if raw.blocks.iter().count() != volume || raw.data.iter().count() != volume {
+            return Err(FalconCoreError::InvalidData(String::from("Block data doesn't match the schematic size")));
+        }
+        let add_blocks: Vec<u8> = raw.add_blocks.map(|add| add.iter().map(|b| b as u8).collect()).unwrap_or_default();
+        let tile_entities = raw.tile_entities.unwrap_or_default();
+        let tile_entity_at: AHashMap<usize, &HashMap<String, Value>> = tile_entities
+            .iter()
+            .filter_map(|nbt| {
+                let data = match nbt {
+                    Value::Compound(data) => data,
+                    _ => return None,
+                };
+                let (x, y, z) = (int(data.get("x")?)?, int(data.get("y")?)?, int(data.get("z")?)?);
+                if x < 0 || y < 0 || z < 0 || x >= width as i32 || y >= height as i32 || z >= length as i32 {
+                    return None;
+                }
+                Some(((y as usize * length as usize + z as usize) * width as usize + x as usize, data))
+            })
+            .collect();
+
+        let mut upgrader = BlockStateUpgrader::new(REQUIRED_DATA_VERSION, fallback);
+        let mut indices = AHashMap::new();
+        let mut tile_entity_indices = AHashMap::new();
+        let mut palette = AHashMap::new();
+        let mut block_data = Vec::with_capacity(volume);
+        for (i, (id, meta)) in raw.blocks.iter().zip(raw.data.iter()).enumerate() {
+            let add = add_blocks.get(i >> 1).map_or(0, |add| {
+                if i & 1 == 0 {
+                    add & 0x0F
+                } else {
+                    add >> 4
+                }
+            });
+            let id = ((add as u16) << 8) | id as u8 as u16;
+            let meta = meta as u8 & 0x0F;
+            let index = match tile_entity_at.get(&i).and_then(|data| tile_entity_state(id, meta, data)) {
+                Some(state) => *tile_entity_indices
+                    .entry(state)
+                    .or_insert_with_key(|state| push(&mut palette, upgrader.parse(state))),
+                None => *indices.entry((id, meta)).or_insert_with(|| {
+                    let block = match legacy_state(id, meta) {
+                        Some(state) => upgrader.parse(&state),
+                        None => upgrader.unknown(format!("{}:{}", id, meta)),
+                    };
+                    push(&mut palette, block)
+                }),
+            };
+            write_var_i32(index, &mut block_data);
+        }
+        upgrader.log_unknown();
+
+        Ok(LegacySchematic {
+            width,
+            height,
+            length,
+            offset: [raw.offset_x.unwrap_or(0), raw.offset_y.unwrap_or(0), raw.offset_z.unwrap_or(0)],
+            palette,
+            block_data,
+            block_entities: tile_entities
+                .into_iter()
+                .map(upgrade_tile_entity)
+                .filter_map(BlockEntity::from_nbt)
+                .collect(),
+        })
+    }
+
+    pub fn data(&self) -> SchematicData<'_> {
+        SchematicData::new(self.width, self.height, self.length, self.offset, self.palette.clone(), ByteArray::new(&self.block_data))
+            .with_block_entities(self.block_entities.clone())
+    }
+}
+
+fn push(palette: &mut AHashMap<i32, Blocks>, block: Blocks) -> i32 {
+    let index = palette.len() as i32;
+    palette.insert(index, block);
+    index
+}
+
+fn int(value: &Value) -> Option<i32> {
+    match value {
+        Value::Byte(v) => Some(*v as i32),
+        Value::Short(v) => Some(*v as i32),
+        Value::Int(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Skulls and banners keep their type, rotation and color in their tile
+/// entity, `None` if the metadata alone determines the state. Legacy
+/// colors count from black, unlike the dye ids.
+fn tile_entity_state(id: u16, meta: u8, data: &HashMap<String, Value>) -> Option<String> {
+    match id {
+        144 => {
+            let (floor, wall) = SKULLS[data.get("SkullType").and_then(int).unwrap_or(0).clamp(0, 5) as usize];
+            match meta & 7 {
+                2..=5 => Some(format!("{}[facing={}]", wall, wall_facing(meta))),
+                _ => Some(format!("{}[rotation={}]", floor, data.get("Rot").and_then(int).unwrap_or(0) & 15)),
+            }
+        },
+        176 | 177 => {
+            let color = COLORS[15 - (data.get("Base").and_then(int).unwrap_or(0) & 15) as usize];
+            match id {
+                176 => Some(format!("{}_banner[rotation={}]", color, meta)),
+                _ => Some(format!("{}_wall_banner[facing={}]", color, wall_facing(meta))),
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Drops the skull and banner data that moved to the block state and
+/// converts the legacy pattern colors to dye ids.
+fn upgrade_tile_entity(nbt: Value) -> Value {
+    let mut data = match nbt {
+        Value::Compound(data) => data,
+        nbt => return nbt,
+    };
+    match data.get("id") {
+        Some(Value::String(id)) if id == "Skull" || id == "minecraft:skull" => {
+            data.remove("SkullType");
+            data.remove("Rot");
+        },
+        Some(Value::String(id)) if id == "Banner" || id == "minecraft:banner" => {
+            data.remove("Base");
+            if let Some(Value::List(patterns)) = data.get_mut("Patterns") {
+                for pattern in patterns {
+                    if let Value::Compound(pattern) = pattern {
+                        if let Some(color) = pattern.get("Color").and_then(int) {
+                            pattern.insert(String::from("Color"), Value::Int(15 - (color & 15)));
+                        }
+                    }
+                }
+            }
+        },
+        _ => {},
+    }
+    Value::Compound(data)
+}
+
+/// `0 south, 1 west, 2 north, 3 east`, used by most horizontal blocks.
+fn horizontal(meta: u8) -> &'static str { ["south", "west", "north", "east"][meta as usize & 3] }
+
+/// `2 north, 3 south, 4 west, 5 east`, used by chests, furnaces, ladders etc.
+fn wall_facing(meta: u8) -> &'static str {
+    match meta & 7 {
+        3 => "south",
+        4 => "west",
+        5 => "east",
+        _ => "north",
+    }
+}
+
+fn facing(meta: u8) -> &'static str {
+    match meta & 7 {
+        0 => "down",
+        2 => "north",
+        3 => "south",
+        4 => "west",
+        5 => "east",
+        _ => "up",
+    }
+}
+
+fn axis(meta: u8) -> &'static str {
+    match (meta >> 2) & 3 {
+        1 => "x",
+        2 => "z",
+        _ => "y",
+    }
+}
+
+fn stairs(name: &str, meta: u8) -> String {
+    let facing = ["east", "west", "south", "north"][meta as usize & 3];
+    format!(
+        "{}[facing={},half={}]",
+        name,
+        facing,
+        if meta & 4 != 0 {
+            "top"
+        } else {
+            "bottom"
+        }
+    )
+}
+
+fn slab(name: &str, meta: u8) -> String {
+    format!(
+        "{}_slab[type={}]",
+        name,
+        if meta & 8 != 0 {
+            "top"
+        } else {
+            "bottom"
+        }
+    )
+}
+
+fn door(name: &str, meta: u8) -> String {
+    if meta & 8 != 0 {
+        format!(
+            "{}[half=upper,hinge={},powered={}]",
+            name,
+            if meta & 1 != 0 {
+                "right"
+            } else {
+                "left"
+            },
+            meta & 2 != 0
+        )
+    } else {
+        let facing = ["east", "south", "west", "north"][meta as usize & 3];
+        format!("{}[half=lower,facing={},open={}]", name, facing, meta & 4 != 0)
+    }
+}
+
+fn trapdoor(name: &str, meta: u8) -> String {
+    let facing = ["north", "south", "west", "east"][meta as usize & 3];
+    format!(
+        "{}[facing={},open={},half={}]",
+        name,
+        facing,
+        meta & 4 != 0,
+        if meta & 8 != 0 {
+            "top"
+        } else {
+            "bottom"
+        }
+    )
+}
+
+fn fence_gate(name: &str, meta: u8) -> String { format!("{}[facing={},open={},powered={}]", name, horizontal(meta), meta & 4 != 0, meta & 8 != 0) }
+
+fn torch(name: &str, wall_name: &str, meta: u8, lit: Option<bool>) -> String {
+    let facing = match meta {
+        1 => "east",
+        2 => "west",
+        3 => "south",
+        4 => "north",
+        _ => return lit.map_or_else(|| name.to_owned(), |lit| format!("{}[lit={}]", name, lit)),
+    };
+    match lit {
+        Some(lit) => format!("{}[facing={},lit={}]", wall_name, facing, lit),
+        None => format!("{}[facing={}]", wall_name, facing),
+    }
+}
+
+fn button(name: &str, meta: u8) -> String {
+    let (face, facing) = match meta & 7 {
+        0 => ("ceiling", "north"),
+        1 => ("wall", "east"),
+        2 => ("wall", "west"),
+        3 => ("wall", "south"),
+        4 => ("wall", "north"),
+        _ => ("floor", "north"),
+    };
+    format!("{}[face={},facing={},powered={}]", name, face, facing, meta & 8 != 0)
+}
+
+fn rail(name: &str, meta: u8, powered: bool) -> String {
+    const SHAPES: [&str; 10] = [
+        "north_south",
+        "east_west",
+        "ascending_east",
+        "ascending_west",
+        "ascending_north",
+        "ascending_south",
+        "south_east",
+        "south_west",
+        "north_west",
+        "north_east",
+    ];
+    if powered {
+        format!("{}[shape={},powered={}]", name, SHAPES[(meta & 7).min(5) as usize], meta & 8 != 0)
+    } else {
+        format!("{}[shape={}]", name, SHAPES[meta.min(9) as usize])
+    }
+}
+
+fn color(meta: u8) -> &'static str { COLORS[meta as usize & 15] }
+
+fn wood(meta: u8) -> &'static str { WOODS[(meta as usize & 7).min(5)] }
+
+/// The block state (as of [`REQUIRED_DATA_VERSION`]) of a numeric block id
+/// and its metadata, as they were used up to 1.12.
+pub fn legacy_state(id: u16, meta: u8) -> Option<String> {
+    let state = match id {
+        0 => String::from("air"),
+        1 => ["stone", "granite", "polished_granite", "diorite", "polished_diorite", "andesite", "polished_andesite"][meta.min(6) as usize].to_owned(),
+        2 => String::from("grass_block"),
+        3 => ["dirt", "coarse_dirt", "podzol"][meta.min(2) as usize].to_owned(),
+        4 => String::from("cobblestone"),
+        5 => format!("{}_planks", wood(meta)),
+        6 => format!("{}_sapling[stage={}]", wood(meta), (meta >> 3) & 1),
+        7 => String::from("bedrock"),
+        8 | 9 => format!("water[level={}]", meta),
+        10 | 11 => format!("lava[level={}]", meta),
+        12 => ["sand", "red_sand"][meta.min(1) as usize].to_owned(),
+        13 => String::from("gravel"),
+        14 => String::from("gold_ore"),
+        15 => String::from("iron_ore"),
+        16 => String::from("coal_ore"),
+        17 | 162 => {
+            let wood = if id == 17 {
+                WOODS[meta as usize & 3]
+            } else {
+                WOODS[4 + (meta as usize & 1)]
+            };
+            if meta >> 2 == 3 {
+                format!("{}_wood[axis=y]", wood)
+            } else {
+                format!("{}_log[axis={}]", wood, axis(meta))
+            }
+        },
+        18 | 161 => {
+            let wood = if id == 18 {
+                WOODS[meta as usize & 3]
+            } else {
+                WOODS[4 + (meta as usize & 1)]
+            };
+            format!("{}_leaves[persistent={}]", wood, meta & 4 != 0)
+        },
+        19 => ["sponge", "wet_sponge"][meta.min(1) as usize].to_owned(),
+        20 => String::from("glass"),
+        21 => String::from("lapis_ore"),
+        22 => String::from("lapis_block"),
+        23 => format!("dispenser[facing={},triggered={}]", facing(meta), meta & 8 != 0),
+        24 => ["sandstone", "chiseled_sandstone", "cut_sandstone"][meta.min(2) as usize].to_owned(),
+        25 => String::from("note_block"),
+        26 => format!(
+            "red_bed[facing={},occupied={},part={}]",
+            horizontal(meta),
+            meta & 4 != 0,
+            if meta & 8 != 0 {
+                "head"
+            } else {
+                "foot"
+            }
+        ),
+        27 => rail("powered_rail", meta, true),
+        28 => rail("detector_rail", meta, true),
+        29 => format!("sticky_piston[facing={},extended={}]", facing(meta), meta & 8 != 0),
+        30 => String::from("cobweb"),
+        31 => ["dead_bush", "grass", "fern"][meta.min(2) as usize].to_owned(),
+        32 => String::from("dead_bush"),
+        33 => format!("piston[facing={},extended={}]", facing(meta), meta & 8 != 0),
+        34 => format!(
+            "piston_head[facing={},type={}]",
+            facing(meta),
+            if meta & 8 != 0 {
+                "sticky"
+            } else {
+                "normal"
+            }
+        ),
+        35 => format!("{}_wool", color(meta)),
+        36 => format!("moving_piston[facing={}]", facing(meta)),
+        37 => String::from("dandelion"),
+        38 => ["poppy", "blue_orchid", "allium", "azure_bluet", "red_tulip", "orange_tulip", "white_tulip", "pink_tulip", "oxeye_daisy"][meta.min(8) as usize]
+            .to_owned(),
+        39 => String::from("brown_mushroom"),
+        40 => String::from("red_mushroom"),
+        41 => String::from("gold_block"),
+        42 => String::from("iron_block"),
+        43 => match meta {
+            8 => String::from("smooth_stone"),
+            9 => String::from("smooth_sandstone"),
+            15 => String::from("smooth_quartz"),
+            _ => format!("{}_slab[type=double]", STONE_SLABS[meta as usize & 7]),
+        },
+        44 => slab(STONE_SLABS[meta as usize & 7], meta),
+        45 => String::from("bricks"),
+        46 => String::from("tnt"),
+        47 => String::from("bookshelf"),
+        48 => String::from("mossy_cobblestone"),
+        49 => String::from("obsidian"),
+        50 => torch("torch", "wall_torch", meta, None),
+        51 => format!("fire[age={}]", meta),
+        52 => String::from("spawner"),
+        53 => stairs("oak_stairs", meta),
+        54 => format!("chest[facing={}]", wall_facing(meta)),
+        55 => format!("redstone_wire[power={}]", meta),
+        56 => String::from("diamond_ore"),
+        57 => String::from("diamond_block"),
+        58 => String::from("crafting_table"),
+        59 => format!("wheat[age={}]", meta & 7),
+        60 => format!("farmland[moisture={}]", meta & 7),
+        61 | 62 => format!("furnace[facing={},lit={}]", wall_facing(meta), id == 62),
+        63 => format!("oak_sign[rotation={}]", meta),
+        64 => door("oak_door", meta),
+        65 => format!("ladder[facing={}]", wall_facing(meta)),
+        66 => rail("rail", meta, false),
+        67 => stairs("cobblestone_stairs", meta),
+        68 => format!("oak_wall_sign[facing={}]", wall_facing(meta)),
+        69 => {
+            let (face, facing) = match meta & 7 {
+                0 => ("ceiling", "west"),
+                1 => ("wall", "east"),
+                2 => ("wall", "west"),
+                3 => ("wall", "south"),
+                4 => ("wall", "north"),
+                5 => ("floor", "north"),
+                6 => ("floor", "west"),
+                _ => ("ceiling", "north"),
+            };
+            format!("lever[face={},facing={},powered={}]", face, facing, meta & 8 != 0)
+        },
+        70 => format!("stone_pressure_plate[powered={}]", meta & 1 != 0),
+        71 => door("iron_door", meta),
+        72 => format!("oak_pressure_plate[powered={}]", meta & 1 != 0),
+        73 | 74 => format!("redstone_ore[lit={}]", id == 74),
+        75 | 76 => torch("redstone_torch", "redstone_wall_torch", meta, Some(id == 76)),
+        77 => button("stone_button", meta),
+        78 => format!("snow[layers={}]", (meta & 7) + 1),
+        79 => String::from("ice"),
+        80 => String::from("snow_block"),
+        81 => format!("cactus[age={}]", meta),
+        82 => String::from("clay"),
+        83 => format!("sugar_cane[age={}]", meta),
+        84 => String::from("jukebox"),
+        85 => String::from("oak_fence"),
+        86 => format!("carved_pumpkin[facing={}]", horizontal(meta)),
+        87 => String::from("netherrack"),
+        88 => String::from("soul_sand"),
+        89 => String::from("glowstone"),
+        90 => format!(
+            "nether_portal[axis={}]",
+            if meta == 2 {
+                "z"
+            } else {
+                "x"
+            }
+        ),
+        91 => format!("jack_o_lantern[facing={}]", horizontal(meta)),
+        92 => format!("cake[bites={}]", meta.min(6)),
+        93 | 94 => format!("repeater[facing={},delay={},powered={}]", horizontal(meta), (meta >> 2) + 1, id == 94),
+        95 => format!("{}_stained_glass", color(meta)),
+        96 => trapdoor("oak_trapdoor", meta),
+        97 => [
+            "infested_stone",
+            "infested_cobblestone",
+            "infested_stone_bricks",
+            "infested_mossy_stone_bricks",
+            "infested_cracked_stone_bricks",
+            "infested_chiseled_stone_bricks",
+        ][meta.min(5) as usize]
+            .to_owned(),
+        98 => ["stone_bricks", "mossy_stone_bricks", "cracked_stone_bricks", "chiseled_stone_bricks"][meta as usize & 3].to_owned(),
+        99 | 100 => {
+            let name = if id == 99 {
+                "brown_mushroom_block"
+            } else {
+                "red_mushroom_block"
+            };
+            match meta {
+                0 => format!("{}[north=false,east=false,south=false,west=false,up=false,down=false]", name),
+                10 => String::from("mushroom_stem[up=false,down=false]"),
+                15 => String::from("mushroom_stem"),
+                _ => name.to_owned(),
+            }
+        },
+        101 => String::from("iron_bars"),
+        102 => String::from("glass_pane"),
+        103 => String::from("melon"),
+        104 => format!("pumpkin_stem[age={}]", meta & 7),
+        105 => format!("melon_stem[age={}]", meta & 7),
+        106 => format!("vine[south={},west={},north={},east={},up={}]", meta & 1 != 0, meta & 2 != 0, meta & 4 != 0, meta & 8 != 0, meta == 0),
+        107 => fence_gate("oak_fence_gate", meta),
+        108 => stairs("brick_stairs", meta),
+        109 => stairs("stone_brick_stairs", meta),
+        110 => String::from("mycelium"),
+        111 => String::from("lily_pad"),
+        112 => String::from("nether_bricks"),
+        113 => String::from("nether_brick_fence"),
+        114 => stairs("nether_brick_stairs", meta),
+        115 => format!("nether_wart[age={}]", meta & 3),
+        116 => String::from("enchanting_table"),
+        117 => format!("brewing_stand[has_bottle_0={},has_bottle_1={},has_bottle_2={}]", meta & 1 != 0, meta & 2 != 0, meta & 4 != 0),
+        118 => format!("cauldron[level={}]", meta & 3),
+        119 => String::from("end_portal"),
+        120 => format!("end_portal_frame[facing={},eye={}]", horizontal(meta), meta & 4 != 0),
+        121 => String::from("end_stone"),
+        122 => String::from("dragon_egg"),
+        123 | 124 => format!("redstone_lamp[lit={}]", id == 124),
+        125 => format!("{}_slab[type=double]", wood(meta)),
+        126 => slab(wood(meta), meta),
+        127 => format!("cocoa[facing={},age={}]", horizontal(meta), (meta >> 2).min(2)),
+        128 => stairs("sandstone_stairs", meta),
+        129 => String::from("emerald_ore"),
+        130 => format!("ender_chest[facing={}]", wall_facing(meta)),
+        131 => format!("tripwire_hook[facing={},attached={},powered={}]", horizontal(meta), meta & 4 != 0, meta & 8 != 0),
+        132 => format!("tripwire[powered={},attached={},disarmed={}]", meta & 1 != 0, meta & 4 != 0, meta & 8 != 0),
+        133 => String::from("emerald_block"),
+        134 => stairs("spruce_stairs", meta),
+        135 => stairs("birch_stairs", meta),
+        136 => stairs("jungle_stairs", meta),
+        137 | 210 | 211 => {
+            let name = match id {
+                137 => "command_block",
+                210 => "repeating_command_block",
+                _ => "chain_command_block",
+            };
+            format!("{}[facing={},conditional={}]", name, facing(meta), meta & 8 != 0)
+        },
+        138 => String::from("beacon"),
+        139 => ["cobblestone_wall", "mossy_cobblestone_wall"][meta.min(1) as usize].to_owned(),
+        140 => String::from("flower_pot"),
+        141 => format!("carrots[age={}]", meta & 7),
+        142 => format!("potatoes[age={}]", meta & 7),
+        143 => button("oak_button", meta),
+        144 => match meta & 7 {
+            2..=5 => format!("skeleton_wall_skull[facing={}]", wall_facing(meta)),
+            _ => String::from("skeleton_skull"),
+        },
+        145 => format!("{}[facing={}]", ["anvil", "chipped_anvil", "damaged_anvil"][(meta >> 2).min(2) as usize], horizontal(meta)),
+        146 => format!("trapped_chest[facing={}]", wall_facing(meta)),
+        147 => format!("light_weighted_pressure_plate[power={}]", meta),
+        148 => format!("heavy_weighted_pressure_plate[power={}]", meta),
+        149 | 150 => format!(
+            "comparator[facing={},mode={},powered={}]",
+            horizontal(meta),
+            if meta & 4 != 0 {
+                "subtract"
+            } else {
+                "compare"
+            },
+            meta & 8 != 0 || id == 150
+        ),
+        151 | 178 => format!("daylight_detector[power={},inverted={}]", meta, id == 178),
+        152 => String::from("redstone_block"),
+        153 => String::from("nether_quartz_ore"),
+        154 => {
+            let facing = match facing(meta) {
+                "up" => "down",
+                facing => facing,
+            };
+            format!("hopper[facing={},enabled={}]", facing, meta & 8 == 0)
+        },
+        155 => match meta {
+            1 => String::from("chiseled_quartz_block"),
+            2 => String::from("quartz_pillar[axis=y]"),
+            3 => String::from("quartz_pillar[axis=x]"),
+            4 => String::from("quartz_pillar[axis=z]"),
+            _ => String::from("quartz_block"),
+        },
+        156 => stairs("quartz_stairs", meta),
+        157 => rail("activator_rail", meta, true),
+        158 => format!("dropper[facing={},triggered={}]", facing(meta), meta & 8 != 0),
+        159 => format!("{}_terracotta", color(meta)),
+        160 => format!("{}_stained_glass_pane", color(meta)),
+        163 => stairs("acacia_stairs", meta),
+        164 => stairs("dark_oak_stairs", meta),
+        165 => String::from("slime_block"),
+        166 => String::from("barrier"),
+        167 => trapdoor("iron_trapdoor", meta),
+        168 => ["prismarine", "prismarine_bricks", "dark_prismarine"][meta.min(2) as usize].to_owned(),
+        169 => String::from("sea_lantern"),
+        170 => format!("hay_block[axis={}]", axis(meta)),
+        171 => format!("{}_carpet", color(meta)),
+        172 => String::from("terracotta"),
+        173 => String::from("coal_block"),
+        174 => String::from("packed_ice"),
+        175 => {
+            if meta & 8 != 0 {
+                // the upper half only knows its variant through the lower half
+                String::from("sunflower[half=upper]")
+            } else {
+                let name = ["sunflower", "lilac", "tall_grass", "large_fern", "rose_bush", "peony"][(meta & 7).min(5) as usize];
+                format!("{}[half=lower]", name)
+            }
+        },
+        176 => format!("white_banner[rotation={}]", meta),
+        177 => format!("white_wall_banner[facing={}]", wall_facing(meta)),
+        179 => ["red_sandstone", "chiseled_red_sandstone", "cut_red_sandstone"][meta.min(2) as usize].to_owned(),
+        180 => stairs("red_sandstone_stairs", meta),
+        181 => match meta & 8 {
+            0 => String::from("red_sandstone_slab[type=double]"),
+            _ => String::from("smooth_red_sandstone"),
+        },
+        182 => slab("red_sandstone", meta),
+        183 => fence_gate("spruce_fence_gate", meta),
+        184 => fence_gate("birch_fence_gate", meta),
+        185 => fence_gate("jungle_fence_gate", meta),
+        186 => fence_gate("dark_oak_fence_gate", meta),
+        187 => fence_gate("acacia_fence_gate", meta),
+        188 => String::from("spruce_fence"),
+        189 => String::from("birch_fence"),
+        190 => String::from("jungle_fence"),
+        191 => String::from("dark_oak_fence"),
+        192 => String::from("acacia_fence"),
+        193 => door("spruce_door", meta),
+        194 => door("birch_door", meta),
+        195 => door("jungle_door", meta),
+        196 => door("acacia_door", meta),
+        197 => door("dark_oak_door", meta),
+        198 => format!("end_rod[facing={}]", facing(meta)),
+        199 => String::from("chorus_plant"),
+        200 => format!("chorus_flower[age={}]", meta.min(5)),
+        201 => String::from("purpur_block"),
+        202 => format!("purpur_pillar[axis={}]", axis(meta)),
+        203 => stairs("purpur_stairs", meta),
+        204 => String::from("purpur_slab[type=double]"),
+        205 => slab("purpur", meta),
+        206 => String::from("end_stone_bricks"),
+        207 => format!("beetroots[age={}]", meta & 3),
+        208 => String::from("dirt_path"),
+        209 => String::from("end_gateway"),
+        212 => format!("frosted_ice[age={}]", meta & 3),
+        213 => String::from("magma_block"),
+        214 => String::from("nether_wart_block"),
+        215 => String::from("red_nether_bricks"),
+        216 => format!("bone_block[axis={}]", axis(meta)),
+        217 => String::from("structure_void"),
+        218 => format!("observer[facing={},powered={}]", facing(meta), meta & 8 != 0),
+        219..=234 => format!("{}_shulker_box[facing={}]", COLORS[id as usize - 219], facing(meta)),
+        235..=250 => format!("{}_glazed_terracotta[facing={}]", COLORS[id as usize - 235], horizontal(meta)),
+        251 => format!("{}_concrete", color(meta)),
+        252 => format!("{}_concrete_powder", color(meta)),
+        255 => format!("structure_block[mode={}]", ["save", "load", "corner", "data"][meta as usize & 3]),
+        _ => return None,
+    };
+    Some(state)
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use fastnbt::Value;
+
+    use super::{legacy_state, LegacySchematic, LegacySchematicRaw};
+    use crate::world::blocks::Blocks;
+
+    #[test]
+    pub fn test_mapping() {
+        for id in 0..=255 {
+            for meta in 0..16 {
+                if let Some(state) = legacy_state(id, meta) {
+                    assert!(Blocks::parse_state(&state).is_ok(), "{}:{} maps to invalid state {}", id, meta, state);
+                }
+            }
+        }
+        assert_eq!(legacy_state(17, 5).unwrap(), "spruce_log[axis=x]");
+        assert_eq!(legacy_state(44, 8).unwrap(), "smooth_stone_slab[type=top]");
+        assert_eq!(legacy_state(35, 14).unwrap(), "red_wool");
+        assert_eq!(legacy_state(253, 0), None);
+    }
+
+    #[test]
+    pub fn test_legacy_schematic() {
+        let data = include_bytes!("../../tests/fixtures/legacy.schematic");
+        let mut decompressed = Vec::new();
+        std::io::Read::read_to_end(&mut flate2::read::GzDecoder::new(&data[..]), &mut decompressed).unwrap();
+        let raw: LegacySchematicRaw = fastnbt::from_bytes(&decompressed).unwrap();
+        let legacy = LegacySchematic::from_raw(raw, Blocks::Air).unwrap();
+        let schematic = legacy.data();
+
+        assert_eq!((schematic.width, schematic.height, schematic.length), (2, 2, 2));
+        assert_eq!(schematic.offset, [-3, 64, 5]);
+        assert_eq!(schematic.palette.len(), 6);
+        let blocks: Vec<_> = schematic.block_data.iter().map(|b| schematic.palette[&(b as i32)]).collect();
+        assert_eq!(blocks[0], Blocks::Stone);
+        assert_eq!(blocks[3], Blocks::Granite);
+        assert_eq!(blocks[4], Blocks::from_str("player_head[rotation=4]").unwrap());
+        assert_eq!(blocks[5], Blocks::from_str("oak_log[axis=x]").unwrap());
+        assert_eq!(blocks[6], Blocks::from_str("red_banner[rotation=8]").unwrap());
+        assert_eq!(blocks[7], Blocks::Air);
+
+        let skull = schematic
+            .block_entities
+            .iter()
+            .find(|entity| entity.id() == "minecraft:skull")
+            .unwrap();
+        assert!(!skull.data().contains_key("SkullType"));
+        let banner = schematic
+            .block_entities
+            .iter()
+            .find(|entity| entity.id() == "minecraft:banner")
+            .unwrap();
+        let pattern = [(String::from("Pattern"), Value::String(String::from("cre"))), (String::from("Color"), Value::Int(5))];
+        assert_eq!(banner.data()["Patterns"], Value::List(vec![Value::Compound(pattern.into_iter().collect())]));
+    }
+}
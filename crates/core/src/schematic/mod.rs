@@ -1,30 +1,73 @@
 use std::borrow::Cow;
-use std::str::FromStr;
+use std::collections::BTreeMap;
+use std::io::Write;
 
 use ahash::AHashMap;
 use fastnbt::borrow::{ByteArray, IntArray};
-use serde::Deserialize;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
 
 use crate::error::FalconCoreError;
+use crate::world::biome::Biome;
+use crate::world::block_entity::BlockEntity;
 use crate::world::blocks::Blocks;
+use crate::world::entity::Entity;
+use crate::world::upgrade::{BlockStateUpgrader, SUPPORTED_DATA_VERSIONS};
 
+pub mod legacy;
+
+/// The data version [`Blocks`] corresponds to, other versions get upgraded.
 pub const REQUIRED_DATA_VERSION: i32 = 2730;
 
+/// Raw schematic nbt, covers both version 2 and version 3.
+///
+/// Version 3 nests everything in a `Schematic` compound and moves the
+/// block palette, data and block entities into `Blocks` and the biomes
+/// into `Biomes`.
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct SchematicVersionedRaw<'a> {
+    #[serde(borrow)]
+    schematic: Option<Box<SchematicVersionedRaw<'a>>>,
     // version
-    version: i32,
+    version: Option<i32>,
     data_version: Option<i32>,
     // mutual data
-    width: i16,
-    height: i16,
-    length: i16,
+    width: Option<i16>,
+    height: Option<i16>,
+    length: Option<i16>,
     #[serde(borrow)]
     offset: Option<IntArray<'a>>,
-    palette: AHashMap<Cow<'a, str>, i32>,
+    // version 2
+    #[serde(borrow)]
+    palette: Option<AHashMap<Cow<'a, str>, i32>>,
     #[serde(borrow)]
     block_data: Option<ByteArray<'a>>,
+    #[serde(borrow)]
+    biome_palette: Option<AHashMap<Cow<'a, str>, i32>>,
+    #[serde(borrow)]
+    biome_data: Option<ByteArray<'a>>,
+    /// Called `TileEntities` by version 1.
+    #[serde(alias = "TileEntities")]
+    block_entities: Option<Vec<fastnbt::Value>>,
+    entities: Option<Vec<fastnbt::Value>>,
+    // version 3
+    #[serde(borrow)]
+    blocks: Option<PaletteContainerRaw<'a>>,
+    #[serde(borrow)]
+    biomes: Option<PaletteContainerRaw<'a>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PaletteContainerRaw<'a> {
+    #[serde(borrow)]
+    palette: AHashMap<Cow<'a, str>, i32>,
+    #[serde(borrow)]
+    data: ByteArray<'a>,
+    block_entities: Option<Vec<fastnbt::Value>>,
 }
 
 pub struct SchematicData<'a> {
@@ -34,6 +77,20 @@ pub struct SchematicData<'a> {
     pub offset: [i32; 3],
     pub palette: AHashMap<i32, Blocks>,
     pub block_data: ByteArray<'a>,
+    pub biomes: Option<SchematicBiomes<'a>>,
+    /// Positioned relative to the schematic.
+    pub block_entities: Vec<BlockEntity>,
+    /// Positioned relative to the schematic.
+    pub entities: Vec<Entity>,
+}
+
+/// Biome names and their varint encoded indices.
+pub struct SchematicBiomes<'a> {
+    pub palette: AHashMap<i32, Cow<'a, str>>,
+    pub data: ByteArray<'a>,
+    /// Version 2 stores a biome per column (`x + z * width`),
+    /// version 3 a biome per block like the block data.
+    pub per_block: bool,
 }
 
 impl<'a> SchematicData<'a> {
@@ -45,30 +102,61 @@ impl<'a> SchematicData<'a> {
             offset,
             palette,
             block_data,
+            biomes: None,
+            block_entities: Vec::new(),
+            entities: Vec::new(),
         }
     }
-}
 
-impl<'a> TryFrom<SchematicVersionedRaw<'a>> for SchematicData<'a> {
-    type Error = FalconCoreError;
+    pub fn with_biomes(mut self, biomes: SchematicBiomes<'a>) -> Self {
+        self.biomes = Some(biomes);
+        self
+    }
 
-    fn try_from(value: SchematicVersionedRaw<'a>) -> std::result::Result<Self, Self::Error> {
-        if value.version != 2 {
-            return Err(FalconCoreError::InvalidSchematic(value.version));
+    pub fn with_block_entities(mut self, block_entities: Vec<BlockEntity>) -> Self {
+        self.block_entities = block_entities;
+        self
+    }
+
+    pub fn with_entities(mut self, entities: Vec<Entity>) -> Self {
+        self.entities = entities;
+        self
+    }
+
+    /// Converts a raw schematic, block states that are unknown
+    /// to this data version are replaced by `fallback`.
+    pub fn from_raw(value: SchematicVersionedRaw<'a>, fallback: Blocks) -> std::result::Result<Self, FalconCoreError> {
+        if let Some(schematic) = value.schematic {
+            return SchematicData::from_raw(*schematic, fallback);
         }
-        match value.data_version {
-            Some(content) => {
-                if content != REQUIRED_DATA_VERSION {
-                    return Err(FalconCoreError::WrongDataVersion(REQUIRED_DATA_VERSION, content));
-                }
-            },
-            None => return Err(FalconCoreError::MissingData),
+        let version = value.version.ok_or(FalconCoreError::MissingData)?;
+        let data_version = value.data_version.ok_or(FalconCoreError::MissingData)?;
+        if !SUPPORTED_DATA_VERSIONS.contains(&data_version) {
+            return Err(FalconCoreError::UnsupportedDataVersion(data_version));
         }
-        let block_data = {
-            match value.block_data {
-                Some(data) => data,
-                None => return Err(FalconCoreError::MissingData),
-            }
+        let (palette, block_data, biomes, block_entities) = match version {
+            2 => {
+                let biomes = match (value.biome_palette, value.biome_data) {
+                    (Some(palette), Some(data)) => Some((palette, data, false)),
+                    _ => None,
+                };
+                (value.palette, value.block_data, biomes, value.block_entities)
+            },
+            3 => match value.blocks {
+                Some(blocks) => {
+                    (Some(blocks.palette), Some(blocks.data), value.biomes.map(|biomes| (biomes.palette, biomes.data, true)), blocks.block_entities)
+                },
+                None => (None, None, None, None),
+            },
+            _ => return Err(FalconCoreError::InvalidSchematic(version)),
+        };
+        let (width, height, length) = match (value.width, value.height, value.length) {
+            (Some(width), Some(height), Some(length)) => (width as u16, height as u16, length as u16),
+            _ => return Err(FalconCoreError::MissingData),
+        };
+        let (palette, block_data) = match (palette, block_data) {
+            (Some(palette), Some(data)) => (palette, data),
+            _ => return Err(FalconCoreError::MissingData),
         };
 
         let mut effective_offset = [0; 3];
@@ -79,11 +167,245 @@ impl<'a> TryFrom<SchematicVersionedRaw<'a>> for SchematicData<'a> {
             offset.iter().enumerate().for_each(|(i, x)| effective_offset[i] = x);
         }
 
-        let mut effective_palette = AHashMap::new();
-        for (state, index) in value.palette {
-            effective_palette.insert(index, Blocks::from_str(state.as_ref())?);
+        let mut upgrader = BlockStateUpgrader::new(data_version, fallback);
+        let effective_palette = palette
+            .into_iter()
+            .map(|(state, index)| (index, upgrader.parse(state.as_ref())))
+            .collect();
+        upgrader.log_unknown();
+
+        let block_entities = block_entities
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(BlockEntity::from_schematic)
+            .collect();
+        let raw_entities = value.entities.unwrap_or_default();
+        let raw_count = raw_entities.len();
+        let entities: Vec<Entity> = raw_entities.into_iter().filter_map(Entity::from_schematic).collect();
+        if entities.len() < raw_count {
+            warn!(count = raw_count - entities.len(), "Skipped unsupported schematic entities");
+        }
+        let data = SchematicData::new(width, height, length, effective_offset, effective_palette, block_data)
+            .with_block_entities(block_entities)
+            .with_entities(entities);
+        Ok(match biomes {
+            Some((palette, data_biomes, per_block)) => data.with_biomes(SchematicBiomes {
+                palette: palette.into_iter().map(|(biome, index)| (index, biome)).collect(),
+                data: data_biomes,
+                per_block,
+            }),
+            None => data,
+        })
+    }
+}
+
+/// A Sponge version 2 schematic as it gets written.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct SchematicV2Out {
+    version: i32,
+    data_version: i32,
+    width: i16,
+    height: i16,
+    length: i16,
+    offset: fastnbt::IntArray,
+    palette_max: i32,
+    palette: AHashMap<String, i32>,
+    block_data: fastnbt::ByteArray,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    biome_palette_max: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    biome_palette: Option<AHashMap<String, i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    biome_data: Option<fastnbt::ByteArray>,
+    block_entities: Vec<fastnbt::Value>,
+    entities: Vec<fastnbt::Value>,
+}
+
+/// Writes a gzip compressed Sponge version 2 schematic of `size` (width,
+/// height and length) blocks, `block_at` is called for every block in `y`,
+/// `z`, `x` order. `biomes` holds a biome per column (`x + z * width`),
+/// block entities and entities are positioned relative to the schematic.
+pub fn write_sponge_v2<F>(
+    size: [u16; 3],
+    offset: [i32; 3],
+    mut block_at: F,
+    biomes: Option<&[Biome]>,
+    block_entities: &[BlockEntity],
+    entities: &[Entity],
+) -> Result<Vec<u8>, FalconCoreError>
+where
+    F: FnMut(u16, u16, u16) -> Blocks,
+{
+    let [width, height, length] = size;
+    let mut indices = BTreeMap::new();
+    let mut block_data = Vec::with_capacity(width as usize * height as usize * length as usize);
+    for y in 0..height {
+        for z in 0..length {
+            for x in 0..width {
+                let block = block_at(x, y, z);
+                let next = indices.len() as i32;
+                write_var_i32(*indices.entry(block).or_insert(next), &mut block_data);
+            }
+        }
+    }
+
+    let biomes = match biomes {
+        Some(biomes) if biomes.len() != width as usize * length as usize => {
+            return Err(FalconCoreError::InvalidData(format!("Expected {} biomes, got {}", width as usize * length as usize, biomes.len())));
+        },
+        Some(biomes) => {
+            let mut biome_indices = AHashMap::new();
+            let mut biome_data = Vec::with_capacity(biomes.len());
+            for biome in biomes {
+                let next = biome_indices.len() as i32;
+                write_var_i32(*biome_indices.entry(*biome).or_insert(next), &mut biome_data);
+            }
+            Some((biome_indices, biome_data))
+        },
+        None => None,
+    };
+
+    // the sizes are unsigned, nbt only has signed shorts to store them in
+    let schematic = SchematicV2Out {
+        version: 2,
+        data_version: REQUIRED_DATA_VERSION,
+        width: width as i16,
+        height: height as i16,
+        length: length as i16,
+        offset: fastnbt::IntArray::new(offset.to_vec()),
+        palette_max: indices.len() as i32,
+        palette: indices.into_iter().map(|(block, index)| (block.to_string(), index)).collect(),
+        block_data: fastnbt::ByteArray::new(block_data),
+        biome_palette_max: biomes.as_ref().map(|(indices, _)| indices.len() as i32),
+        biome_palette: biomes
+            .as_ref()
+            .map(|(indices, _)| indices.iter().map(|(biome, index)| (biome.to_string(), *index)).collect()),
+        biome_data: biomes.map(|(_, data)| fastnbt::ByteArray::new(data)),
+        block_entities: block_entities.iter().map(BlockEntity::to_schematic).collect(),
+        entities: entities.iter().map(Entity::to_schematic).collect(),
+    };
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&fastnbt::to_bytes(&schematic)?)?;
+    Ok(encoder.finish()?)
+}
+
+pub(crate) fn write_var_i32(value: i32, buffer: &mut Vec<i8>) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7F == 0 {
+            buffer.push(value as i8);
+            return;
         }
+        buffer.push(((value & 0x7F) | 0x80) as u8 as i8);
+        value >>= 7;
+    }
+}
+
+impl<'a> TryFrom<SchematicVersionedRaw<'a>> for SchematicData<'a> {
+    type Error = FalconCoreError;
+
+    fn try_from(value: SchematicVersionedRaw<'a>) -> std::result::Result<Self, Self::Error> { SchematicData::from_raw(value, Blocks::Air) }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::io::Read;
+
+    use fastnbt::Value;
+    use flate2::read::GzDecoder;
+
+    use super::{write_sponge_v2, SchematicData, SchematicVersionedRaw};
+    use crate::world::biome::Biome;
+    use crate::world::block_entity::BlockEntity;
+    use crate::world::blocks::Blocks;
+    use crate::world::entity::Entity;
+    use crate::world::BlockPosition;
+
+    fn decompress(data: &[u8]) -> Vec<u8> {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(data).read_to_end(&mut decompressed).unwrap();
+        decompressed
+    }
+
+    fn check_blocks(schematic: &SchematicData) {
+        assert_eq!((schematic.width, schematic.height, schematic.length), (2, 2, 2));
+        assert_eq!(schematic.offset, [-3, 64, 5]);
+        assert_eq!(schematic.palette.len(), 3);
+        assert_eq!(schematic.palette[&1], Blocks::Stone);
+        assert_eq!(schematic.block_data.iter().map(|b| b as u8).collect::<Vec<_>>(), vec![1, 1, 1, 1, 0, 2, 0, 0]);
+    }
+
+    #[test]
+    pub fn test_sponge_v2() {
+        let data = decompress(include_bytes!("../../tests/fixtures/sponge_v2.schem"));
+        let raw: SchematicVersionedRaw = fastnbt::from_bytes(&data).unwrap();
+        let schematic = SchematicData::try_from(raw).unwrap();
+        check_blocks(&schematic);
+
+        let biomes = schematic.biomes.unwrap();
+        assert!(!biomes.per_block);
+        assert_eq!(biomes.palette[&0], "minecraft:plains");
+        assert_eq!(biomes.data.iter().count(), 4);
+    }
+
+    #[test]
+    pub fn test_sponge_v3() {
+        let data = decompress(include_bytes!("../../tests/fixtures/sponge_v3.schem"));
+        let raw: SchematicVersionedRaw = fastnbt::from_bytes(&data).unwrap();
+        let schematic = SchematicData::try_from(raw).unwrap();
+        check_blocks(&schematic);
+
+        let biomes = schematic.biomes.unwrap();
+        assert!(biomes.per_block);
+        assert_eq!(biomes.palette[&1], "minecraft:desert");
+        assert_eq!(biomes.data.iter().map(|b| b as u8).collect::<Vec<_>>(), vec![0, 0, 0, 0, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    pub fn test_write_sponge_v2() {
+        let slab = Blocks::parse_state("minecraft:oak_slab[type=top,waterlogged=false]").unwrap();
+        let biomes = [Biome::Plains, Biome::Desert, Biome::Desert, Biome::Plains];
+        let sign = BlockEntity::new(BlockPosition::new(1, 0, 1), "minecraft:sign", HashMap::new());
+        let stand = Value::Compound(
+            [
+                (String::from("Id"), Value::String(String::from("minecraft:armor_stand"))),
+                (String::from("Pos"), Value::List(vec![Value::Double(0.5), Value::Double(0.0), Value::Double(1.5)])),
+                (String::from("Invisible"), Value::Byte(1)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let entities = [Entity::from_schematic(stand).unwrap()];
+        let block_at = |x, _, z| {
+            if x == z {
+                slab
+            } else {
+                Blocks::Air
+            }
+        };
+        let data = write_sponge_v2([2, 1, 2], [-16, 0, 32], block_at, Some(&biomes), &[sign], &entities).unwrap();
+        let data = decompress(&data);
+        let raw: SchematicVersionedRaw = fastnbt::from_bytes(&data).unwrap();
+        let schematic = SchematicData::try_from(raw).unwrap();
 
-        Ok(SchematicData::new(value.width as u16, value.height as u16, value.length as u16, effective_offset, effective_palette, block_data))
+        assert_eq!((schematic.width, schematic.height, schematic.length), (2, 1, 2));
+        assert_eq!(schematic.offset, [-16, 0, 32]);
+        let blocks: Vec<_> = schematic.block_data.iter().map(|b| schematic.palette[&(b as i32)]).collect();
+        assert_eq!(blocks, vec![slab, Blocks::Air, Blocks::Air, slab]);
+        let schematic_biomes = schematic.biomes.unwrap();
+        let names: Vec<_> = schematic_biomes
+            .data
+            .iter()
+            .map(|b| schematic_biomes.palette[&(b as i32)].to_string())
+            .collect();
+        assert_eq!(names, biomes.map(|biome| biome.to_string()));
+        assert_eq!(schematic.block_entities.len(), 1);
+        assert_eq!(schematic.block_entities[0].position(), BlockPosition::new(1, 0, 1));
+        assert_eq!(schematic.entities.len(), 1);
+        assert!(schematic.entities[0].invisible());
+        let position = schematic.entities[0].position();
+        assert_eq!((position.x, position.y, position.z), (0.5, 0.0, 1.5));
     }
 }
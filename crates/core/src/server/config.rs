@@ -1,13 +1,17 @@
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
+use std::time::Duration;
 
 use confy::ConfyError;
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use tokio::net::ToSocketAddrs;
 use tracing::metadata::LevelFilter;
+use uuid::Uuid;
 
 use crate::player::data::{LookAngles, Position};
+use crate::world::biome::{Biome, ParseBiomeError};
+use crate::world::blocks::{Blocks, ParseBlockError};
 
 static INSTANCE: OnceCell<FalconConfig> = OnceCell::new();
 
@@ -22,6 +26,8 @@ pub struct FalconConfig {
 impl FalconConfig {
     // 47, 107, 108, 109, 110, 210, 315, 316, 335, 338, 340, --> for future update
     pub const ALLOWED_VERSIONS: [u32; 13] = [393, 401, 404, 477, 480, 485, 490, 498, 573, 575, 578, 735, 736];
+    /// Name of the world loaded from `server.world`, players join this world.
+    pub const DEFAULT_WORLD: &'static str = "world";
 
     pub fn global() -> &'static FalconConfig { INSTANCE.get().expect("FalconConfig is not initialized!!") }
 
@@ -33,12 +39,90 @@ impl FalconConfig {
     pub fn server_socket_addrs(&self) -> impl ToSocketAddrs + '_ { (self.connection.server_ip, self.connection.server_port) }
 
     pub fn world_file(&self) -> Option<&str> { self.server.world.as_deref() }
+
+    pub fn world_origin(&self) -> Option<[i32; 3]> { self.server.world_origin }
+
+    pub fn world_dimension(&self) -> Option<&str> { self.server.dimension.as_deref() }
+
+    pub fn world_generator(&self) -> Option<&str> { self.server.generator.as_deref() }
+
+    /// The additional worlds players can switch to.
+    pub fn worlds(&self) -> &[WorldSettings] { &self.server.worlds }
+
+    /// The settings of the additional world called `name`.
+    pub fn world_settings(&self, name: &str) -> Option<&WorldSettings> { self.server.worlds.iter().find(|world| world.name == name) }
+
+    /// Where the world called `name` gets saved, see
+    /// [`FalconConfig::save_path`].
+    pub fn world_save_path(&self, name: &str) -> Option<&str> {
+        if name == Self::DEFAULT_WORLD {
+            self.save_path()
+        } else {
+            self.world_settings(name).and_then(WorldSettings::save_path)
+        }
+    }
+
+    /// Whether players may place and break blocks in the world called `name`.
+    pub fn allows_building(&self, name: &str) -> bool {
+        if name == Self::DEFAULT_WORLD {
+            self.server.allow_building
+        } else {
+            self.world_settings(name).and_then(|world| world.allow_building).unwrap_or(false)
+        }
+    }
+
+    /// Whether a player is listed in `players.builders` by name or uuid.
+    pub fn is_builder(&self, username: &str, uuid: Uuid) -> bool {
+        let uuid = uuid.hyphenated().to_string();
+        self.players
+            .builders
+            .iter()
+            .any(|builder| builder.eq_ignore_ascii_case(username) || builder.eq_ignore_ascii_case(&uuid))
+    }
+
+    /// Where players enter the world called `name`.
+    pub fn world_spawn(&self, name: &str) -> Position {
+        self.world_settings(name)
+            .and_then(|world| world.spawn_position)
+            .unwrap_or(self.players.spawn_position)
+    }
+
+    /// Where the world gets saved, worlds are only saved when this is set.
+    pub fn save_path(&self) -> Option<&str> { self.server.save.as_deref() }
+
+    pub fn autosave_interval(&self) -> Option<Duration> { (self.server.autosave_interval > 0).then(|| Duration::from_secs(self.server.autosave_interval)) }
+
+    pub fn packet_capture_dir(&self) -> Option<&str> { self.server.packet_capture.as_deref() }
+
+    pub fn metrics_addr(&self) -> Option<SocketAddr> { self.server.metrics }
+
+    /// Where the item registries are loaded from, placed blocks are
+    /// reverted without them.
+    pub fn item_registries_dir(&self) -> Option<&str> { self.server.item_registries.as_deref() }
+
+    /// The block that replaces unknown block states when loading a world.
+    pub fn fallback_block(&self) -> Result<Blocks, ParseBlockError> { Blocks::parse_state(&self.server.fallback_block) }
+
+    /// The biome of chunks without biome data, also replaces unknown biomes
+    /// when loading a world.
+    pub fn default_biome(&self) -> Result<Biome, ParseBiomeError> { Biome::from_str(&self.server.default_biome) }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConnectionSettings {
     pub server_ip: IpAddr,
     pub server_port: u16,
+    /// Amount of outgoing bytes a connection may buffer before
+    /// non-essential packets (e.g. chunks) get deferred.
+    pub max_pending_bytes: usize,
+    /// Amount of queued connection tasks before a connection is
+    /// considered congested.
+    pub max_queued_tasks: usize,
+    /// Seconds a connection may stay congested before it gets dropped.
+    pub slow_client_timeout: u64,
+    /// Amount of held back packets a connection may have, it gets
+    /// dropped when it falls further behind.
+    pub max_deferred_packets: usize,
 }
 
 impl Default for ConnectionSettings {
@@ -46,6 +130,10 @@ impl Default for ConnectionSettings {
         ConnectionSettings {
             server_port: 30000,
             server_ip: IpAddr::from_str("0.0.0.0").unwrap(),
+            max_pending_bytes: 4 * 1024 * 1024,
+            max_queued_tasks: 4096,
+            slow_client_timeout: 15,
+            max_deferred_packets: 4096,
         }
     }
 }
@@ -56,6 +144,8 @@ pub struct PlayerSettings {
     pub max_view_distance: u8,
     pub spawn_position: Position,
     pub spawn_look: LookAngles,
+    /// Names or uuids of players that may edit worlds that allow building.
+    pub builders: Vec<String>,
 }
 
 impl Default for PlayerSettings {
@@ -65,6 +155,7 @@ impl Default for PlayerSettings {
             max_view_distance: 10,
             spawn_position: Default::default(),
             spawn_look: Default::default(),
+            builders: Vec::new(),
         }
     }
 }
@@ -77,6 +168,39 @@ pub struct ServerSettings {
     pub description: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub world: Option<String>,
+    pub fallback_block: String,
+    pub default_biome: String,
+    /// Where the first block of a schematic is placed, overrides the schematic
+    /// offset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world_origin: Option<[i32; 3]>,
+    /// The dimension the world is served as: overworld, the_nether or the_end.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimension: Option<String>,
+    /// Generates the chunks outside the loaded world: `void` or superflat
+    /// layers from the bottom up like `minecraft:bedrock,2*dirt,grass_block`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generator: Option<String>,
+    /// Lets players in `players.builders` place and break blocks.
+    pub allow_building: bool,
+    /// Where the world gets saved, a `.schem` file or a world folder. The
+    /// world isn't saved when this is unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub save: Option<String>,
+    /// Seconds between autosaves, 0 disables them.
+    pub autosave_interval: u64,
+    /// Directory to write packet captures to, one file per connection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packet_capture: Option<String>,
+    /// Address to serve metrics on, in the Prometheus text format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<SocketAddr>,
+    /// Directory with the vanilla item registry report of every version,
+    /// named after its protocol version like `736.json`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_registries: Option<String>,
+    /// Additional worlds, players switch between them with `/world <name>`.
+    pub worlds: Vec<WorldSettings>,
 }
 
 impl Default for ServerSettings {
@@ -86,10 +210,47 @@ impl Default for ServerSettings {
             max_players: -1,
             description: String::from("§eFalcon server§r§b!!!"),
             world: None,
+            fallback_block: String::from("minecraft:air"),
+            default_biome: String::from("minecraft:plains"),
+            world_origin: None,
+            dimension: None,
+            generator: None,
+            allow_building: false,
+            save: None,
+            autosave_interval: 300,
+            packet_capture: None,
+            metrics: None,
+            item_registries: None,
+            worlds: Vec::new(),
         }
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorldSettings {
+    pub name: String,
+    /// A schematic file or a world folder, like `server.world`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world_origin: Option<[i32; 3]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimension: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_building: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub save: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spawn_position: Option<Position>,
+}
+
+impl WorldSettings {
+    /// Where this world gets saved, it is only saved when this is set.
+    pub fn save_path(&self) -> Option<&str> { self.save.as_deref() }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct VersionSettings {
     pub excluded: Vec<u32>,
@@ -0,0 +1,846 @@
+//! Loading and saving of vanilla Anvil worlds (`region/r.x.z.mca`).
+//!
+//! A region file holds 32x32 chunks. It starts with a table of 1024
+//! locations (3 byte sector offset + 1 byte sector count) followed by
+//! 1024 timestamps, every chunk is stored in 4KiB sectors as a big-endian
+//! length, a compression type and the compressed nbt data. Chunks too large
+//! for 255 sectors are stored in `c.x.z.mcc` next to the region file, which
+//! is marked by adding 128 to the compression type.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ahash::AHashMap;
+use fastnbt::{IntArray, LongArray, Value};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::error::{FalconCoreError, Result};
+use crate::schematic::REQUIRED_DATA_VERSION;
+use crate::world::biome::Biome;
+use crate::world::bit_storage::BitStorage;
+use crate::world::block_entity::BlockEntity;
+use crate::world::blocks::Blocks;
+use crate::world::chunks::{
+    Chunk, ChunkPos, ChunkSection, BIOME_CELLS, BIOME_CELLS_HEIGHT, BIOME_CELLS_LENGTH, BIOME_CELLS_WIDTH, BIOME_CELL_SIZE, SECTIONS_NUM, SECTION_HEIGHT,
+    SECTION_LENGTH, SECTION_WIDTH,
+};
+use crate::world::upgrade::{BlockStateUpgrader, SUPPORTED_DATA_VERSIONS};
+use crate::world::write_atomically;
+
+pub const SECTOR_LEN: usize = 4096;
+pub const REGION_WIDTH: i32 = 32;
+const HEADER_LEN: usize = 2 * SECTOR_LEN;
+const BLOCKS_PER_SECTION: usize = (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH) as usize;
+/// 20w17a, block states no longer span multiple longs from here on.
+const PADDED_STATES_DATA_VERSION: i32 = 2529;
+
+const COMPRESSION_GZIP: u8 = 1;
+const COMPRESSION_ZLIB: u8 = 2;
+const COMPRESSION_NONE: u8 = 3;
+/// Added to the compression type of chunks stored in their own file.
+const COMPRESSION_EXTERNAL: u8 = 128;
+
+/// Loads every chunk from the `region` directory of a world folder,
+/// block states that can't be upgraded become `fallback` and unknown
+/// biomes become `fallback_biome`. Chunks that can't be read are skipped.
+#[tracing::instrument(name = "anvil_loading", skip_all)]
+pub fn load_world<P: AsRef<Path>>(world_dir: P, fallback: Blocks, fallback_biome: Biome) -> Result<Vec<Chunk>> {
+    let mut chunks = Vec::new();
+    let mut upgrader = BlockStateUpgrader::new(0, fallback).with_fallback_biome(fallback_biome);
+    for entry in std::fs::read_dir(world_dir.as_ref().join("region"))? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "mca") {
+            continue;
+        }
+        let region = Region::open(&path)?;
+        debug!(x = region.x, z = region.z, "Loading region");
+        let width = REGION_WIDTH as usize;
+        for i in 0..width * width {
+            match region.chunk(i % width, i / width, &mut upgrader) {
+                Ok(Some(chunk)) => chunks.push(chunk),
+                Ok(None) => {},
+                Err(error) => {
+                    let (x, z) = (region.x * REGION_WIDTH + (i % width) as i32, region.z * REGION_WIDTH + (i / width) as i32);
+                    warn!(x, z, %error, "Skipping chunk that can't be loaded");
+                },
+            }
+        }
+    }
+    upgrader.log_unknown();
+    debug!(count = chunks.len(), "Loaded chunks.");
+    Ok(chunks)
+}
+
+/// Writes every region containing a dirty chunk to the `region` directory
+/// of a world folder. Only dirty chunks and chunks the region file doesn't
+/// have yet are encoded, dirty chunks are merged into their saved nbt with
+/// [`merge_chunk`]. Chunks saved by a newer version keep their saved nbt.
+/// Returns the amount of regions written.
+#[tracing::instrument(name = "anvil_saving", skip_all)]
+pub fn save_world<'c, P, I>(world_dir: P, chunks: I) -> Result<usize>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = &'c Chunk>,
+{
+    let region_dir = world_dir.as_ref().join("region");
+    std::fs::create_dir_all(&region_dir)?;
+
+    let mut regions: AHashMap<(i32, i32), Vec<&Chunk>> = AHashMap::new();
+    for chunk in chunks {
+        let pos = chunk.get_position();
+        regions
+            .entry((pos.x.div_euclid(REGION_WIDTH), pos.z.div_euclid(REGION_WIDTH)))
+            .or_default()
+            .push(chunk);
+    }
+
+    let width = REGION_WIDTH as usize;
+    let mut saved = 0;
+    for ((x, z), chunks) in regions {
+        if !chunks.iter().any(|chunk| chunk.is_dirty()) {
+            continue;
+        }
+        let mut data = vec![None; width * width];
+        let path = region_dir.join(format!("r.{}.{}.mca", x, z));
+        if path.is_file() {
+            let existing = Region::open(&path)?;
+            for (i, chunk) in data.iter_mut().enumerate() {
+                *chunk = existing.chunk_data(i % width, i / width)?;
+            }
+        }
+        for chunk in chunks {
+            let pos = chunk.get_position();
+            let slot = &mut data[(pos.x.rem_euclid(REGION_WIDTH) + pos.z.rem_euclid(REGION_WIDTH) * REGION_WIDTH) as usize];
+            *slot = match slot {
+                Some(nbt) if chunk.is_dirty() => match merge_chunk(chunk, nbt) {
+                    Ok(merged) => Some(merged),
+                    Err(FalconCoreError::UnsupportedDataVersion(data_version)) => {
+                        warn!(x = pos.x, z = pos.z, data_version, "Keeping chunk saved by a newer version");
+                        continue;
+                    },
+                    Err(error) => return Err(error),
+                },
+                Some(_) => continue,
+                None => Some(encode_chunk(chunk)?),
+            };
+        }
+        debug!(x, z, "Saving region");
+        Region::from_chunks(x, z, &data)?.save(&region_dir)?;
+        saved += 1;
+    }
+    Ok(saved)
+}
+
+/// A single region file, the raw data is kept in memory.
+#[derive(Debug)]
+pub struct Region {
+    x: i32,
+    z: i32,
+    data: Vec<u8>,
+    /// Where external chunks are read from, `None` if the region isn't
+    /// backed by a file.
+    dir: Option<PathBuf>,
+    /// Compressed chunks stored in their own file, written by
+    /// [`save`](Self::save).
+    external: Vec<(ChunkPos, Vec<u8>)>,
+}
+
+impl Region {
+    /// Opens a region file, the position is taken from the `r.x.z.mca` file
+    /// name.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let mut parts = name.split('.').skip(1).map(i32::from_str);
+        let (x, z) = match (name.starts_with("r."), parts.next(), parts.next()) {
+            (true, Some(Ok(x)), Some(Ok(z))) => (x, z),
+            _ => return Err(FalconCoreError::InvalidData(format!("Invalid region file name \"{}\"", name))),
+        };
+        let mut region = Region::new(x, z, std::fs::read(path)?)?;
+        region.dir = path.parent().map(Path::to_path_buf);
+        Ok(region)
+    }
+
+    pub fn new(x: i32, z: i32, data: Vec<u8>) -> Result<Self> {
+        // empty region files are written by the vanilla server
+        if !data.is_empty() && data.len() < HEADER_LEN {
+            return Err(FalconCoreError::InvalidData(format!("Region {} {} is missing its header", x, z)));
+        }
+        Ok(Region {
+            x,
+            z,
+            data,
+            dir: None,
+            external: Vec::new(),
+        })
+    }
+
+    /// Builds a region from uncompressed chunk nbt, indexed
+    /// by `local_x + local_z * 32`.
+    pub fn from_chunks(x: i32, z: i32, chunks: &[Option<Vec<u8>>]) -> Result<Self> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs() as u32);
+        let mut data = vec![0u8; HEADER_LEN];
+        let mut external = Vec::new();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let chunk = match chunk {
+                Some(chunk) => chunk,
+                None => continue,
+            };
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(chunk)?;
+            let compressed = encoder.finish()?;
+
+            let offset = data.len() / SECTOR_LEN;
+            let sectors = (compressed.len() + 5).div_ceil(SECTOR_LEN);
+            data[SECTOR_LEN + 4 * i..SECTOR_LEN + 4 * i + 4].copy_from_slice(&timestamp.to_be_bytes());
+            if sectors > u8::MAX as usize {
+                // only the compression type stays in the region
+                data[4 * i..4 * i + 4].copy_from_slice(&[(offset >> 16) as u8, (offset >> 8) as u8, offset as u8, 1]);
+                data.extend_from_slice(&1u32.to_be_bytes());
+                data.push(COMPRESSION_ZLIB | COMPRESSION_EXTERNAL);
+                data.resize((offset + 1) * SECTOR_LEN, 0);
+                let pos = ChunkPos::new(x * REGION_WIDTH + (i % REGION_WIDTH as usize) as i32, z * REGION_WIDTH + (i / REGION_WIDTH as usize) as i32);
+                external.push((pos, compressed));
+                continue;
+            }
+            data[4 * i..4 * i + 4].copy_from_slice(&[(offset >> 16) as u8, (offset >> 8) as u8, offset as u8, sectors as u8]);
+            data.extend_from_slice(&(compressed.len() as u32 + 1).to_be_bytes());
+            data.push(COMPRESSION_ZLIB);
+            data.extend_from_slice(&compressed);
+            data.resize((offset + sectors) * SECTOR_LEN, 0);
+        }
+        let mut region = Region::new(x, z, data)?;
+        region.external = external;
+        Ok(region)
+    }
+
+    /// Writes this region to `r.x.z.mca` in the given directory, external
+    /// chunks are written to `c.x.z.mcc` first.
+    pub fn save<P: AsRef<Path>>(&self, region_dir: P) -> Result<()> {
+        for (pos, compressed) in &self.external {
+            write_atomically(region_dir.as_ref().join(format!("c.{}.{}.mcc", pos.x, pos.z)), compressed)?;
+        }
+        write_atomically(region_dir.as_ref().join(format!("r.{}.{}.mca", self.x, self.z)), &self.data)?;
+        Ok(())
+    }
+
+    pub fn x(&self) -> i32 { self.x }
+
+    pub fn z(&self) -> i32 { self.z }
+
+    /// Decompressed nbt data of the chunk at the given
+    /// coordinates relative to this region.
+    pub fn chunk_data(&self, local_x: usize, local_z: usize) -> Result<Option<Vec<u8>>> {
+        if self.data.is_empty() {
+            return Ok(None);
+        }
+        let location = 4 * (local_x + local_z * REGION_WIDTH as usize);
+        let header = &self.data[location..location + 4];
+        let offset = u32::from_be_bytes([0, header[0], header[1], header[2]]) as usize * SECTOR_LEN;
+        let sectors = header[3] as usize;
+        if offset == 0 || sectors == 0 {
+            return Ok(None);
+        }
+
+        let invalid = || FalconCoreError::InvalidData(format!("Invalid chunk location in region {} {}", self.x, self.z));
+        let chunk = self.data.get(offset..offset + 5).ok_or_else(invalid)?;
+        let len = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as usize;
+        let compression = chunk[4];
+        if len == 0 || len > sectors * SECTOR_LEN {
+            return Err(invalid());
+        }
+        let compressed = self.data.get(offset + 5..offset + 4 + len).ok_or_else(invalid)?;
+
+        let (compression, compressed) = if compression & COMPRESSION_EXTERNAL != 0 {
+            let pos = ChunkPos::new(self.x * REGION_WIDTH + local_x as i32, self.z * REGION_WIDTH + local_z as i32);
+            let name = format!("c.{}.{}.mcc", pos.x, pos.z);
+            let compressed = match (self.external.iter().find(|(external, _)| *external == pos), &self.dir) {
+                (Some((_, compressed)), _) => Cow::Borrowed(compressed.as_slice()),
+                (None, Some(dir)) => Cow::Owned(std::fs::read(dir.join(&name))?),
+                (None, None) => return Err(FalconCoreError::InvalidData(format!("External chunk {} of region {} {} has no file", name, self.x, self.z))),
+            };
+            (compression & !COMPRESSION_EXTERNAL, compressed)
+        } else {
+            (compression, Cow::Borrowed(compressed))
+        };
+        let mut data = Vec::new();
+        match compression {
+            COMPRESSION_GZIP => GzDecoder::new(&*compressed).read_to_end(&mut data)?,
+            COMPRESSION_ZLIB => ZlibDecoder::new(&*compressed).read_to_end(&mut data)?,
+            COMPRESSION_NONE => {
+                data.extend_from_slice(&compressed);
+                data.len()
+            },
+            _ => return Err(FalconCoreError::InvalidData(format!("Unsupported chunk compression {}", compression))),
+        };
+        Ok(Some(data))
+    }
+
+    /// Decodes the chunk at the given coordinates relative to this region.
+    /// Chunks that haven't been fully generated yet are skipped.
+    pub fn chunk(&self, local_x: usize, local_z: usize, upgrader: &mut BlockStateUpgrader) -> Result<Option<Chunk>> {
+        let data = match self.chunk_data(local_x, local_z)? {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        let pos = ChunkPos::new(self.x * REGION_WIDTH + local_x as i32, self.z * REGION_WIDTH + local_z as i32);
+        decode_chunk(&data, pos, upgrader)
+    }
+
+    pub fn chunks<'b>(&'b self, upgrader: &'b mut BlockStateUpgrader) -> impl Iterator<Item = Result<Option<Chunk>>> + 'b {
+        let width = REGION_WIDTH as usize;
+        (0..width * width).map(move |i| self.chunk(i % width, i / width, upgrader))
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawChunk {
+    #[serde(rename = "DataVersion")]
+    data_version: Option<i32>,
+    /// Up until 21w43a everything is nested in `Level`.
+    #[serde(rename = "Level", skip_serializing_if = "Option::is_none")]
+    level: Option<RawLevel>,
+    #[serde(rename = "Status", skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sections: Option<Vec<RawSection>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block_entities: Option<Vec<Value>>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawLevel {
+    #[serde(rename = "xPos")]
+    x: Option<i32>,
+    #[serde(rename = "zPos")]
+    z: Option<i32>,
+    #[serde(rename = "Status")]
+    status: Option<String>,
+    #[serde(rename = "Sections")]
+    sections: Option<Vec<RawSection>>,
+    /// 256 ids (one per column) before 19w36a, 1024 ids (4x4x4 cells) after.
+    #[serde(rename = "Biomes", skip_serializing_if = "Option::is_none")]
+    biomes: Option<IntArray>,
+    #[serde(rename = "TileEntities", skip_serializing_if = "Option::is_none")]
+    block_entities: Option<Vec<Value>>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawSection {
+    #[serde(rename = "Y")]
+    y: i8,
+    #[serde(rename = "Palette", skip_serializing_if = "Option::is_none")]
+    palette: Option<Vec<RawBlockState>>,
+    #[serde(rename = "BlockStates", skip_serializing_if = "Option::is_none")]
+    states: Option<LongArray>,
+    /// Since 21w43a the palette and data are nested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block_states: Option<RawBlockStates>,
+    /// Since 21w37a biomes are stored per section.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    biomes: Option<RawBiomes>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawBiomes {
+    palette: Vec<String>,
+    data: Option<LongArray>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawBlockStates {
+    palette: Vec<RawBlockState>,
+    data: Option<LongArray>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawBlockState {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Properties", skip_serializing_if = "Option::is_none")]
+    properties: Option<AHashMap<String, String>>,
+}
+
+impl From<&Blocks> for RawBlockState {
+    fn from(block: &Blocks) -> Self {
+        let state = block.to_string();
+        match state.split_once('[') {
+            Some((name, properties)) => RawBlockState {
+                name: name.to_owned(),
+                properties: Some(
+                    properties
+                        .trim_end_matches(']')
+                        .split(',')
+                        .filter_map(|property| property.split_once('='))
+                        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                        .collect(),
+                ),
+            },
+            None => RawBlockState {
+                name: state,
+                properties: None,
+            },
+        }
+    }
+}
+
+impl RawBlockState {
+    fn to_block(&self, upgrader: &mut BlockStateUpgrader) -> Blocks {
+        match &self.properties {
+            Some(properties) if !properties.is_empty() => {
+                let properties: Vec<String> = properties.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+                upgrader.parse(&format!("{}[{}]", self.name, properties.join(",")))
+            },
+            _ => upgrader.parse(&self.name),
+        }
+    }
+}
+
+/// Decodes the nbt data of a single chunk.
+pub fn decode_chunk(data: &[u8], pos: ChunkPos, upgrader: &mut BlockStateUpgrader) -> Result<Option<Chunk>> {
+    let raw: RawChunk = fastnbt::from_bytes(data)?;
+    let data_version = raw.data_version.unwrap_or(0);
+    if !SUPPORTED_DATA_VERSIONS.contains(&data_version) {
+        return Err(FalconCoreError::UnsupportedDataVersion(data_version));
+    }
+    upgrader.set_data_version(data_version);
+    let (status, sections, biomes, block_entities) = match raw.level {
+        Some(level) => (level.status, level.sections, level.biomes, level.block_entities),
+        None => (raw.status, raw.sections, None, raw.block_entities),
+    };
+    if status.is_some_and(|status| status != "full" && status != "minecraft:full") {
+        return Ok(None);
+    }
+
+    // biome ids before 21w37a start at y 0, the chunk grows to fit every section
+    // with blocks
+    let mut chunk = Chunk::with_height(pos, 0, SECTIONS_NUM as usize);
+    if let Some(biomes) = biomes {
+        chunk.set_biomes(decode_biome_ids(&biomes, upgrader));
+    }
+    let sections = sections.unwrap_or_default();
+    let block_sections = sections
+        .iter()
+        .filter(|section| section.palette.is_some() || section.block_states.is_some())
+        .map(|section| section.y as i32);
+    let (min_section, max_section) = block_sections.fold((0, SECTIONS_NUM as i32 - 1), |(min, max), y| (min.min(y), max.max(y)));
+    chunk.set_height(min_section, (max_section - min_section + 1) as usize);
+    for section in sections {
+        if let Some(biomes) = section.biomes {
+            let palette: Vec<Biome> = biomes.palette.iter().map(|biome| upgrader.parse_biome(biome)).collect();
+            let bits = usize::BITS - palette.len().saturating_sub(1).leading_zeros();
+            let cells = (BIOME_CELLS_WIDTH * BIOME_CELLS_LENGTH * SECTION_HEIGHT / BIOME_CELL_SIZE) as usize;
+            let indices = match biomes.data {
+                Some(data) if bits > 0 => unpack_padded(&data, bits, cells),
+                _ => vec![0; cells],
+            };
+            for (i, index) in indices.into_iter().enumerate() {
+                let i = i as u16;
+                let (x, z, y) = (i % BIOME_CELLS_WIDTH, i / BIOME_CELLS_WIDTH % BIOME_CELLS_LENGTH, i / (BIOME_CELLS_WIDTH * BIOME_CELLS_LENGTH));
+                let biome = palette.get(index as usize).copied().unwrap_or(upgrader.fallback_biome());
+                let cell_y = section.y as i32 * (SECTION_HEIGHT / BIOME_CELL_SIZE) as i32 + y as i32;
+                chunk.set_biome(x, cell_y, z, biome, upgrader.fallback_biome());
+            }
+        }
+        let (palette, states) = match (section.block_states, section.palette) {
+            (Some(block_states), _) => (block_states.palette, block_states.data),
+            (None, Some(palette)) => (palette, section.states),
+            (None, None) => continue,
+        };
+        let palette: Vec<Blocks> = palette.iter().map(|state| state.to_block(upgrader)).collect();
+        let indices = match states {
+            Some(states) => unpack_block_states(&states, bits_per_block(palette.len()), data_version < PADDED_STATES_DATA_VERSION),
+            // single entry palettes have no data
+            None => vec![0; BLOCKS_PER_SECTION],
+        };
+        chunk.set_section(section.y as i32, ChunkSection::from_palette(&palette, &indices));
+    }
+    for block_entity in block_entities.unwrap_or_default().into_iter().filter_map(BlockEntity::from_nbt) {
+        if (chunk.min_y()..chunk.min_y() + chunk.height()).contains(&block_entity.position().get_y()) {
+            chunk.set_block_entity(block_entity);
+        }
+    }
+    Ok(Some(chunk))
+}
+
+/// Converts the numeric biome ids stored before 21w37a to cells,
+/// column biomes are used for the whole height of the chunk.
+fn decode_biome_ids(ids: &[i32], upgrader: &mut BlockStateUpgrader) -> Vec<Biome> {
+    if ids.len() != SECTION_WIDTH as usize * SECTION_LENGTH as usize {
+        return ids.iter().map(|id| upgrader.biome_from_id(*id)).collect();
+    }
+    let columns: Vec<Biome> = (0..BIOME_CELLS_WIDTH * BIOME_CELLS_LENGTH)
+        .map(|i| {
+            let (x, z) = (i % BIOME_CELLS_WIDTH * BIOME_CELL_SIZE, i / BIOME_CELLS_WIDTH * BIOME_CELL_SIZE);
+            upgrader.biome_from_id(ids[(x + z * SECTION_WIDTH) as usize])
+        })
+        .collect();
+    (0..BIOME_CELLS_HEIGHT).flat_map(|_| columns.iter().copied()).collect()
+}
+
+fn bits_per_block(palette_len: usize) -> u32 { (usize::BITS - palette_len.saturating_sub(1).leading_zeros()).max(4) }
+
+/// Unpacks the palette indices of one section. Before 20w17a entries
+/// may span two longs, afterwards every long is padded instead.
+pub fn unpack_block_states(data: &[i64], bits: u32, spanning: bool) -> Vec<u16> {
+    let mask = (1u64 << bits) - 1;
+    let mut indices = Vec::with_capacity(BLOCKS_PER_SECTION);
+    if spanning {
+        for i in 0..BLOCKS_PER_SECTION {
+            let bit = i * bits as usize;
+            let (long, offset) = (bit / 64, bit % 64);
+            let mut value = data.get(long).map_or(0, |long| *long as u64 >> offset);
+            if offset + bits as usize > 64 {
+                value |= data.get(long + 1).map_or(0, |long| (*long as u64) << (64 - offset));
+            }
+            indices.push((value & mask) as u16);
+        }
+    } else {
+        let per_long = 64 / bits as usize;
+        'outer: for long in data {
+            for i in 0..per_long {
+                if indices.len() == BLOCKS_PER_SECTION {
+                    break 'outer;
+                }
+                indices.push(((*long as u64 >> (i * bits as usize)) & mask) as u16);
+            }
+        }
+    }
+    indices.resize(BLOCKS_PER_SECTION, 0);
+    indices
+}
+
+fn unpack_padded(data: &[i64], bits: u32, count: usize) -> Vec<u16> {
+    let mask = (1u64 << bits) - 1;
+    let per_long = 64 / bits as usize;
+    (0..count)
+        .map(|i| {
+            let long = data.get(i / per_long).map_or(0, |long| *long as u64);
+            ((long >> ((i % per_long) * bits as usize)) & mask) as u16
+        })
+        .collect()
+}
+
+/// The block states of a section the way [`unpack_block_states`] reads them
+/// since 20w17a, which packs like [`BitStorage`] with at least 4 bits.
+pub fn pack_block_states(blocks: &BitStorage) -> Vec<i64> {
+    let data = match blocks.bits() {
+        0 => blocks.resized(4).into_data(),
+        _ => blocks.data().to_vec(),
+    };
+    data.into_iter().map(|long| long as i64).collect()
+}
+
+/// Encodes a chunk in the format of [`REQUIRED_DATA_VERSION`],
+/// only the blocks, biomes and block entities are written. That format
+/// only has biomes from y 0 to 256.
+pub fn encode_chunk(chunk: &Chunk) -> Result<Vec<u8>> { Ok(fastnbt::to_bytes(&raw_chunk(chunk))?) }
+
+/// Replaces the blocks, biomes and block entities of saved chunk nbt with
+/// those of `chunk`, everything else like entities and ticks is kept.
+/// Light and heightmaps are dropped so vanilla recomputes them. Chunks
+/// saved by versions newer than [`REQUIRED_DATA_VERSION`] are refused
+/// instead of being downgraded.
+pub fn merge_chunk(chunk: &Chunk, saved: &[u8]) -> Result<Vec<u8>> {
+    let mut root: HashMap<String, Value> = fastnbt::from_bytes(saved)?;
+    match root.get("DataVersion") {
+        Some(Value::Int(data_version)) if *data_version > REQUIRED_DATA_VERSION => return Err(FalconCoreError::UnsupportedDataVersion(*data_version)),
+        _ => {},
+    }
+    let level = match root.get_mut("Level") {
+        Some(Value::Compound(level)) => level,
+        _ => return encode_chunk(chunk),
+    };
+    let mut encoded = match fastnbt::to_value(&raw_chunk(chunk).level)? {
+        Value::Compound(encoded) => encoded,
+        _ => return encode_chunk(chunk),
+    };
+    for key in ["Sections", "Biomes", "TileEntities"] {
+        match encoded.remove(key) {
+            Some(value) => level.insert(String::from(key), value),
+            None => level.remove(key),
+        };
+    }
+    level.remove("Heightmaps");
+    level.insert(String::from("isLightOn"), Value::Byte(0));
+    root.insert(String::from("DataVersion"), Value::Int(REQUIRED_DATA_VERSION));
+    Ok(fastnbt::to_bytes(&root)?)
+}
+
+fn raw_chunk(chunk: &Chunk) -> RawChunk {
+    let sections = chunk
+        .get_chunk_sections()
+        .iter()
+        .enumerate()
+        .filter_map(|(y, section)| section.as_ref().map(|section| (y, section)))
+        .map(|(y, section)| {
+            let palette = section.get_palette().items();
+            RawSection {
+                y: (chunk.min_section() + y as i32) as i8,
+                palette: Some(palette.iter().map(RawBlockState::from).collect()),
+                states: Some(LongArray::new(pack_block_states(section.get_block_data()))),
+                block_states: None,
+                biomes: None,
+            }
+        })
+        .collect();
+    let pos = chunk.get_position();
+    RawChunk {
+        data_version: Some(REQUIRED_DATA_VERSION),
+        level: Some(RawLevel {
+            x: Some(pos.x),
+            z: Some(pos.z),
+            status: Some(String::from("full")),
+            sections: Some(sections),
+            biomes: chunk.biomes().map(|_| {
+                let cells = (0..BIOME_CELLS as u16).map(|i| {
+                    let (x, z, y) = (i % BIOME_CELLS_WIDTH, i / BIOME_CELLS_WIDTH % BIOME_CELLS_LENGTH, i / (BIOME_CELLS_WIDTH * BIOME_CELLS_LENGTH));
+                    chunk.biome_at(x, y as i32, z).unwrap_or_default().id()
+                });
+                IntArray::new(cells.collect())
+            }),
+            block_entities: Some(chunk.block_entities().iter().map(BlockEntity::to_nbt).collect()),
+        }),
+        status: None,
+        sections: None,
+        block_entities: None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::io::Write;
+
+    use fastnbt::{IntArray, LongArray, Value};
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+
+    use super::{encode_chunk, load_world, merge_chunk, save_world, unpack_block_states, Region, SECTOR_LEN};
+    use crate::world::biome::Biome;
+    use crate::world::block_entity::BlockEntity;
+    use crate::world::blocks::Blocks;
+    use crate::world::chunks::{Chunk, ChunkPos};
+    use crate::world::dimension::Dimension;
+    use crate::world::upgrade::BlockStateUpgrader;
+    use crate::world::BlockPosition;
+
+    #[test]
+    pub fn test_unpack() {
+        // 5 bits per entry: 12 per long when padded, spanning otherwise
+        let values: Vec<u64> = (0..4096).map(|i| i % 32).collect();
+        let padded: Vec<i64> = values
+            .chunks(12)
+            .map(|chunk| chunk.iter().enumerate().fold(0u64, |long, (i, value)| long | value << (i * 5)) as i64)
+            .collect();
+        assert_eq!(unpack_block_states(&padded, 5, false).iter().map(|v| *v as u64).collect::<Vec<_>>(), values);
+
+        let mut spanning = vec![0i64; 4096 * 5 / 64];
+        for (i, value) in values.iter().enumerate() {
+            let bit = i * 5;
+            spanning[bit / 64] |= (value << (bit % 64)) as i64;
+            if bit % 64 + 5 > 64 {
+                spanning[bit / 64 + 1] |= (value >> (64 - bit % 64)) as i64;
+            }
+        }
+        assert_eq!(unpack_block_states(&spanning, 5, true).iter().map(|v| *v as u64).collect::<Vec<_>>(), values);
+    }
+
+    #[test]
+    pub fn test_region() {
+        let state = |name: &str| Value::Compound([(String::from("Name"), Value::String(String::from(name)))].into_iter().collect());
+        // the bottom layer is stone, the rest is air
+        let mut states = vec![0i64; 256];
+        states[..16].iter_mut().for_each(|long| *long = 0x1111_1111_1111_1111);
+        let section = Value::Compound(
+            [
+                (String::from("Y"), Value::Byte(0)),
+                (String::from("Palette"), Value::List(vec![state("minecraft:air"), state("minecraft:stone")])),
+                (String::from("BlockStates"), Value::LongArray(LongArray::new(states))),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        // column biomes, desert at x = 4
+        let mut biomes = vec![1; 256];
+        biomes[4] = 2;
+        let level = Value::Compound(
+            [
+                (String::from("Status"), Value::String(String::from("full"))),
+                (String::from("Sections"), Value::List(vec![section])),
+                (String::from("Biomes"), Value::IntArray(IntArray::new(biomes))),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let chunk = Value::Compound(
+            [(String::from("DataVersion"), Value::Int(2730)), (String::from("Level"), level)]
+                .into_iter()
+                .collect(),
+        );
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&fastnbt::to_bytes(&chunk).unwrap()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        // chunk (1, 2) in the third sector
+        let mut data = vec![0u8; 2 * SECTOR_LEN];
+        let location = 4 * (1 + 2 * 32);
+        data[location..location + 4].copy_from_slice(&[0, 0, 2, 1]);
+        data.extend_from_slice(&(compressed.len() as u32 + 1).to_be_bytes());
+        data.push(2);
+        data.extend_from_slice(&compressed);
+        data.resize(3 * SECTOR_LEN, 0);
+
+        let region = Region::new(-1, 0, data).unwrap();
+        let mut upgrader = BlockStateUpgrader::new(0, Blocks::Air);
+        let chunks: Vec<_> = region
+            .chunks(&mut upgrader)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+        assert_eq!(chunks.len(), 1);
+        let chunk = &chunks[0];
+        assert_eq!(*chunk.get_position(), ChunkPos::new(-31, 2));
+        assert_eq!(chunk.get_bit_mask(), 1);
+        let section = chunk.get_chunk_sections()[0].as_ref().unwrap();
+        assert_eq!(section.get_block_count(), 256);
+        assert_eq!(*section.block_at(3, 0, 7), Blocks::Stone);
+        assert_eq!(*section.block_at(3, 1, 7), Blocks::Air);
+        assert_eq!(chunk.biome_at(1, 40, 0), Some(Biome::Desert));
+        assert_eq!(chunk.biome_at(0, 40, 0), Some(Biome::Plains));
+    }
+
+    #[test]
+    pub fn test_save_region() {
+        let mut chunk = Chunk::empty(ChunkPos::new(-33, 5), &Dimension::overworld().with_height(-64, 384));
+        chunk.set_block_at(0, 0, 0, Blocks::Stone);
+        chunk.set_block_at(15, 200, 15, Blocks::Granite);
+        chunk.set_block_at(4, -30, 4, Blocks::Bedrock);
+        chunk.set_biome(3, 50, 3, Biome::BambooJungle, Biome::Ocean);
+        let text = [(String::from("Text1"), Value::String(String::from("\"Lobby\"")))]
+            .into_iter()
+            .collect();
+        chunk.set_block_entity(BlockEntity::new(BlockPosition::new(-526, 70, 82), "minecraft:sign", text));
+        let mut chunks = vec![None; 32 * 32];
+        chunks[31 + 5 * 32] = Some(encode_chunk(&chunk).unwrap());
+
+        let region = Region::from_chunks(-2, 0, &chunks).unwrap();
+        let mut upgrader = BlockStateUpgrader::new(0, Blocks::Air);
+        let loaded: Vec<_> = region.chunks(&mut upgrader).filter_map(|chunk| chunk.unwrap()).collect();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(*loaded[0].get_position(), ChunkPos::new(-33, 5));
+        assert_eq!(loaded[0].get_bit_mask(), chunk.get_bit_mask());
+        assert_eq!(loaded[0].min_section(), -2);
+        assert_eq!(loaded[0].block_at(4, -30, 4), Blocks::Bedrock);
+        let section = loaded[0].section(12).unwrap();
+        assert_eq!(*section.block_at(15, 8, 15), Blocks::Granite);
+        assert_eq!(section.get_block_count(), 1);
+        assert_eq!(loaded[0].biome_at(3, 50, 3), Some(Biome::BambooJungle));
+        assert_eq!(loaded[0].biome_at(0, 0, 0), Some(Biome::Ocean));
+        let block_entities = loaded[0].block_entities();
+        assert_eq!(block_entities.len(), 1);
+        assert_eq!(block_entities[0].position().get_x(), -526);
+        assert_eq!(block_entities[0].data()["Text1"], Value::String(String::from("\"Lobby\"")));
+    }
+
+    #[test]
+    pub fn test_merge_chunk() {
+        let compound = |entries: Vec<(&str, Value)>| Value::Compound(entries.into_iter().map(|(key, value)| (String::from(key), value)).collect());
+        let entity = compound(vec![("id", Value::String(String::from("minecraft:pig")))]);
+        let saved = |data_version: i32| {
+            let level = compound(vec![
+                ("Status", Value::String(String::from("full"))),
+                ("Entities", Value::List(vec![entity.clone()])),
+                ("Heightmaps", compound(vec![])),
+                ("isLightOn", Value::Byte(1)),
+            ]);
+            fastnbt::to_bytes(&compound(vec![("DataVersion", Value::Int(data_version)), ("Level", level)])).unwrap()
+        };
+        let mut chunk = Chunk::empty(ChunkPos::new(0, 0), &Dimension::overworld());
+        chunk.set_block_at(1, 2, 3, Blocks::Stone);
+
+        let merged: HashMap<String, Value> = fastnbt::from_bytes(&merge_chunk(&chunk, &saved(2586)).unwrap()).unwrap();
+        assert_eq!(merged["DataVersion"], Value::Int(2730));
+        let level = match &merged["Level"] {
+            Value::Compound(level) => level,
+            _ => panic!("Level is not a compound"),
+        };
+        assert_eq!(level["Entities"], Value::List(vec![entity.clone()]));
+        assert_eq!(level["isLightOn"], Value::Byte(0));
+        assert!(!level.contains_key("Heightmaps"));
+        let mut upgrader = BlockStateUpgrader::new(0, Blocks::Air);
+        let merged = super::decode_chunk(&fastnbt::to_bytes(&merged).unwrap(), ChunkPos::new(0, 0), &mut upgrader)
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.block_at(1, 2, 3), Blocks::Stone);
+
+        assert!(merge_chunk(&chunk, &saved(3120)).is_err(), "newer chunks aren't downgraded");
+    }
+
+    #[test]
+    pub fn test_save_newer_chunk() {
+        let world_dir = std::env::temp_dir().join(format!("falcon_save_{}", std::process::id()));
+        let region_dir = world_dir.join("region");
+        std::fs::create_dir_all(&region_dir).unwrap();
+        let saved = |data_version: i32| {
+            let level = [(String::from("Status"), Value::String(String::from("full")))].into_iter().collect();
+            let root = [(String::from("DataVersion"), Value::Int(data_version)), (String::from("Level"), Value::Compound(level))];
+            fastnbt::to_bytes(&Value::Compound(root.into_iter().collect())).unwrap()
+        };
+        let newer_nbt = saved(2860);
+        let mut data = vec![None; 32 * 32];
+        data[0] = Some(saved(2586));
+        data[1] = Some(newer_nbt.clone());
+        Region::from_chunks(0, 0, &data).unwrap().save(&region_dir).unwrap();
+
+        let mut old = Chunk::empty(ChunkPos::new(0, 0), &Dimension::overworld());
+        old.set_block_at(1, 2, 3, Blocks::Stone);
+        let mut newer = Chunk::empty(ChunkPos::new(1, 0), &Dimension::overworld());
+        newer.set_block_at(1, 2, 3, Blocks::Stone);
+        assert_eq!(save_world(&world_dir, [&old, &newer]).unwrap(), 1);
+
+        let region = Region::open(region_dir.join("r.0.0.mca")).unwrap();
+        let merged: HashMap<String, Value> = fastnbt::from_bytes(&region.chunk_data(0, 0).unwrap().unwrap()).unwrap();
+        assert_eq!(merged["DataVersion"], Value::Int(2730));
+        assert_eq!(region.chunk_data(1, 0).unwrap(), Some(newer_nbt));
+        std::fs::remove_dir_all(&world_dir).unwrap();
+    }
+
+    #[test]
+    pub fn test_external_chunk() {
+        let region_dir = std::env::temp_dir()
+            .join(format!("falcon_anvil_{}", std::process::id()))
+            .join("region");
+        std::fs::create_dir_all(&region_dir).unwrap();
+        let mut chunk = Chunk::empty(ChunkPos::new(-31, 2), &Dimension::overworld());
+        chunk.set_block_at(3, 0, 7, Blocks::Stone);
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&encode_chunk(&chunk).unwrap()).unwrap();
+        std::fs::write(region_dir.join("c.-31.2.mcc"), encoder.finish().unwrap()).unwrap();
+
+        // chunk (1, 2) is external, chunk (2, 2) has an unknown compression
+        let mut data = vec![0u8; 4 * SECTOR_LEN];
+        data[4 * (1 + 2 * 32)..4 * (1 + 2 * 32) + 4].copy_from_slice(&[0, 0, 2, 1]);
+        data[2 * SECTOR_LEN..2 * SECTOR_LEN + 5].copy_from_slice(&[0, 0, 0, 1, 2 | 128]);
+        data[4 * (2 + 2 * 32)..4 * (2 + 2 * 32) + 4].copy_from_slice(&[0, 0, 3, 1]);
+        data[3 * SECTOR_LEN..3 * SECTOR_LEN + 5].copy_from_slice(&[0, 0, 0, 1, 9]);
+        std::fs::write(region_dir.join("r.-1.0.mca"), data).unwrap();
+
+        let chunks = load_world(region_dir.parent().unwrap(), Blocks::Air, Biome::Plains).unwrap();
+        std::fs::remove_dir_all(region_dir.parent().unwrap()).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(*chunks[0].get_position(), ChunkPos::new(-31, 2));
+        assert_eq!(chunks[0].block_at(3, 0, 7), Blocks::Stone);
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// 1.14, bamboo jungles were added.
+const BAMBOO_PROTOCOL: i32 = 477;
+/// 1.16, the nether biomes were added.
+const NETHER_UPDATE_PROTOCOL: i32 = 735;
+
+/// Biomes that got renamed or merged after 1.17 and their closest equivalent.
+const ALIASES: [(&str, Biome); 22] = [
+    // 1.16
+    ("nether", Biome::NetherWastes),
+    // 1.18
+    ("windswept_hills", Biome::Mountains),
+    ("windswept_gravelly_hills", Biome::GravellyMountains),
+    ("windswept_forest", Biome::WoodedMountains),
+    ("windswept_savanna", Biome::ShatteredSavanna),
+    ("snowy_plains", Biome::SnowyTundra),
+    ("old_growth_pine_taiga", Biome::GiantTreeTaiga),
+    ("old_growth_spruce_taiga", Biome::GiantSpruceTaiga),
+    ("old_growth_birch_forest", Biome::TallBirchForest),
+    ("sparse_jungle", Biome::JungleEdge),
+    ("stony_shore", Biome::StoneShore),
+    ("wooded_badlands", Biome::WoodedBadlandsPlateau),
+    ("meadow", Biome::Plains),
+    ("grove", Biome::SnowyTaiga),
+    ("snowy_slopes", Biome::SnowyMountains),
+    ("frozen_peaks", Biome::SnowyMountains),
+    ("jagged_peaks", Biome::SnowyMountains),
+    ("stony_peaks", Biome::Mountains),
+    // 1.19
+    ("mangrove_swamp", Biome::Swamp),
+    ("deep_dark", Biome::DripstoneCaves),
+    // 1.20
+    ("cherry_grove", Biome::FlowerForest),
+    // 1.21
+    ("pale_garden", Biome::DarkForest),
+];
+
+macro_rules! biomes {
+    ($($(#[$attr:meta])* $variant:ident = $id:literal, $name:literal;)*) => {
+        /// The biomes of 1.17 with their numeric ids.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        pub enum Biome {
+            $($(#[$attr])* $variant),*
+        }
+
+        impl Biome {
+            /// The numeric id used by worlds and 1.16 clients.
+            pub fn id(self) -> i32 {
+                match self {
+                    $(Biome::$variant => $id),*
+                }
+            }
+
+            pub fn from_id(id: i32) -> Option<Self> {
+                match id {
+                    $($id => Some(Biome::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// The name without the `minecraft` namespace.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Biome::$variant => $name),*
+                }
+            }
+
+            /// Finds a biome by name, with or without the `minecraft` namespace.
+            /// Biomes of newer versions map to their closest equivalent.
+            pub fn from_name(name: &str) -> Option<Self> {
+                let name = name.strip_prefix("minecraft:").unwrap_or(name);
+                match name {
+                    $($name => Some(Biome::$variant),)*
+                    _ => ALIASES.iter().find(|(alias, _)| *alias == name).map(|(_, biome)| *biome),
+                }
+            }
+        }
+    };
+}
+
+biomes! {
+    Ocean = 0, "ocean";
+    #[default]
+    Plains = 1, "plains";
+    Desert = 2, "desert";
+    Mountains = 3, "mountains";
+    Forest = 4, "forest";
+    Taiga = 5, "taiga";
+    Swamp = 6, "swamp";
+    River = 7, "river";
+    NetherWastes = 8, "nether_wastes";
+    TheEnd = 9, "the_end";
+    FrozenOcean = 10, "frozen_ocean";
+    FrozenRiver = 11, "frozen_river";
+    SnowyTundra = 12, "snowy_tundra";
+    SnowyMountains = 13, "snowy_mountains";
+    MushroomFields = 14, "mushroom_fields";
+    MushroomFieldShore = 15, "mushroom_field_shore";
+    Beach = 16, "beach";
+    DesertHills = 17, "desert_hills";
+    WoodedHills = 18, "wooded_hills";
+    TaigaHills = 19, "taiga_hills";
+    MountainEdge = 20, "mountain_edge";
+    Jungle = 21, "jungle";
+    JungleHills = 22, "jungle_hills";
+    JungleEdge = 23, "jungle_edge";
+    DeepOcean = 24, "deep_ocean";
+    StoneShore = 25, "stone_shore";
+    SnowyBeach = 26, "snowy_beach";
+    BirchForest = 27, "birch_forest";
+    BirchForestHills = 28, "birch_forest_hills";
+    DarkForest = 29, "dark_forest";
+    SnowyTaiga = 30, "snowy_taiga";
+    SnowyTaigaHills = 31, "snowy_taiga_hills";
+    GiantTreeTaiga = 32, "giant_tree_taiga";
+    GiantTreeTaigaHills = 33, "giant_tree_taiga_hills";
+    WoodedMountains = 34, "wooded_mountains";
+    Savanna = 35, "savanna";
+    SavannaPlateau = 36, "savanna_plateau";
+    Badlands = 37, "badlands";
+    WoodedBadlandsPlateau = 38, "wooded_badlands_plateau";
+    BadlandsPlateau = 39, "badlands_plateau";
+    SmallEndIslands = 40, "small_end_islands";
+    EndMidlands = 41, "end_midlands";
+    EndHighlands = 42, "end_highlands";
+    EndBarrens = 43, "end_barrens";
+    WarmOcean = 44, "warm_ocean";
+    LukewarmOcean = 45, "lukewarm_ocean";
+    ColdOcean = 46, "cold_ocean";
+    DeepWarmOcean = 47, "deep_warm_ocean";
+    DeepLukewarmOcean = 48, "deep_lukewarm_ocean";
+    DeepColdOcean = 49, "deep_cold_ocean";
+    DeepFrozenOcean = 50, "deep_frozen_ocean";
+    TheVoid = 127, "the_void";
+    SunflowerPlains = 129, "sunflower_plains";
+    DesertLakes = 130, "desert_lakes";
+    GravellyMountains = 131, "gravelly_mountains";
+    FlowerForest = 132, "flower_forest";
+    TaigaMountains = 133, "taiga_mountains";
+    SwampHills = 134, "swamp_hills";
+    IceSpikes = 140, "ice_spikes";
+    ModifiedJungle = 149, "modified_jungle";
+    ModifiedJungleEdge = 151, "modified_jungle_edge";
+    TallBirchForest = 155, "tall_birch_forest";
+    TallBirchHills = 156, "tall_birch_hills";
+    DarkForestHills = 157, "dark_forest_hills";
+    SnowyTaigaMountains = 158, "snowy_taiga_mountains";
+    GiantSpruceTaiga = 160, "giant_spruce_taiga";
+    GiantSpruceTaigaHills = 161, "giant_spruce_taiga_hills";
+    ModifiedGravellyMountains = 162, "modified_gravelly_mountains";
+    ShatteredSavanna = 163, "shattered_savanna";
+    ShatteredSavannaPlateau = 164, "shattered_savanna_plateau";
+    ErodedBadlands = 165, "eroded_badlands";
+    ModifiedWoodedBadlandsPlateau = 166, "modified_wooded_badlands_plateau";
+    ModifiedBadlandsPlateau = 167, "modified_badlands_plateau";
+    BambooJungle = 168, "bamboo_jungle";
+    BambooJungleHills = 169, "bamboo_jungle_hills";
+    SoulSandValley = 170, "soul_sand_valley";
+    CrimsonForest = 171, "crimson_forest";
+    WarpedForest = 172, "warped_forest";
+    BasaltDeltas = 173, "basalt_deltas";
+    DripstoneCaves = 174, "dripstone_caves";
+    LushCaves = 175, "lush_caves";
+}
+
+impl Biome {
+    /// The id a client of the given protocol version knows this biome by,
+    /// biomes the client doesn't know are swapped for a similar one.
+    pub fn protocol_id(self, protocol: i32) -> i32 {
+        match self {
+            Biome::BambooJungle if protocol < BAMBOO_PROTOCOL => Biome::Jungle.id(),
+            Biome::BambooJungleHills if protocol < BAMBOO_PROTOCOL => Biome::JungleHills.id(),
+            Biome::SoulSandValley | Biome::CrimsonForest | Biome::WarpedForest | Biome::BasaltDeltas if protocol < NETHER_UPDATE_PROTOCOL => {
+                Biome::NetherWastes.id()
+            },
+            // no supported client knows the cave biomes
+            Biome::DripstoneCaves | Biome::LushCaves => Biome::Plains.id(),
+            _ => self.id(),
+        }
+    }
+}
+
+impl Display for Biome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result { write!(f, "minecraft:{}", self.name()) }
+}
+
+#[derive(Debug, Error)]
+#[error("Unknown biome \"{0}\"")]
+pub struct ParseBiomeError(String);
+
+impl FromStr for Biome {
+    type Err = ParseBiomeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Biome::from_name(s).ok_or_else(|| ParseBiomeError(s.to_string())) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Biome;
+
+    #[test]
+    pub fn test_biome_names() {
+        assert_eq!(Biome::from_name("minecraft:plains"), Some(Biome::Plains));
+        assert_eq!(Biome::from_name("nether"), Some(Biome::NetherWastes));
+        assert_eq!(Biome::from_name("minecraft:windswept_hills"), Some(Biome::Mountains));
+        assert_eq!(Biome::from_name("minecraft:cheese"), None);
+        assert_eq!(Biome::from_id(168), Some(Biome::BambooJungle));
+        assert_eq!(Biome::BambooJungle.protocol_id(404), 21);
+        assert_eq!(Biome::BambooJungle.protocol_id(498), 168);
+        assert_eq!(Biome::CrimsonForest.protocol_id(578), 8);
+        assert_eq!(Biome::CrimsonForest.to_string(), "minecraft:crimson_forest");
+    }
+}
@@ -0,0 +1,105 @@
+/// Values of a fixed number of bits packed into longs. Values never span
+/// two longs, like in the 1.16 protocol and in Anvil since 20w17a.
+/// With zero bits every value is zero and nothing is allocated.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitStorage {
+    bits: u8,
+    len: usize,
+    data: Vec<u64>,
+}
+
+impl BitStorage {
+    /// `len` zeros of `bits` bits, at most 16.
+    pub fn new(bits: u8, len: usize) -> Self {
+        BitStorage {
+            bits,
+            len,
+            data: vec![0; Self::longs(bits, len)],
+        }
+    }
+
+    /// Packs the first `len` values, missing values are zero.
+    pub fn from_values<I: IntoIterator<Item = u16>>(bits: u8, len: usize, values: I) -> Self {
+        let mut storage = BitStorage::new(bits, len);
+        if bits > 0 {
+            let per_long = 64 / bits as usize;
+            for (index, value) in values.into_iter().take(len).enumerate() {
+                storage.data[index / per_long] |= (value as u64) << ((index % per_long) * bits as usize);
+            }
+        }
+        storage
+    }
+
+    /// The number of longs needed for `len` values.
+    pub fn longs(bits: u8, len: usize) -> usize {
+        match bits {
+            0 => 0,
+            bits => len.div_ceil(64 / bits as usize),
+        }
+    }
+
+    pub fn bits(&self) -> u8 { self.bits }
+
+    pub fn len(&self) -> usize { self.len }
+
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    pub fn data(&self) -> &[u64] { &self.data }
+
+    pub fn into_data(self) -> Vec<u64> { self.data }
+
+    pub fn get(&self, index: usize) -> u16 {
+        if self.bits == 0 {
+            return 0;
+        }
+        let (long, shift) = self.position(index);
+        ((self.data[long] >> shift) & self.mask()) as u16
+    }
+
+    pub fn set(&mut self, index: usize, value: u16) {
+        debug_assert!(self.bits == 16 || value >> self.bits == 0, "{} doesn't fit in {} bits", value, self.bits);
+        if self.bits == 0 {
+            return;
+        }
+        let (long, shift) = self.position(index);
+        let mask = self.mask();
+        self.data[long] = (self.data[long] & !(mask << shift)) | ((value as u64 & mask) << shift);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u16> + '_ { (0..self.len).map(|index| self.get(index)) }
+
+    /// The same values with a different number of bits.
+    pub fn resized(&self, bits: u8) -> Self { BitStorage::from_values(bits, self.len, self.iter()) }
+
+    fn position(&self, index: usize) -> (usize, usize) {
+        let per_long = 64 / self.bits as usize;
+        (index / per_long, (index % per_long) * self.bits as usize)
+    }
+
+    fn mask(&self) -> u64 { (1u64 << self.bits) - 1 }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BitStorage;
+
+    #[test]
+    pub fn test_bit_storage() {
+        let values: Vec<u16> = (0..100).map(|i| (i * 7) % 32).collect();
+        let mut storage = BitStorage::from_values(5, values.len(), values.iter().copied());
+        assert_eq!(storage.data().len(), 9);
+        assert_eq!(storage.iter().collect::<Vec<_>>(), values);
+        assert_eq!(storage.data()[0] >> 60, 0, "values don't span two longs");
+
+        storage.set(12, 31);
+        storage.set(13, 0);
+        assert_eq!((storage.get(11), storage.get(12), storage.get(13)), (values[11], 31, 0));
+        let resized = storage.resized(12);
+        assert_eq!(resized.iter().collect::<Vec<_>>(), storage.iter().collect::<Vec<_>>());
+
+        let single = BitStorage::new(0, 4096);
+        assert!(single.data().is_empty());
+        assert_eq!(single.get(4095), 0);
+        assert_eq!(single.resized(4).data(), [0; 256]);
+    }
+}
@@ -0,0 +1,407 @@
+//! Block entities like signs, skulls, banners and containers. Their data
+//! is kept in the format of 1.16, data of other versions gets upgraded
+//! when it is loaded.
+
+use std::collections::HashMap;
+
+use fastnbt::{IntArray, Value};
+
+use crate::world::BlockPosition;
+
+/// Block entity ids from before 1.11 and their namespaced equivalent.
+const LEGACY_IDS: [(&str, &str); 24] = [
+    ("Airportal", "minecraft:end_portal"),
+    ("Banner", "minecraft:banner"),
+    ("Beacon", "minecraft:beacon"),
+    ("Cauldron", "minecraft:brewing_stand"),
+    ("Chest", "minecraft:chest"),
+    ("Comparator", "minecraft:comparator"),
+    ("Control", "minecraft:command_block"),
+    ("DLDetector", "minecraft:daylight_detector"),
+    ("Dropper", "minecraft:dropper"),
+    ("EnchantTable", "minecraft:enchanting_table"),
+    ("EndGateway", "minecraft:end_gateway"),
+    ("EnderChest", "minecraft:ender_chest"),
+    ("FlowerPot", "minecraft:flower_pot"),
+    ("Furnace", "minecraft:furnace"),
+    ("Hopper", "minecraft:hopper"),
+    ("MobSpawner", "minecraft:mob_spawner"),
+    ("Music", "minecraft:noteblock"),
+    ("Piston", "minecraft:piston"),
+    ("RecordPlayer", "minecraft:jukebox"),
+    ("Sign", "minecraft:sign"),
+    ("Skull", "minecraft:skull"),
+    ("Structure", "minecraft:structure_block"),
+    ("Trap", "minecraft:dispenser"),
+    ("Bed", "minecraft:bed"),
+];
+
+/// Banner pattern names since 1.20.5 and the codes used before.
+const BANNER_PATTERNS: [(&str, &str); 40] = [
+    ("base", "b"),
+    ("square_bottom_left", "bl"),
+    ("square_bottom_right", "br"),
+    ("square_top_left", "tl"),
+    ("square_top_right", "tr"),
+    ("stripe_bottom", "bs"),
+    ("stripe_top", "ts"),
+    ("stripe_left", "ls"),
+    ("stripe_right", "rs"),
+    ("stripe_center", "cs"),
+    ("stripe_middle", "ms"),
+    ("stripe_downright", "drs"),
+    ("stripe_downleft", "dls"),
+    ("small_stripes", "ss"),
+    ("cross", "cr"),
+    ("straight_cross", "sc"),
+    ("triangle_bottom", "bt"),
+    ("triangle_top", "tt"),
+    ("triangles_bottom", "bts"),
+    ("triangles_top", "tts"),
+    ("diagonal_left", "ld"),
+    ("diagonal_up_right", "rd"),
+    ("diagonal_up_left", "lud"),
+    ("diagonal_right", "rud"),
+    ("circle", "mc"),
+    ("rhombus", "mr"),
+    ("half_vertical", "vh"),
+    ("half_horizontal", "hh"),
+    ("half_vertical_right", "vhr"),
+    ("half_horizontal_bottom", "hhb"),
+    ("border", "bo"),
+    ("curly_border", "cbo"),
+    ("gradient", "gra"),
+    ("gradient_up", "gru"),
+    ("bricks", "bri"),
+    ("globe", "glb"),
+    ("creeper", "cre"),
+    ("skull", "sku"),
+    ("flower", "flo"),
+    ("mojang", "moj"),
+];
+
+const DYE_COLORS: [&str; 16] =
+    ["white", "orange", "magenta", "light_blue", "yellow", "lime", "pink", "gray", "light_gray", "cyan", "purple", "blue", "brown", "green", "red", "black"];
+
+#[derive(Clone, Debug)]
+pub struct BlockEntity {
+    position: BlockPosition,
+    id: String,
+    data: HashMap<String, Value>,
+}
+
+impl BlockEntity {
+    /// Creates a block entity, `id` and `data` may be
+    /// in the format of any version since 1.8.
+    pub fn new(position: BlockPosition, id: &str, mut data: HashMap<String, Value>) -> Self {
+        let id = upgrade_id(id);
+        for key in ["id", "Id", "x", "y", "z", "Pos", "keepPacked"] {
+            data.remove(key);
+        }
+        match id.as_str() {
+            "minecraft:sign" | "minecraft:hanging_sign" => upgrade_sign(&mut data),
+            "minecraft:skull" => upgrade_skull(&mut data),
+            "minecraft:banner" => upgrade_banner(&mut data),
+            _ => {},
+        }
+        BlockEntity { position, id, data }
+    }
+
+    /// Reads a block entity the way chunks store it, with
+    /// its id and absolute position next to its data.
+    pub fn from_nbt(nbt: Value) -> Option<Self> {
+        let data = match nbt {
+            Value::Compound(data) => data,
+            _ => return None,
+        };
+        let position = match (data.get("x"), data.get("y"), data.get("z")) {
+            (Some(x), Some(y), Some(z)) => BlockPosition::new(int(x)?, int(y)?, int(z)?),
+            _ => return None,
+        };
+        let id = match data.get("id") {
+            Some(Value::String(id)) => id.clone(),
+            _ => return None,
+        };
+        Some(BlockEntity::new(position, &id, data))
+    }
+
+    /// Reads a block entity of a schematic, these store their position
+    /// relative to the schematic in `Pos` and version 3 schematics nest
+    /// the data in `Data`. Pre 1.13 schematics use the chunk format.
+    pub fn from_schematic(nbt: Value) -> Option<Self> {
+        let mut data = match nbt {
+            Value::Compound(data) => data,
+            _ => return None,
+        };
+        let position = match data.get("Pos") {
+            Some(Value::IntArray(pos)) if pos.len() == 3 => BlockPosition::new(pos[0], pos[1], pos[2]),
+            Some(_) => return None,
+            None => return BlockEntity::from_nbt(Value::Compound(data)),
+        };
+        let id = match data.get("Id") {
+            Some(Value::String(id)) => id.clone(),
+            _ => return None,
+        };
+        if let Some(Value::Compound(nested)) = data.remove("Data") {
+            data.extend(nested);
+        }
+        Some(BlockEntity::new(position, &id, data))
+    }
+
+    /// The way chunks store a block entity.
+    pub fn to_nbt(&self) -> Value {
+        let mut nbt = self.data.clone();
+        nbt.insert(String::from("id"), Value::String(self.id.clone()));
+        nbt.insert(String::from("x"), Value::Int(self.position.get_x()));
+        nbt.insert(String::from("y"), Value::Int(self.position.get_y()));
+        nbt.insert(String::from("z"), Value::Int(self.position.get_z()));
+        Value::Compound(nbt)
+    }
+
+    /// The way version 2 schematics store a block entity, `Pos` is the
+    /// position of the block entity.
+    pub fn to_schematic(&self) -> Value {
+        let mut nbt = self.data.clone();
+        nbt.insert(String::from("Id"), Value::String(self.id.clone()));
+        nbt.insert(String::from("Pos"), Value::IntArray(IntArray::new(vec![self.position.get_x(), self.position.get_y(), self.position.get_z()])));
+        Value::Compound(nbt)
+    }
+
+    pub fn position(&self) -> BlockPosition { self.position }
+
+    pub fn set_position(&mut self, position: BlockPosition) { self.position = position; }
+
+    pub fn id(&self) -> &str { &self.id }
+
+    pub fn data(&self) -> &HashMap<String, Value> { &self.data }
+}
+
+fn int(value: &Value) -> Option<i32> {
+    match value {
+        Value::Byte(v) => Some(*v as i32),
+        Value::Short(v) => Some(*v as i32),
+        Value::Int(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn upgrade_id(id: &str) -> String {
+    match LEGACY_IDS.iter().find(|(legacy, _)| *legacy == id) {
+        Some((_, id)) => String::from(*id),
+        None if id.contains(':') => String::from(id),
+        None => format!("minecraft:{}", id),
+    }
+}
+
+/// Moves the text of 1.20 signs back to `Text1` to `Text4` and turns
+/// the plain text lines of pre 1.8 signs into JSON components.
+fn upgrade_sign(data: &mut HashMap<String, Value>) {
+    if let Some(Value::Compound(front)) = data.remove("front_text") {
+        if let Some(Value::List(messages)) = front.get("messages") {
+            for (i, message) in messages.iter().take(4).enumerate() {
+                data.insert(format!("Text{}", i + 1), message.clone());
+            }
+        }
+        if let Some(color) = front.get("color") {
+            data.insert(String::from("Color"), color.clone());
+        }
+        if let Some(glowing) = front.get("has_glowing_text") {
+            data.insert(String::from("GlowingText"), glowing.clone());
+        }
+    }
+    data.remove("back_text");
+    data.remove("is_waxed");
+    for i in 1..=4 {
+        let key = format!("Text{}", i);
+        let text = match data.get(&key) {
+            Some(Value::String(text)) if text == "null" => String::from("\"\""),
+            Some(Value::String(text)) if is_json(text) => continue,
+            Some(Value::String(text)) => json_string(text),
+            _ => String::from("\"\""),
+        };
+        data.insert(key, Value::String(text));
+    }
+}
+
+fn is_json(text: &str) -> bool {
+    let text = text.trim();
+    (text.starts_with('{') && text.ends_with('}'))
+        || (text.starts_with('[') && text.ends_with(']'))
+        || (text.len() > 1 && text.starts_with('"') && text.ends_with('"'))
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Converts the 1.20.5 `profile` and plain owner names to a `SkullOwner`
+/// compound whose `Id` is an int array.
+fn upgrade_skull(data: &mut HashMap<String, Value>) {
+    if let Some(profile) = data.remove("profile") {
+        let owner = match profile {
+            Value::String(name) => [(String::from("Name"), Value::String(name))].into_iter().collect(),
+            Value::Compound(profile) => {
+                let mut owner = HashMap::new();
+                if let Some(name) = profile.get("name") {
+                    owner.insert(String::from("Name"), name.clone());
+                }
+                if let Some(id) = profile.get("id") {
+                    owner.insert(String::from("Id"), id.clone());
+                }
+                if let Some(Value::List(properties)) = profile.get("properties") {
+                    let textures = properties
+                        .iter()
+                        .filter_map(|property| match property {
+                            Value::Compound(property) if property.get("name") == Some(&Value::String(String::from("textures"))) => {
+                                let mut texture: HashMap<String, Value> = HashMap::new();
+                                texture.insert(String::from("Value"), property.get("value")?.clone());
+                                if let Some(signature) = property.get("signature") {
+                                    texture.insert(String::from("Signature"), signature.clone());
+                                }
+                                Some(Value::Compound(texture))
+                            },
+                            _ => None,
+                        })
+                        .collect();
+                    let properties = [(String::from("textures"), Value::List(textures))].into_iter().collect();
+                    owner.insert(String::from("Properties"), Value::Compound(properties));
+                }
+                owner
+            },
+            _ => return,
+        };
+        data.insert(String::from("SkullOwner"), Value::Compound(owner));
+    }
+    if let Some(owner) = data.remove("Owner") {
+        data.entry(String::from("SkullOwner")).or_insert(owner);
+    }
+    match data.get_mut("SkullOwner") {
+        Some(Value::String(name)) => {
+            let name = [(String::from("Name"), Value::String(std::mem::take(name)))].into_iter().collect();
+            data.insert(String::from("SkullOwner"), Value::Compound(name));
+        },
+        Some(Value::Compound(owner)) => {
+            if let Some(Value::String(id)) = owner.get("Id") {
+                match uuid::Uuid::parse_str(id) {
+                    Ok(id) => {
+                        let id = id.as_u128();
+                        let ints = (0..4).map(|i| (id >> (96 - i * 32)) as u32 as i32).collect();
+                        owner.insert(String::from("Id"), Value::IntArray(IntArray::new(ints)));
+                    },
+                    Err(_) => {
+                        owner.remove("Id");
+                    },
+                }
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Converts the 1.20.5 pattern and color names to codes and dye ids.
+fn upgrade_banner(data: &mut HashMap<String, Value>) {
+    if let Some(Value::List(patterns)) = data.remove("patterns") {
+        let patterns = patterns
+            .into_iter()
+            .filter_map(|pattern| {
+                let pattern = match pattern {
+                    Value::Compound(pattern) => pattern,
+                    _ => return None,
+                };
+                let code = match pattern.get("pattern") {
+                    Some(Value::String(name)) => {
+                        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+                        BANNER_PATTERNS.iter().find(|(pattern, _)| *pattern == name)?.1
+                    },
+                    _ => return None,
+                };
+                let color = match pattern.get("color") {
+                    Some(Value::String(color)) => DYE_COLORS.iter().position(|dye| dye == color)? as i32,
+                    _ => return None,
+                };
+                let pattern = [(String::from("Pattern"), Value::String(String::from(code))), (String::from("Color"), Value::Int(color))];
+                Some(Value::Compound(pattern.into_iter().collect()))
+            })
+            .collect();
+        data.insert(String::from("Patterns"), Value::List(patterns));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fastnbt::{IntArray, Value};
+
+    use super::BlockEntity;
+
+    fn compound(entries: Vec<(&str, Value)>) -> Value { Value::Compound(entries.into_iter().map(|(key, value)| (String::from(key), value)).collect()) }
+
+    fn string(s: &str) -> Value { Value::String(String::from(s)) }
+
+    #[test]
+    pub fn test_upgrade() {
+        let sign = compound(vec![
+            ("Pos", Value::IntArray(IntArray::new(vec![1, 2, 3]))),
+            ("Id", string("minecraft:sign")),
+            (
+                "Data",
+                compound(vec![(
+                    "front_text",
+                    compound(vec![
+                        ("messages", Value::List(vec![string("{\"text\":\"Hello\"}"), string("\"\""), string("\"\""), string("\"\"")])),
+                        ("color", string("red")),
+                    ]),
+                )]),
+            ),
+        ]);
+        let sign = BlockEntity::from_schematic(sign).unwrap();
+        assert_eq!(sign.id(), "minecraft:sign");
+        assert_eq!(sign.position().get_z(), 3);
+        assert_eq!(sign.data()["Text1"], string("{\"text\":\"Hello\"}"));
+        assert_eq!(sign.data()["Color"], string("red"));
+        assert!(!sign.data().contains_key("Data"));
+
+        let legacy =
+            compound(vec![("id", string("Sign")), ("x", Value::Int(-5)), ("y", Value::Int(64)), ("z", Value::Int(9)), ("Text1", string("Say \"hi\""))]);
+        let legacy = BlockEntity::from_nbt(legacy).unwrap();
+        assert_eq!(legacy.id(), "minecraft:sign");
+        assert_eq!(legacy.data()["Text1"], string("\"Say \\\"hi\\\"\""));
+        assert_eq!(legacy.data()["Text4"], string("\"\""));
+        assert!(!legacy.data().contains_key("x"));
+
+        let skull = compound(vec![
+            ("id", string("Skull")),
+            ("x", Value::Int(0)),
+            ("y", Value::Int(0)),
+            ("z", Value::Int(0)),
+            ("Owner", compound(vec![("Id", string("069a79f4-44e9-4726-a5be-fca90e38aaf5")), ("Name", string("Notch"))])),
+        ]);
+        let skull = BlockEntity::from_nbt(skull).unwrap();
+        match &skull.data()["SkullOwner"] {
+            Value::Compound(owner) => {
+                assert_eq!(owner["Id"], Value::IntArray(IntArray::new(vec![0x069a79f4, 0x44e94726, 0xa5befca9u32 as i32, 0x0e38aaf5])))
+            },
+            _ => panic!("SkullOwner should be a compound"),
+        }
+
+        let banner = compound(vec![
+            ("id", string("minecraft:banner")),
+            ("x", Value::Int(0)),
+            ("y", Value::Int(0)),
+            ("z", Value::Int(0)),
+            ("patterns", Value::List(vec![compound(vec![("pattern", string("minecraft:creeper")), ("color", string("lime"))])])),
+        ]);
+        let banner = BlockEntity::from_nbt(banner).unwrap();
+        assert_eq!(banner.data()["Patterns"], Value::List(vec![compound(vec![("Pattern", string("cre")), ("Color", Value::Int(5))])]));
+    }
+}
@@ -0,0 +1,2362 @@
+//! Block state strings (`minecraft:name[key=value,...]`) of [`Blocks`],
+//! the inverse of its `FromStr` impl. Properties are written in
+//! alphabetical order like vanilla does.
+//!
+//! [`Blocks`] is also hashed here instead of deriving it in the generated
+//! code, every state has its own 1.17.1 global id.
+
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use crate::world::blocks::*;
+
+impl Blocks {
+    /// Parses a block state string as vanilla writes it. The generated
+    /// `FromStr` impl reads the `type` property as `typed`.
+    pub fn parse_state(state: &str) -> Result<Self, ParseBlockError> {
+        if !state.contains("[type=") && !state.contains(",type=") {
+            return Blocks::from_str(state);
+        }
+        Blocks::from_str(&state.replace("[type=", "[typed=").replace(",type=", ",typed="))
+    }
+}
+
+impl Hash for Blocks {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.get_global_id_2730().hash(state) }
+}
+
+impl Display for Blocks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Blocks::Air => f.write_str("minecraft:air"),
+            Blocks::Stone => f.write_str("minecraft:stone"),
+            Blocks::Granite => f.write_str("minecraft:granite"),
+            Blocks::PolishedGranite => f.write_str("minecraft:polished_granite"),
+            Blocks::Diorite => f.write_str("minecraft:diorite"),
+            Blocks::PolishedDiorite => f.write_str("minecraft:polished_diorite"),
+            Blocks::Andesite => f.write_str("minecraft:andesite"),
+            Blocks::PolishedAndesite => f.write_str("minecraft:polished_andesite"),
+            Blocks::GrassBlock(state) => write!(f, "minecraft:grass_block[snowy={}]", state.snowy()),
+            Blocks::Dirt => f.write_str("minecraft:dirt"),
+            Blocks::CoarseDirt => f.write_str("minecraft:coarse_dirt"),
+            Blocks::Podzol(state) => write!(f, "minecraft:podzol[snowy={}]", state.snowy()),
+            Blocks::Cobblestone => f.write_str("minecraft:cobblestone"),
+            Blocks::OakPlanks => f.write_str("minecraft:oak_planks"),
+            Blocks::SprucePlanks => f.write_str("minecraft:spruce_planks"),
+            Blocks::BirchPlanks => f.write_str("minecraft:birch_planks"),
+            Blocks::JunglePlanks => f.write_str("minecraft:jungle_planks"),
+            Blocks::AcaciaPlanks => f.write_str("minecraft:acacia_planks"),
+            Blocks::DarkOakPlanks => f.write_str("minecraft:dark_oak_planks"),
+            Blocks::OakSapling(state) => write!(f, "minecraft:oak_sapling[stage={}]", state.stage()),
+            Blocks::SpruceSapling(state) => write!(f, "minecraft:spruce_sapling[stage={}]", state.stage()),
+            Blocks::BirchSapling(state) => write!(f, "minecraft:birch_sapling[stage={}]", state.stage()),
+            Blocks::JungleSapling(state) => write!(f, "minecraft:jungle_sapling[stage={}]", state.stage()),
+            Blocks::AcaciaSapling(state) => write!(f, "minecraft:acacia_sapling[stage={}]", state.stage()),
+            Blocks::DarkOakSapling(state) => write!(f, "minecraft:dark_oak_sapling[stage={}]", state.stage()),
+            Blocks::Bedrock => f.write_str("minecraft:bedrock"),
+            Blocks::Water(state) => write!(f, "minecraft:water[level={}]", state.level()),
+            Blocks::Lava(state) => write!(f, "minecraft:lava[level={}]", state.level()),
+            Blocks::Sand => f.write_str("minecraft:sand"),
+            Blocks::RedSand => f.write_str("minecraft:red_sand"),
+            Blocks::Gravel => f.write_str("minecraft:gravel"),
+            Blocks::GoldOre => f.write_str("minecraft:gold_ore"),
+            Blocks::DeepslateGoldOre => f.write_str("minecraft:deepslate_gold_ore"),
+            Blocks::IronOre => f.write_str("minecraft:iron_ore"),
+            Blocks::DeepslateIronOre => f.write_str("minecraft:deepslate_iron_ore"),
+            Blocks::CoalOre => f.write_str("minecraft:coal_ore"),
+            Blocks::DeepslateCoalOre => f.write_str("minecraft:deepslate_coal_ore"),
+            Blocks::NetherGoldOre => f.write_str("minecraft:nether_gold_ore"),
+            Blocks::OakLog(state) => write!(f, "minecraft:oak_log[axis={}]", state.axis()),
+            Blocks::SpruceLog(state) => write!(f, "minecraft:spruce_log[axis={}]", state.axis()),
+            Blocks::BirchLog(state) => write!(f, "minecraft:birch_log[axis={}]", state.axis()),
+            Blocks::JungleLog(state) => write!(f, "minecraft:jungle_log[axis={}]", state.axis()),
+            Blocks::AcaciaLog(state) => write!(f, "minecraft:acacia_log[axis={}]", state.axis()),
+            Blocks::DarkOakLog(state) => write!(f, "minecraft:dark_oak_log[axis={}]", state.axis()),
+            Blocks::StrippedSpruceLog(state) => write!(f, "minecraft:stripped_spruce_log[axis={}]", state.axis()),
+            Blocks::StrippedBirchLog(state) => write!(f, "minecraft:stripped_birch_log[axis={}]", state.axis()),
+            Blocks::StrippedJungleLog(state) => write!(f, "minecraft:stripped_jungle_log[axis={}]", state.axis()),
+            Blocks::StrippedAcaciaLog(state) => write!(f, "minecraft:stripped_acacia_log[axis={}]", state.axis()),
+            Blocks::StrippedDarkOakLog(state) => write!(f, "minecraft:stripped_dark_oak_log[axis={}]", state.axis()),
+            Blocks::StrippedOakLog(state) => write!(f, "minecraft:stripped_oak_log[axis={}]", state.axis()),
+            Blocks::OakWood(state) => write!(f, "minecraft:oak_wood[axis={}]", state.axis()),
+            Blocks::SpruceWood(state) => write!(f, "minecraft:spruce_wood[axis={}]", state.axis()),
+            Blocks::BirchWood(state) => write!(f, "minecraft:birch_wood[axis={}]", state.axis()),
+            Blocks::JungleWood(state) => write!(f, "minecraft:jungle_wood[axis={}]", state.axis()),
+            Blocks::AcaciaWood(state) => write!(f, "minecraft:acacia_wood[axis={}]", state.axis()),
+            Blocks::DarkOakWood(state) => write!(f, "minecraft:dark_oak_wood[axis={}]", state.axis()),
+            Blocks::StrippedOakWood(state) => write!(f, "minecraft:stripped_oak_wood[axis={}]", state.axis()),
+            Blocks::StrippedSpruceWood(state) => write!(f, "minecraft:stripped_spruce_wood[axis={}]", state.axis()),
+            Blocks::StrippedBirchWood(state) => write!(f, "minecraft:stripped_birch_wood[axis={}]", state.axis()),
+            Blocks::StrippedJungleWood(state) => write!(f, "minecraft:stripped_jungle_wood[axis={}]", state.axis()),
+            Blocks::StrippedAcaciaWood(state) => write!(f, "minecraft:stripped_acacia_wood[axis={}]", state.axis()),
+            Blocks::StrippedDarkOakWood(state) => write!(f, "minecraft:stripped_dark_oak_wood[axis={}]", state.axis()),
+            Blocks::OakLeaves(state) => write!(f, "minecraft:oak_leaves[distance={},persistent={}]", state.distance(), state.persistent()),
+            Blocks::SpruceLeaves(state) => write!(f, "minecraft:spruce_leaves[distance={},persistent={}]", state.distance(), state.persistent()),
+            Blocks::BirchLeaves(state) => write!(f, "minecraft:birch_leaves[distance={},persistent={}]", state.distance(), state.persistent()),
+            Blocks::JungleLeaves(state) => write!(f, "minecraft:jungle_leaves[distance={},persistent={}]", state.distance(), state.persistent()),
+            Blocks::AcaciaLeaves(state) => write!(f, "minecraft:acacia_leaves[distance={},persistent={}]", state.distance(), state.persistent()),
+            Blocks::DarkOakLeaves(state) => write!(f, "minecraft:dark_oak_leaves[distance={},persistent={}]", state.distance(), state.persistent()),
+            Blocks::AzaleaLeaves(state) => write!(f, "minecraft:azalea_leaves[distance={},persistent={}]", state.distance(), state.persistent()),
+            Blocks::FloweringAzaleaLeaves(state) => {
+                write!(f, "minecraft:flowering_azalea_leaves[distance={},persistent={}]", state.distance(), state.persistent())
+            },
+            Blocks::Sponge => f.write_str("minecraft:sponge"),
+            Blocks::WetSponge => f.write_str("minecraft:wet_sponge"),
+            Blocks::Glass => f.write_str("minecraft:glass"),
+            Blocks::LapisOre => f.write_str("minecraft:lapis_ore"),
+            Blocks::DeepslateLapisOre => f.write_str("minecraft:deepslate_lapis_ore"),
+            Blocks::LapisBlock => f.write_str("minecraft:lapis_block"),
+            Blocks::Dispenser(state) => write!(f, "minecraft:dispenser[facing={},triggered={}]", state.facing(), state.triggered()),
+            Blocks::Sandstone => f.write_str("minecraft:sandstone"),
+            Blocks::ChiseledSandstone => f.write_str("minecraft:chiseled_sandstone"),
+            Blocks::CutSandstone => f.write_str("minecraft:cut_sandstone"),
+            Blocks::NoteBlock(state) => write!(f, "minecraft:note_block[instrument={},note={},powered={}]", state.instrument(), state.note(), state.powered()),
+            Blocks::WhiteBed(state) => write!(f, "minecraft:white_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::OrangeBed(state) => write!(f, "minecraft:orange_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::MagentaBed(state) => write!(f, "minecraft:magenta_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::LightBlueBed(state) => write!(f, "minecraft:light_blue_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::YellowBed(state) => write!(f, "minecraft:yellow_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::LimeBed(state) => write!(f, "minecraft:lime_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::PinkBed(state) => write!(f, "minecraft:pink_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::GrayBed(state) => write!(f, "minecraft:gray_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::LightGrayBed(state) => write!(f, "minecraft:light_gray_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::CyanBed(state) => write!(f, "minecraft:cyan_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::PurpleBed(state) => write!(f, "minecraft:purple_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::BlueBed(state) => write!(f, "minecraft:blue_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::BrownBed(state) => write!(f, "minecraft:brown_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::GreenBed(state) => write!(f, "minecraft:green_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::RedBed(state) => write!(f, "minecraft:red_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::BlackBed(state) => write!(f, "minecraft:black_bed[facing={},occupied={},part={}]", state.facing(), state.occupied(), state.part()),
+            Blocks::PoweredRail(state) => {
+                write!(f, "minecraft:powered_rail[powered={},shape={},waterlogged={}]", state.powered(), state.shape(), state.waterlogged())
+            },
+            Blocks::DetectorRail(state) => {
+                write!(f, "minecraft:detector_rail[powered={},shape={},waterlogged={}]", state.powered(), state.shape(), state.waterlogged())
+            },
+            Blocks::StickyPiston(state) => write!(f, "minecraft:sticky_piston[extended={},facing={}]", state.extended(), state.facing()),
+            Blocks::Cobweb => f.write_str("minecraft:cobweb"),
+            Blocks::Grass => f.write_str("minecraft:grass"),
+            Blocks::Fern => f.write_str("minecraft:fern"),
+            Blocks::DeadBush => f.write_str("minecraft:dead_bush"),
+            Blocks::Seagrass => f.write_str("minecraft:seagrass"),
+            Blocks::TallSeagrass(state) => write!(f, "minecraft:tall_seagrass[half={}]", state.half()),
+            Blocks::Piston(state) => write!(f, "minecraft:piston[extended={},facing={}]", state.extended(), state.facing()),
+            Blocks::PistonHead(state) => write!(f, "minecraft:piston_head[facing={},short={},type={}]", state.facing(), state.short(), state.typed()),
+            Blocks::WhiteWool => f.write_str("minecraft:white_wool"),
+            Blocks::OrangeWool => f.write_str("minecraft:orange_wool"),
+            Blocks::MagentaWool => f.write_str("minecraft:magenta_wool"),
+            Blocks::LightBlueWool => f.write_str("minecraft:light_blue_wool"),
+            Blocks::YellowWool => f.write_str("minecraft:yellow_wool"),
+            Blocks::LimeWool => f.write_str("minecraft:lime_wool"),
+            Blocks::PinkWool => f.write_str("minecraft:pink_wool"),
+            Blocks::GrayWool => f.write_str("minecraft:gray_wool"),
+            Blocks::LightGrayWool => f.write_str("minecraft:light_gray_wool"),
+            Blocks::CyanWool => f.write_str("minecraft:cyan_wool"),
+            Blocks::PurpleWool => f.write_str("minecraft:purple_wool"),
+            Blocks::BlueWool => f.write_str("minecraft:blue_wool"),
+            Blocks::BrownWool => f.write_str("minecraft:brown_wool"),
+            Blocks::GreenWool => f.write_str("minecraft:green_wool"),
+            Blocks::RedWool => f.write_str("minecraft:red_wool"),
+            Blocks::BlackWool => f.write_str("minecraft:black_wool"),
+            Blocks::MovingPiston(state) => write!(f, "minecraft:moving_piston[facing={},type={}]", state.facing(), state.typed()),
+            Blocks::Dandelion => f.write_str("minecraft:dandelion"),
+            Blocks::Poppy => f.write_str("minecraft:poppy"),
+            Blocks::BlueOrchid => f.write_str("minecraft:blue_orchid"),
+            Blocks::Allium => f.write_str("minecraft:allium"),
+            Blocks::AzureBluet => f.write_str("minecraft:azure_bluet"),
+            Blocks::RedTulip => f.write_str("minecraft:red_tulip"),
+            Blocks::OrangeTulip => f.write_str("minecraft:orange_tulip"),
+            Blocks::WhiteTulip => f.write_str("minecraft:white_tulip"),
+            Blocks::PinkTulip => f.write_str("minecraft:pink_tulip"),
+            Blocks::OxeyeDaisy => f.write_str("minecraft:oxeye_daisy"),
+            Blocks::Cornflower => f.write_str("minecraft:cornflower"),
+            Blocks::WitherRose => f.write_str("minecraft:wither_rose"),
+            Blocks::LilyOfTheValley => f.write_str("minecraft:lily_of_the_valley"),
+            Blocks::BrownMushroom => f.write_str("minecraft:brown_mushroom"),
+            Blocks::RedMushroom => f.write_str("minecraft:red_mushroom"),
+            Blocks::GoldBlock => f.write_str("minecraft:gold_block"),
+            Blocks::IronBlock => f.write_str("minecraft:iron_block"),
+            Blocks::Bricks => f.write_str("minecraft:bricks"),
+            Blocks::Tnt(state) => write!(f, "minecraft:tnt[unstable={}]", state.unstable()),
+            Blocks::Bookshelf => f.write_str("minecraft:bookshelf"),
+            Blocks::MossyCobblestone => f.write_str("minecraft:mossy_cobblestone"),
+            Blocks::Obsidian => f.write_str("minecraft:obsidian"),
+            Blocks::Torch => f.write_str("minecraft:torch"),
+            Blocks::WallTorch(state) => write!(f, "minecraft:wall_torch[facing={}]", state.facing()),
+            Blocks::Fire(state) => write!(
+                f,
+                "minecraft:fire[age={},east={},north={},south={},up={},west={}]",
+                state.age(),
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.west()
+            ),
+            Blocks::SoulFire => f.write_str("minecraft:soul_fire"),
+            Blocks::Spawner => f.write_str("minecraft:spawner"),
+            Blocks::OakStairs(state) => {
+                write!(f, "minecraft:oak_stairs[facing={},half={},shape={},waterlogged={}]", state.facing(), state.half(), state.shape(), state.waterlogged())
+            },
+            Blocks::Chest(state) => write!(f, "minecraft:chest[facing={},type={},waterlogged={}]", state.facing(), state.typed(), state.waterlogged()),
+            Blocks::RedstoneWire(state) => write!(
+                f,
+                "minecraft:redstone_wire[east={},north={},power={},south={},west={}]",
+                state.east(),
+                state.north(),
+                state.power(),
+                state.south(),
+                state.west()
+            ),
+            Blocks::DiamondOre => f.write_str("minecraft:diamond_ore"),
+            Blocks::DeepslateDiamondOre => f.write_str("minecraft:deepslate_diamond_ore"),
+            Blocks::DiamondBlock => f.write_str("minecraft:diamond_block"),
+            Blocks::CraftingTable => f.write_str("minecraft:crafting_table"),
+            Blocks::Wheat(state) => write!(f, "minecraft:wheat[age={}]", state.age()),
+            Blocks::Farmland(state) => write!(f, "minecraft:farmland[moisture={}]", state.moisture()),
+            Blocks::Furnace(state) => write!(f, "minecraft:furnace[facing={},lit={}]", state.facing(), state.lit()),
+            Blocks::OakSign(state) => write!(f, "minecraft:oak_sign[rotation={},waterlogged={}]", state.rotation(), state.waterlogged()),
+            Blocks::SpruceSign(state) => write!(f, "minecraft:spruce_sign[rotation={},waterlogged={}]", state.rotation(), state.waterlogged()),
+            Blocks::BirchSign(state) => write!(f, "minecraft:birch_sign[rotation={},waterlogged={}]", state.rotation(), state.waterlogged()),
+            Blocks::AcaciaSign(state) => write!(f, "minecraft:acacia_sign[rotation={},waterlogged={}]", state.rotation(), state.waterlogged()),
+            Blocks::JungleSign(state) => write!(f, "minecraft:jungle_sign[rotation={},waterlogged={}]", state.rotation(), state.waterlogged()),
+            Blocks::DarkOakSign(state) => write!(f, "minecraft:dark_oak_sign[rotation={},waterlogged={}]", state.rotation(), state.waterlogged()),
+            Blocks::OakDoor(state) => write!(
+                f,
+                "minecraft:oak_door[facing={},half={},hinge={},open={},powered={}]",
+                state.facing(),
+                state.half(),
+                state.hinge(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::Ladder(state) => write!(f, "minecraft:ladder[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::Rail(state) => write!(f, "minecraft:rail[shape={},waterlogged={}]", state.shape(), state.waterlogged()),
+            Blocks::CobblestoneStairs(state) => write!(
+                f,
+                "minecraft:cobblestone_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::OakWallSign(state) => write!(f, "minecraft:oak_wall_sign[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::SpruceWallSign(state) => write!(f, "minecraft:spruce_wall_sign[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::BirchWallSign(state) => write!(f, "minecraft:birch_wall_sign[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::AcaciaWallSign(state) => write!(f, "minecraft:acacia_wall_sign[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::JungleWallSign(state) => write!(f, "minecraft:jungle_wall_sign[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::DarkOakWallSign(state) => write!(f, "minecraft:dark_oak_wall_sign[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::Lever(state) => write!(f, "minecraft:lever[face={},facing={},powered={}]", state.face(), state.facing(), state.powered()),
+            Blocks::StonePressurePlate(state) => write!(f, "minecraft:stone_pressure_plate[powered={}]", state.powered()),
+            Blocks::IronDoor(state) => write!(
+                f,
+                "minecraft:iron_door[facing={},half={},hinge={},open={},powered={}]",
+                state.facing(),
+                state.half(),
+                state.hinge(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::OakPressurePlate(state) => write!(f, "minecraft:oak_pressure_plate[powered={}]", state.powered()),
+            Blocks::SprucePressurePlate(state) => write!(f, "minecraft:spruce_pressure_plate[powered={}]", state.powered()),
+            Blocks::BirchPressurePlate(state) => write!(f, "minecraft:birch_pressure_plate[powered={}]", state.powered()),
+            Blocks::JunglePressurePlate(state) => write!(f, "minecraft:jungle_pressure_plate[powered={}]", state.powered()),
+            Blocks::AcaciaPressurePlate(state) => write!(f, "minecraft:acacia_pressure_plate[powered={}]", state.powered()),
+            Blocks::DarkOakPressurePlate(state) => write!(f, "minecraft:dark_oak_pressure_plate[powered={}]", state.powered()),
+            Blocks::RedstoneOre(state) => write!(f, "minecraft:redstone_ore[lit={}]", state.lit()),
+            Blocks::DeepslateRedstoneOre(state) => write!(f, "minecraft:deepslate_redstone_ore[lit={}]", state.lit()),
+            Blocks::RedstoneTorch(state) => write!(f, "minecraft:redstone_torch[lit={}]", state.lit()),
+            Blocks::RedstoneWallTorch(state) => write!(f, "minecraft:redstone_wall_torch[facing={},lit={}]", state.facing(), state.lit()),
+            Blocks::StoneButton(state) => write!(f, "minecraft:stone_button[face={},facing={},powered={}]", state.face(), state.facing(), state.powered()),
+            Blocks::Snow(state) => write!(f, "minecraft:snow[layers={}]", state.layers()),
+            Blocks::Ice => f.write_str("minecraft:ice"),
+            Blocks::SnowBlock => f.write_str("minecraft:snow_block"),
+            Blocks::Cactus(state) => write!(f, "minecraft:cactus[age={}]", state.age()),
+            Blocks::Clay => f.write_str("minecraft:clay"),
+            Blocks::SugarCane(state) => write!(f, "minecraft:sugar_cane[age={}]", state.age()),
+            Blocks::Jukebox(state) => write!(f, "minecraft:jukebox[has_record={}]", state.has_record()),
+            Blocks::OakFence(state) => write!(
+                f,
+                "minecraft:oak_fence[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::Pumpkin => f.write_str("minecraft:pumpkin"),
+            Blocks::Netherrack => f.write_str("minecraft:netherrack"),
+            Blocks::SoulSand => f.write_str("minecraft:soul_sand"),
+            Blocks::SoulSoil => f.write_str("minecraft:soul_soil"),
+            Blocks::Basalt(state) => write!(f, "minecraft:basalt[axis={}]", state.axis()),
+            Blocks::PolishedBasalt(state) => write!(f, "minecraft:polished_basalt[axis={}]", state.axis()),
+            Blocks::SoulTorch => f.write_str("minecraft:soul_torch"),
+            Blocks::SoulWallTorch(state) => write!(f, "minecraft:soul_wall_torch[facing={}]", state.facing()),
+            Blocks::Glowstone => f.write_str("minecraft:glowstone"),
+            Blocks::NetherPortal(state) => write!(f, "minecraft:nether_portal[axis={}]", state.axis()),
+            Blocks::CarvedPumpkin(state) => write!(f, "minecraft:carved_pumpkin[facing={}]", state.facing()),
+            Blocks::JackOLantern(state) => write!(f, "minecraft:jack_o_lantern[facing={}]", state.facing()),
+            Blocks::Cake(state) => write!(f, "minecraft:cake[bites={}]", state.bites()),
+            Blocks::Repeater(state) => {
+                write!(f, "minecraft:repeater[delay={},facing={},locked={},powered={}]", state.delay(), state.facing(), state.locked(), state.powered())
+            },
+            Blocks::WhiteStainedGlass => f.write_str("minecraft:white_stained_glass"),
+            Blocks::OrangeStainedGlass => f.write_str("minecraft:orange_stained_glass"),
+            Blocks::MagentaStainedGlass => f.write_str("minecraft:magenta_stained_glass"),
+            Blocks::LightBlueStainedGlass => f.write_str("minecraft:light_blue_stained_glass"),
+            Blocks::YellowStainedGlass => f.write_str("minecraft:yellow_stained_glass"),
+            Blocks::LimeStainedGlass => f.write_str("minecraft:lime_stained_glass"),
+            Blocks::PinkStainedGlass => f.write_str("minecraft:pink_stained_glass"),
+            Blocks::GrayStainedGlass => f.write_str("minecraft:gray_stained_glass"),
+            Blocks::LightGrayStainedGlass => f.write_str("minecraft:light_gray_stained_glass"),
+            Blocks::CyanStainedGlass => f.write_str("minecraft:cyan_stained_glass"),
+            Blocks::PurpleStainedGlass => f.write_str("minecraft:purple_stained_glass"),
+            Blocks::BlueStainedGlass => f.write_str("minecraft:blue_stained_glass"),
+            Blocks::BrownStainedGlass => f.write_str("minecraft:brown_stained_glass"),
+            Blocks::GreenStainedGlass => f.write_str("minecraft:green_stained_glass"),
+            Blocks::RedStainedGlass => f.write_str("minecraft:red_stained_glass"),
+            Blocks::BlackStainedGlass => f.write_str("minecraft:black_stained_glass"),
+            Blocks::OakTrapdoor(state) => write!(
+                f,
+                "minecraft:oak_trapdoor[facing={},half={},open={},powered={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.open(),
+                state.powered(),
+                state.waterlogged()
+            ),
+            Blocks::SpruceTrapdoor(state) => write!(
+                f,
+                "minecraft:spruce_trapdoor[facing={},half={},open={},powered={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.open(),
+                state.powered(),
+                state.waterlogged()
+            ),
+            Blocks::BirchTrapdoor(state) => write!(
+                f,
+                "minecraft:birch_trapdoor[facing={},half={},open={},powered={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.open(),
+                state.powered(),
+                state.waterlogged()
+            ),
+            Blocks::JungleTrapdoor(state) => write!(
+                f,
+                "minecraft:jungle_trapdoor[facing={},half={},open={},powered={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.open(),
+                state.powered(),
+                state.waterlogged()
+            ),
+            Blocks::AcaciaTrapdoor(state) => write!(
+                f,
+                "minecraft:acacia_trapdoor[facing={},half={},open={},powered={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.open(),
+                state.powered(),
+                state.waterlogged()
+            ),
+            Blocks::DarkOakTrapdoor(state) => write!(
+                f,
+                "minecraft:dark_oak_trapdoor[facing={},half={},open={},powered={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.open(),
+                state.powered(),
+                state.waterlogged()
+            ),
+            Blocks::StoneBricks => f.write_str("minecraft:stone_bricks"),
+            Blocks::MossyStoneBricks => f.write_str("minecraft:mossy_stone_bricks"),
+            Blocks::CrackedStoneBricks => f.write_str("minecraft:cracked_stone_bricks"),
+            Blocks::ChiseledStoneBricks => f.write_str("minecraft:chiseled_stone_bricks"),
+            Blocks::InfestedStone => f.write_str("minecraft:infested_stone"),
+            Blocks::InfestedCobblestone => f.write_str("minecraft:infested_cobblestone"),
+            Blocks::InfestedStoneBricks => f.write_str("minecraft:infested_stone_bricks"),
+            Blocks::InfestedMossyStoneBricks => f.write_str("minecraft:infested_mossy_stone_bricks"),
+            Blocks::InfestedCrackedStoneBricks => f.write_str("minecraft:infested_cracked_stone_bricks"),
+            Blocks::InfestedChiseledStoneBricks => f.write_str("minecraft:infested_chiseled_stone_bricks"),
+            Blocks::BrownMushroomBlock(state) => write!(
+                f,
+                "minecraft:brown_mushroom_block[down={},east={},north={},south={},up={},west={}]",
+                state.down(),
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.west()
+            ),
+            Blocks::RedMushroomBlock(state) => write!(
+                f,
+                "minecraft:red_mushroom_block[down={},east={},north={},south={},up={},west={}]",
+                state.down(),
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.west()
+            ),
+            Blocks::MushroomStem(state) => write!(
+                f,
+                "minecraft:mushroom_stem[down={},east={},north={},south={},up={},west={}]",
+                state.down(),
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.west()
+            ),
+            Blocks::IronBars(state) => write!(
+                f,
+                "minecraft:iron_bars[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::Chain(state) => write!(f, "minecraft:chain[axis={},waterlogged={}]", state.axis(), state.waterlogged()),
+            Blocks::GlassPane(state) => write!(
+                f,
+                "minecraft:glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::Melon => f.write_str("minecraft:melon"),
+            Blocks::AttachedPumpkinStem(state) => write!(f, "minecraft:attached_pumpkin_stem[facing={}]", state.facing()),
+            Blocks::AttachedMelonStem(state) => write!(f, "minecraft:attached_melon_stem[facing={}]", state.facing()),
+            Blocks::PumpkinStem(state) => write!(f, "minecraft:pumpkin_stem[age={}]", state.age()),
+            Blocks::MelonStem(state) => write!(f, "minecraft:melon_stem[age={}]", state.age()),
+            Blocks::Vine(state) => {
+                write!(f, "minecraft:vine[east={},north={},south={},up={},west={}]", state.east(), state.north(), state.south(), state.up(), state.west())
+            },
+            Blocks::GlowLichen(state) => write!(
+                f,
+                "minecraft:glow_lichen[down={},east={},north={},south={},up={},waterlogged={},west={}]",
+                state.down(),
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::OakFenceGate(state) => {
+                write!(f, "minecraft:oak_fence_gate[facing={},in_wall={},open={},powered={}]", state.facing(), state.in_wall(), state.open(), state.powered())
+            },
+            Blocks::BrickStairs(state) => {
+                write!(f, "minecraft:brick_stairs[facing={},half={},shape={},waterlogged={}]", state.facing(), state.half(), state.shape(), state.waterlogged())
+            },
+            Blocks::StoneBrickStairs(state) => write!(
+                f,
+                "minecraft:stone_brick_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::Mycelium(state) => write!(f, "minecraft:mycelium[snowy={}]", state.snowy()),
+            Blocks::LilyPad => f.write_str("minecraft:lily_pad"),
+            Blocks::NetherBricks => f.write_str("minecraft:nether_bricks"),
+            Blocks::NetherBrickFence(state) => write!(
+                f,
+                "minecraft:nether_brick_fence[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::NetherBrickStairs(state) => write!(
+                f,
+                "minecraft:nether_brick_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::NetherWart(state) => write!(f, "minecraft:nether_wart[age={}]", state.age()),
+            Blocks::EnchantingTable => f.write_str("minecraft:enchanting_table"),
+            Blocks::BrewingStand(state) => write!(
+                f,
+                "minecraft:brewing_stand[has_bottle_0={},has_bottle_1={},has_bottle_2={}]",
+                state.has_bottle_0(),
+                state.has_bottle_1(),
+                state.has_bottle_2()
+            ),
+            Blocks::Cauldron => f.write_str("minecraft:cauldron"),
+            Blocks::WaterCauldron(state) => write!(f, "minecraft:water_cauldron[level={}]", state.level()),
+            Blocks::LavaCauldron => f.write_str("minecraft:lava_cauldron"),
+            Blocks::PowderSnowCauldron(state) => write!(f, "minecraft:powder_snow_cauldron[level={}]", state.level()),
+            Blocks::EndPortal => f.write_str("minecraft:end_portal"),
+            Blocks::EndPortalFrame(state) => write!(f, "minecraft:end_portal_frame[eye={},facing={}]", state.eye(), state.facing()),
+            Blocks::EndStone => f.write_str("minecraft:end_stone"),
+            Blocks::DragonEgg => f.write_str("minecraft:dragon_egg"),
+            Blocks::RedstoneLamp(state) => write!(f, "minecraft:redstone_lamp[lit={}]", state.lit()),
+            Blocks::Cocoa(state) => write!(f, "minecraft:cocoa[age={},facing={}]", state.age(), state.facing()),
+            Blocks::SandstoneStairs(state) => write!(
+                f,
+                "minecraft:sandstone_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::EmeraldOre => f.write_str("minecraft:emerald_ore"),
+            Blocks::DeepslateEmeraldOre => f.write_str("minecraft:deepslate_emerald_ore"),
+            Blocks::EnderChest(state) => write!(f, "minecraft:ender_chest[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::TripwireHook(state) => {
+                write!(f, "minecraft:tripwire_hook[attached={},facing={},powered={}]", state.attached(), state.facing(), state.powered())
+            },
+            Blocks::Tripwire(state) => write!(
+                f,
+                "minecraft:tripwire[attached={},disarmed={},east={},north={},powered={},south={},west={}]",
+                state.attached(),
+                state.disarmed(),
+                state.east(),
+                state.north(),
+                state.powered(),
+                state.south(),
+                state.west()
+            ),
+            Blocks::EmeraldBlock => f.write_str("minecraft:emerald_block"),
+            Blocks::SpruceStairs(state) => write!(
+                f,
+                "minecraft:spruce_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::BirchStairs(state) => {
+                write!(f, "minecraft:birch_stairs[facing={},half={},shape={},waterlogged={}]", state.facing(), state.half(), state.shape(), state.waterlogged())
+            },
+            Blocks::JungleStairs(state) => write!(
+                f,
+                "minecraft:jungle_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::CommandBlock(state) => write!(f, "minecraft:command_block[conditional={},facing={}]", state.conditional(), state.facing()),
+            Blocks::Beacon => f.write_str("minecraft:beacon"),
+            Blocks::CobblestoneWall(state) => write!(
+                f,
+                "minecraft:cobblestone_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::MossyCobblestoneWall(state) => write!(
+                f,
+                "minecraft:mossy_cobblestone_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::FlowerPot => f.write_str("minecraft:flower_pot"),
+            Blocks::PottedOakSapling => f.write_str("minecraft:potted_oak_sapling"),
+            Blocks::PottedSpruceSapling => f.write_str("minecraft:potted_spruce_sapling"),
+            Blocks::PottedBirchSapling => f.write_str("minecraft:potted_birch_sapling"),
+            Blocks::PottedJungleSapling => f.write_str("minecraft:potted_jungle_sapling"),
+            Blocks::PottedAcaciaSapling => f.write_str("minecraft:potted_acacia_sapling"),
+            Blocks::PottedDarkOakSapling => f.write_str("minecraft:potted_dark_oak_sapling"),
+            Blocks::PottedFern => f.write_str("minecraft:potted_fern"),
+            Blocks::PottedDandelion => f.write_str("minecraft:potted_dandelion"),
+            Blocks::PottedPoppy => f.write_str("minecraft:potted_poppy"),
+            Blocks::PottedBlueOrchid => f.write_str("minecraft:potted_blue_orchid"),
+            Blocks::PottedAllium => f.write_str("minecraft:potted_allium"),
+            Blocks::PottedAzureBluet => f.write_str("minecraft:potted_azure_bluet"),
+            Blocks::PottedRedTulip => f.write_str("minecraft:potted_red_tulip"),
+            Blocks::PottedOrangeTulip => f.write_str("minecraft:potted_orange_tulip"),
+            Blocks::PottedWhiteTulip => f.write_str("minecraft:potted_white_tulip"),
+            Blocks::PottedPinkTulip => f.write_str("minecraft:potted_pink_tulip"),
+            Blocks::PottedOxeyeDaisy => f.write_str("minecraft:potted_oxeye_daisy"),
+            Blocks::PottedCornflower => f.write_str("minecraft:potted_cornflower"),
+            Blocks::PottedLilyOfTheValley => f.write_str("minecraft:potted_lily_of_the_valley"),
+            Blocks::PottedWitherRose => f.write_str("minecraft:potted_wither_rose"),
+            Blocks::PottedRedMushroom => f.write_str("minecraft:potted_red_mushroom"),
+            Blocks::PottedBrownMushroom => f.write_str("minecraft:potted_brown_mushroom"),
+            Blocks::PottedDeadBush => f.write_str("minecraft:potted_dead_bush"),
+            Blocks::PottedCactus => f.write_str("minecraft:potted_cactus"),
+            Blocks::Carrots(state) => write!(f, "minecraft:carrots[age={}]", state.age()),
+            Blocks::Potatoes(state) => write!(f, "minecraft:potatoes[age={}]", state.age()),
+            Blocks::OakButton(state) => write!(f, "minecraft:oak_button[face={},facing={},powered={}]", state.face(), state.facing(), state.powered()),
+            Blocks::SpruceButton(state) => write!(f, "minecraft:spruce_button[face={},facing={},powered={}]", state.face(), state.facing(), state.powered()),
+            Blocks::BirchButton(state) => write!(f, "minecraft:birch_button[face={},facing={},powered={}]", state.face(), state.facing(), state.powered()),
+            Blocks::JungleButton(state) => write!(f, "minecraft:jungle_button[face={},facing={},powered={}]", state.face(), state.facing(), state.powered()),
+            Blocks::AcaciaButton(state) => write!(f, "minecraft:acacia_button[face={},facing={},powered={}]", state.face(), state.facing(), state.powered()),
+            Blocks::DarkOakButton(state) => write!(f, "minecraft:dark_oak_button[face={},facing={},powered={}]", state.face(), state.facing(), state.powered()),
+            Blocks::SkeletonSkull(state) => write!(f, "minecraft:skeleton_skull[rotation={}]", state.rotation()),
+            Blocks::SkeletonWallSkull(state) => write!(f, "minecraft:skeleton_wall_skull[facing={}]", state.facing()),
+            Blocks::WitherSkeletonSkull(state) => write!(f, "minecraft:wither_skeleton_skull[rotation={}]", state.rotation()),
+            Blocks::WitherSkeletonWallSkull(state) => write!(f, "minecraft:wither_skeleton_wall_skull[facing={}]", state.facing()),
+            Blocks::ZombieHead(state) => write!(f, "minecraft:zombie_head[rotation={}]", state.rotation()),
+            Blocks::ZombieWallHead(state) => write!(f, "minecraft:zombie_wall_head[facing={}]", state.facing()),
+            Blocks::PlayerHead(state) => write!(f, "minecraft:player_head[rotation={}]", state.rotation()),
+            Blocks::PlayerWallHead(state) => write!(f, "minecraft:player_wall_head[facing={}]", state.facing()),
+            Blocks::CreeperHead(state) => write!(f, "minecraft:creeper_head[rotation={}]", state.rotation()),
+            Blocks::CreeperWallHead(state) => write!(f, "minecraft:creeper_wall_head[facing={}]", state.facing()),
+            Blocks::DragonHead(state) => write!(f, "minecraft:dragon_head[rotation={}]", state.rotation()),
+            Blocks::DragonWallHead(state) => write!(f, "minecraft:dragon_wall_head[facing={}]", state.facing()),
+            Blocks::Anvil(state) => write!(f, "minecraft:anvil[facing={}]", state.facing()),
+            Blocks::ChippedAnvil(state) => write!(f, "minecraft:chipped_anvil[facing={}]", state.facing()),
+            Blocks::DamagedAnvil(state) => write!(f, "minecraft:damaged_anvil[facing={}]", state.facing()),
+            Blocks::TrappedChest(state) => {
+                write!(f, "minecraft:trapped_chest[facing={},type={},waterlogged={}]", state.facing(), state.typed(), state.waterlogged())
+            },
+            Blocks::LightWeightedPressurePlate(state) => write!(f, "minecraft:light_weighted_pressure_plate[power={}]", state.power()),
+            Blocks::HeavyWeightedPressurePlate(state) => write!(f, "minecraft:heavy_weighted_pressure_plate[power={}]", state.power()),
+            Blocks::Comparator(state) => write!(f, "minecraft:comparator[facing={},mode={},powered={}]", state.facing(), state.mode(), state.powered()),
+            Blocks::DaylightDetector(state) => write!(f, "minecraft:daylight_detector[inverted={},power={}]", state.inverted(), state.power()),
+            Blocks::RedstoneBlock => f.write_str("minecraft:redstone_block"),
+            Blocks::NetherQuartzOre => f.write_str("minecraft:nether_quartz_ore"),
+            Blocks::Hopper(state) => write!(f, "minecraft:hopper[enabled={},facing={}]", state.enabled(), state.facing()),
+            Blocks::QuartzBlock => f.write_str("minecraft:quartz_block"),
+            Blocks::ChiseledQuartzBlock => f.write_str("minecraft:chiseled_quartz_block"),
+            Blocks::QuartzPillar(state) => write!(f, "minecraft:quartz_pillar[axis={}]", state.axis()),
+            Blocks::QuartzStairs(state) => write!(
+                f,
+                "minecraft:quartz_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::ActivatorRail(state) => {
+                write!(f, "minecraft:activator_rail[powered={},shape={},waterlogged={}]", state.powered(), state.shape(), state.waterlogged())
+            },
+            Blocks::Dropper(state) => write!(f, "minecraft:dropper[facing={},triggered={}]", state.facing(), state.triggered()),
+            Blocks::WhiteTerracotta => f.write_str("minecraft:white_terracotta"),
+            Blocks::OrangeTerracotta => f.write_str("minecraft:orange_terracotta"),
+            Blocks::MagentaTerracotta => f.write_str("minecraft:magenta_terracotta"),
+            Blocks::LightBlueTerracotta => f.write_str("minecraft:light_blue_terracotta"),
+            Blocks::YellowTerracotta => f.write_str("minecraft:yellow_terracotta"),
+            Blocks::LimeTerracotta => f.write_str("minecraft:lime_terracotta"),
+            Blocks::PinkTerracotta => f.write_str("minecraft:pink_terracotta"),
+            Blocks::GrayTerracotta => f.write_str("minecraft:gray_terracotta"),
+            Blocks::LightGrayTerracotta => f.write_str("minecraft:light_gray_terracotta"),
+            Blocks::CyanTerracotta => f.write_str("minecraft:cyan_terracotta"),
+            Blocks::PurpleTerracotta => f.write_str("minecraft:purple_terracotta"),
+            Blocks::BlueTerracotta => f.write_str("minecraft:blue_terracotta"),
+            Blocks::BrownTerracotta => f.write_str("minecraft:brown_terracotta"),
+            Blocks::GreenTerracotta => f.write_str("minecraft:green_terracotta"),
+            Blocks::RedTerracotta => f.write_str("minecraft:red_terracotta"),
+            Blocks::BlackTerracotta => f.write_str("minecraft:black_terracotta"),
+            Blocks::WhiteStainedGlassPane(state) => write!(
+                f,
+                "minecraft:white_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::OrangeStainedGlassPane(state) => write!(
+                f,
+                "minecraft:orange_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::MagentaStainedGlassPane(state) => write!(
+                f,
+                "minecraft:magenta_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::LightBlueStainedGlassPane(state) => write!(
+                f,
+                "minecraft:light_blue_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::YellowStainedGlassPane(state) => write!(
+                f,
+                "minecraft:yellow_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::LimeStainedGlassPane(state) => write!(
+                f,
+                "minecraft:lime_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::PinkStainedGlassPane(state) => write!(
+                f,
+                "minecraft:pink_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::GrayStainedGlassPane(state) => write!(
+                f,
+                "minecraft:gray_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::LightGrayStainedGlassPane(state) => write!(
+                f,
+                "minecraft:light_gray_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::CyanStainedGlassPane(state) => write!(
+                f,
+                "minecraft:cyan_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::PurpleStainedGlassPane(state) => write!(
+                f,
+                "minecraft:purple_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::BlueStainedGlassPane(state) => write!(
+                f,
+                "minecraft:blue_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::BrownStainedGlassPane(state) => write!(
+                f,
+                "minecraft:brown_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::GreenStainedGlassPane(state) => write!(
+                f,
+                "minecraft:green_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::RedStainedGlassPane(state) => write!(
+                f,
+                "minecraft:red_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::BlackStainedGlassPane(state) => write!(
+                f,
+                "minecraft:black_stained_glass_pane[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::AcaciaStairs(state) => write!(
+                f,
+                "minecraft:acacia_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::DarkOakStairs(state) => write!(
+                f,
+                "minecraft:dark_oak_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::SlimeBlock => f.write_str("minecraft:slime_block"),
+            Blocks::Barrier => f.write_str("minecraft:barrier"),
+            Blocks::Light(state) => write!(f, "minecraft:light[level={},waterlogged={}]", state.level(), state.waterlogged()),
+            Blocks::IronTrapdoor(state) => write!(
+                f,
+                "minecraft:iron_trapdoor[facing={},half={},open={},powered={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.open(),
+                state.powered(),
+                state.waterlogged()
+            ),
+            Blocks::Prismarine => f.write_str("minecraft:prismarine"),
+            Blocks::PrismarineBricks => f.write_str("minecraft:prismarine_bricks"),
+            Blocks::DarkPrismarine => f.write_str("minecraft:dark_prismarine"),
+            Blocks::PrismarineStairs(state) => write!(
+                f,
+                "minecraft:prismarine_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::PrismarineBrickStairs(state) => write!(
+                f,
+                "minecraft:prismarine_brick_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::DarkPrismarineStairs(state) => write!(
+                f,
+                "minecraft:dark_prismarine_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::PrismarineSlab(state) => write!(f, "minecraft:prismarine_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::PrismarineBrickSlab(state) => write!(f, "minecraft:prismarine_brick_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::DarkPrismarineSlab(state) => write!(f, "minecraft:dark_prismarine_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::SeaLantern => f.write_str("minecraft:sea_lantern"),
+            Blocks::HayBlock(state) => write!(f, "minecraft:hay_block[axis={}]", state.axis()),
+            Blocks::WhiteCarpet => f.write_str("minecraft:white_carpet"),
+            Blocks::OrangeCarpet => f.write_str("minecraft:orange_carpet"),
+            Blocks::MagentaCarpet => f.write_str("minecraft:magenta_carpet"),
+            Blocks::LightBlueCarpet => f.write_str("minecraft:light_blue_carpet"),
+            Blocks::YellowCarpet => f.write_str("minecraft:yellow_carpet"),
+            Blocks::LimeCarpet => f.write_str("minecraft:lime_carpet"),
+            Blocks::PinkCarpet => f.write_str("minecraft:pink_carpet"),
+            Blocks::GrayCarpet => f.write_str("minecraft:gray_carpet"),
+            Blocks::LightGrayCarpet => f.write_str("minecraft:light_gray_carpet"),
+            Blocks::CyanCarpet => f.write_str("minecraft:cyan_carpet"),
+            Blocks::PurpleCarpet => f.write_str("minecraft:purple_carpet"),
+            Blocks::BlueCarpet => f.write_str("minecraft:blue_carpet"),
+            Blocks::BrownCarpet => f.write_str("minecraft:brown_carpet"),
+            Blocks::GreenCarpet => f.write_str("minecraft:green_carpet"),
+            Blocks::RedCarpet => f.write_str("minecraft:red_carpet"),
+            Blocks::BlackCarpet => f.write_str("minecraft:black_carpet"),
+            Blocks::Terracotta => f.write_str("minecraft:terracotta"),
+            Blocks::CoalBlock => f.write_str("minecraft:coal_block"),
+            Blocks::PackedIce => f.write_str("minecraft:packed_ice"),
+            Blocks::Sunflower(state) => write!(f, "minecraft:sunflower[half={}]", state.half()),
+            Blocks::Lilac(state) => write!(f, "minecraft:lilac[half={}]", state.half()),
+            Blocks::RoseBush(state) => write!(f, "minecraft:rose_bush[half={}]", state.half()),
+            Blocks::Peony(state) => write!(f, "minecraft:peony[half={}]", state.half()),
+            Blocks::TallGrass(state) => write!(f, "minecraft:tall_grass[half={}]", state.half()),
+            Blocks::LargeFern(state) => write!(f, "minecraft:large_fern[half={}]", state.half()),
+            Blocks::WhiteBanner(state) => write!(f, "minecraft:white_banner[rotation={}]", state.rotation()),
+            Blocks::OrangeBanner(state) => write!(f, "minecraft:orange_banner[rotation={}]", state.rotation()),
+            Blocks::MagentaBanner(state) => write!(f, "minecraft:magenta_banner[rotation={}]", state.rotation()),
+            Blocks::LightBlueBanner(state) => write!(f, "minecraft:light_blue_banner[rotation={}]", state.rotation()),
+            Blocks::YellowBanner(state) => write!(f, "minecraft:yellow_banner[rotation={}]", state.rotation()),
+            Blocks::LimeBanner(state) => write!(f, "minecraft:lime_banner[rotation={}]", state.rotation()),
+            Blocks::PinkBanner(state) => write!(f, "minecraft:pink_banner[rotation={}]", state.rotation()),
+            Blocks::GrayBanner(state) => write!(f, "minecraft:gray_banner[rotation={}]", state.rotation()),
+            Blocks::LightGrayBanner(state) => write!(f, "minecraft:light_gray_banner[rotation={}]", state.rotation()),
+            Blocks::CyanBanner(state) => write!(f, "minecraft:cyan_banner[rotation={}]", state.rotation()),
+            Blocks::PurpleBanner(state) => write!(f, "minecraft:purple_banner[rotation={}]", state.rotation()),
+            Blocks::BlueBanner(state) => write!(f, "minecraft:blue_banner[rotation={}]", state.rotation()),
+            Blocks::BrownBanner(state) => write!(f, "minecraft:brown_banner[rotation={}]", state.rotation()),
+            Blocks::GreenBanner(state) => write!(f, "minecraft:green_banner[rotation={}]", state.rotation()),
+            Blocks::RedBanner(state) => write!(f, "minecraft:red_banner[rotation={}]", state.rotation()),
+            Blocks::BlackBanner(state) => write!(f, "minecraft:black_banner[rotation={}]", state.rotation()),
+            Blocks::WhiteWallBanner(state) => write!(f, "minecraft:white_wall_banner[facing={}]", state.facing()),
+            Blocks::OrangeWallBanner(state) => write!(f, "minecraft:orange_wall_banner[facing={}]", state.facing()),
+            Blocks::MagentaWallBanner(state) => write!(f, "minecraft:magenta_wall_banner[facing={}]", state.facing()),
+            Blocks::LightBlueWallBanner(state) => write!(f, "minecraft:light_blue_wall_banner[facing={}]", state.facing()),
+            Blocks::YellowWallBanner(state) => write!(f, "minecraft:yellow_wall_banner[facing={}]", state.facing()),
+            Blocks::LimeWallBanner(state) => write!(f, "minecraft:lime_wall_banner[facing={}]", state.facing()),
+            Blocks::PinkWallBanner(state) => write!(f, "minecraft:pink_wall_banner[facing={}]", state.facing()),
+            Blocks::GrayWallBanner(state) => write!(f, "minecraft:gray_wall_banner[facing={}]", state.facing()),
+            Blocks::LightGrayWallBanner(state) => write!(f, "minecraft:light_gray_wall_banner[facing={}]", state.facing()),
+            Blocks::CyanWallBanner(state) => write!(f, "minecraft:cyan_wall_banner[facing={}]", state.facing()),
+            Blocks::PurpleWallBanner(state) => write!(f, "minecraft:purple_wall_banner[facing={}]", state.facing()),
+            Blocks::BlueWallBanner(state) => write!(f, "minecraft:blue_wall_banner[facing={}]", state.facing()),
+            Blocks::BrownWallBanner(state) => write!(f, "minecraft:brown_wall_banner[facing={}]", state.facing()),
+            Blocks::GreenWallBanner(state) => write!(f, "minecraft:green_wall_banner[facing={}]", state.facing()),
+            Blocks::RedWallBanner(state) => write!(f, "minecraft:red_wall_banner[facing={}]", state.facing()),
+            Blocks::BlackWallBanner(state) => write!(f, "minecraft:black_wall_banner[facing={}]", state.facing()),
+            Blocks::RedSandstone => f.write_str("minecraft:red_sandstone"),
+            Blocks::ChiseledRedSandstone => f.write_str("minecraft:chiseled_red_sandstone"),
+            Blocks::CutRedSandstone => f.write_str("minecraft:cut_red_sandstone"),
+            Blocks::RedSandstoneStairs(state) => write!(
+                f,
+                "minecraft:red_sandstone_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::OakSlab(state) => write!(f, "minecraft:oak_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::SpruceSlab(state) => write!(f, "minecraft:spruce_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::BirchSlab(state) => write!(f, "minecraft:birch_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::JungleSlab(state) => write!(f, "minecraft:jungle_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::AcaciaSlab(state) => write!(f, "minecraft:acacia_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::DarkOakSlab(state) => write!(f, "minecraft:dark_oak_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::StoneSlab(state) => write!(f, "minecraft:stone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::SmoothStoneSlab(state) => write!(f, "minecraft:smooth_stone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::SandstoneSlab(state) => write!(f, "minecraft:sandstone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::CutSandstoneSlab(state) => write!(f, "minecraft:cut_sandstone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::PetrifiedOakSlab(state) => write!(f, "minecraft:petrified_oak_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::CobblestoneSlab(state) => write!(f, "minecraft:cobblestone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::BrickSlab(state) => write!(f, "minecraft:brick_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::StoneBrickSlab(state) => write!(f, "minecraft:stone_brick_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::NetherBrickSlab(state) => write!(f, "minecraft:nether_brick_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::QuartzSlab(state) => write!(f, "minecraft:quartz_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::RedSandstoneSlab(state) => write!(f, "minecraft:red_sandstone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::CutRedSandstoneSlab(state) => write!(f, "minecraft:cut_red_sandstone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::PurpurSlab(state) => write!(f, "minecraft:purpur_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::SmoothStone => f.write_str("minecraft:smooth_stone"),
+            Blocks::SmoothSandstone => f.write_str("minecraft:smooth_sandstone"),
+            Blocks::SmoothQuartz => f.write_str("minecraft:smooth_quartz"),
+            Blocks::SmoothRedSandstone => f.write_str("minecraft:smooth_red_sandstone"),
+            Blocks::SpruceFenceGate(state) => write!(
+                f,
+                "minecraft:spruce_fence_gate[facing={},in_wall={},open={},powered={}]",
+                state.facing(),
+                state.in_wall(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::BirchFenceGate(state) => {
+                write!(f, "minecraft:birch_fence_gate[facing={},in_wall={},open={},powered={}]", state.facing(), state.in_wall(), state.open(), state.powered())
+            },
+            Blocks::JungleFenceGate(state) => write!(
+                f,
+                "minecraft:jungle_fence_gate[facing={},in_wall={},open={},powered={}]",
+                state.facing(),
+                state.in_wall(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::AcaciaFenceGate(state) => write!(
+                f,
+                "minecraft:acacia_fence_gate[facing={},in_wall={},open={},powered={}]",
+                state.facing(),
+                state.in_wall(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::DarkOakFenceGate(state) => write!(
+                f,
+                "minecraft:dark_oak_fence_gate[facing={},in_wall={},open={},powered={}]",
+                state.facing(),
+                state.in_wall(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::SpruceFence(state) => write!(
+                f,
+                "minecraft:spruce_fence[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::BirchFence(state) => write!(
+                f,
+                "minecraft:birch_fence[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::JungleFence(state) => write!(
+                f,
+                "minecraft:jungle_fence[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::AcaciaFence(state) => write!(
+                f,
+                "minecraft:acacia_fence[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::DarkOakFence(state) => write!(
+                f,
+                "minecraft:dark_oak_fence[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::SpruceDoor(state) => write!(
+                f,
+                "minecraft:spruce_door[facing={},half={},hinge={},open={},powered={}]",
+                state.facing(),
+                state.half(),
+                state.hinge(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::BirchDoor(state) => write!(
+                f,
+                "minecraft:birch_door[facing={},half={},hinge={},open={},powered={}]",
+                state.facing(),
+                state.half(),
+                state.hinge(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::JungleDoor(state) => write!(
+                f,
+                "minecraft:jungle_door[facing={},half={},hinge={},open={},powered={}]",
+                state.facing(),
+                state.half(),
+                state.hinge(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::AcaciaDoor(state) => write!(
+                f,
+                "minecraft:acacia_door[facing={},half={},hinge={},open={},powered={}]",
+                state.facing(),
+                state.half(),
+                state.hinge(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::DarkOakDoor(state) => write!(
+                f,
+                "minecraft:dark_oak_door[facing={},half={},hinge={},open={},powered={}]",
+                state.facing(),
+                state.half(),
+                state.hinge(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::EndRod(state) => write!(f, "minecraft:end_rod[facing={}]", state.facing()),
+            Blocks::ChorusPlant(state) => write!(
+                f,
+                "minecraft:chorus_plant[down={},east={},north={},south={},up={},west={}]",
+                state.down(),
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.west()
+            ),
+            Blocks::ChorusFlower(state) => write!(f, "minecraft:chorus_flower[age={}]", state.age()),
+            Blocks::PurpurBlock => f.write_str("minecraft:purpur_block"),
+            Blocks::PurpurPillar(state) => write!(f, "minecraft:purpur_pillar[axis={}]", state.axis()),
+            Blocks::PurpurStairs(state) => write!(
+                f,
+                "minecraft:purpur_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::EndStoneBricks => f.write_str("minecraft:end_stone_bricks"),
+            Blocks::Beetroots(state) => write!(f, "minecraft:beetroots[age={}]", state.age()),
+            Blocks::DirtPath => f.write_str("minecraft:dirt_path"),
+            Blocks::EndGateway => f.write_str("minecraft:end_gateway"),
+            Blocks::RepeatingCommandBlock(state) => {
+                write!(f, "minecraft:repeating_command_block[conditional={},facing={}]", state.conditional(), state.facing())
+            },
+            Blocks::ChainCommandBlock(state) => write!(f, "minecraft:chain_command_block[conditional={},facing={}]", state.conditional(), state.facing()),
+            Blocks::FrostedIce(state) => write!(f, "minecraft:frosted_ice[age={}]", state.age()),
+            Blocks::MagmaBlock => f.write_str("minecraft:magma_block"),
+            Blocks::NetherWartBlock => f.write_str("minecraft:nether_wart_block"),
+            Blocks::RedNetherBricks => f.write_str("minecraft:red_nether_bricks"),
+            Blocks::BoneBlock(state) => write!(f, "minecraft:bone_block[axis={}]", state.axis()),
+            Blocks::StructureVoid => f.write_str("minecraft:structure_void"),
+            Blocks::Observer(state) => write!(f, "minecraft:observer[facing={},powered={}]", state.facing(), state.powered()),
+            Blocks::ShulkerBox(state) => write!(f, "minecraft:shulker_box[facing={}]", state.facing()),
+            Blocks::WhiteShulkerBox(state) => write!(f, "minecraft:white_shulker_box[facing={}]", state.facing()),
+            Blocks::OrangeShulkerBox(state) => write!(f, "minecraft:orange_shulker_box[facing={}]", state.facing()),
+            Blocks::MagentaShulkerBox(state) => write!(f, "minecraft:magenta_shulker_box[facing={}]", state.facing()),
+            Blocks::LightBlueShulkerBox(state) => write!(f, "minecraft:light_blue_shulker_box[facing={}]", state.facing()),
+            Blocks::YellowShulkerBox(state) => write!(f, "minecraft:yellow_shulker_box[facing={}]", state.facing()),
+            Blocks::LimeShulkerBox(state) => write!(f, "minecraft:lime_shulker_box[facing={}]", state.facing()),
+            Blocks::PinkShulkerBox(state) => write!(f, "minecraft:pink_shulker_box[facing={}]", state.facing()),
+            Blocks::GrayShulkerBox(state) => write!(f, "minecraft:gray_shulker_box[facing={}]", state.facing()),
+            Blocks::LightGrayShulkerBox(state) => write!(f, "minecraft:light_gray_shulker_box[facing={}]", state.facing()),
+            Blocks::CyanShulkerBox(state) => write!(f, "minecraft:cyan_shulker_box[facing={}]", state.facing()),
+            Blocks::PurpleShulkerBox(state) => write!(f, "minecraft:purple_shulker_box[facing={}]", state.facing()),
+            Blocks::BlueShulkerBox(state) => write!(f, "minecraft:blue_shulker_box[facing={}]", state.facing()),
+            Blocks::BrownShulkerBox(state) => write!(f, "minecraft:brown_shulker_box[facing={}]", state.facing()),
+            Blocks::GreenShulkerBox(state) => write!(f, "minecraft:green_shulker_box[facing={}]", state.facing()),
+            Blocks::RedShulkerBox(state) => write!(f, "minecraft:red_shulker_box[facing={}]", state.facing()),
+            Blocks::BlackShulkerBox(state) => write!(f, "minecraft:black_shulker_box[facing={}]", state.facing()),
+            Blocks::WhiteGlazedTerracotta(state) => write!(f, "minecraft:white_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::OrangeGlazedTerracotta(state) => write!(f, "minecraft:orange_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::MagentaGlazedTerracotta(state) => write!(f, "minecraft:magenta_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::LightBlueGlazedTerracotta(state) => write!(f, "minecraft:light_blue_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::YellowGlazedTerracotta(state) => write!(f, "minecraft:yellow_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::LimeGlazedTerracotta(state) => write!(f, "minecraft:lime_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::PinkGlazedTerracotta(state) => write!(f, "minecraft:pink_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::GrayGlazedTerracotta(state) => write!(f, "minecraft:gray_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::LightGrayGlazedTerracotta(state) => write!(f, "minecraft:light_gray_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::CyanGlazedTerracotta(state) => write!(f, "minecraft:cyan_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::PurpleGlazedTerracotta(state) => write!(f, "minecraft:purple_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::BlueGlazedTerracotta(state) => write!(f, "minecraft:blue_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::BrownGlazedTerracotta(state) => write!(f, "minecraft:brown_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::GreenGlazedTerracotta(state) => write!(f, "minecraft:green_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::RedGlazedTerracotta(state) => write!(f, "minecraft:red_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::BlackGlazedTerracotta(state) => write!(f, "minecraft:black_glazed_terracotta[facing={}]", state.facing()),
+            Blocks::WhiteConcrete => f.write_str("minecraft:white_concrete"),
+            Blocks::OrangeConcrete => f.write_str("minecraft:orange_concrete"),
+            Blocks::MagentaConcrete => f.write_str("minecraft:magenta_concrete"),
+            Blocks::LightBlueConcrete => f.write_str("minecraft:light_blue_concrete"),
+            Blocks::YellowConcrete => f.write_str("minecraft:yellow_concrete"),
+            Blocks::LimeConcrete => f.write_str("minecraft:lime_concrete"),
+            Blocks::PinkConcrete => f.write_str("minecraft:pink_concrete"),
+            Blocks::GrayConcrete => f.write_str("minecraft:gray_concrete"),
+            Blocks::LightGrayConcrete => f.write_str("minecraft:light_gray_concrete"),
+            Blocks::CyanConcrete => f.write_str("minecraft:cyan_concrete"),
+            Blocks::PurpleConcrete => f.write_str("minecraft:purple_concrete"),
+            Blocks::BlueConcrete => f.write_str("minecraft:blue_concrete"),
+            Blocks::BrownConcrete => f.write_str("minecraft:brown_concrete"),
+            Blocks::GreenConcrete => f.write_str("minecraft:green_concrete"),
+            Blocks::RedConcrete => f.write_str("minecraft:red_concrete"),
+            Blocks::BlackConcrete => f.write_str("minecraft:black_concrete"),
+            Blocks::WhiteConcretePowder => f.write_str("minecraft:white_concrete_powder"),
+            Blocks::OrangeConcretePowder => f.write_str("minecraft:orange_concrete_powder"),
+            Blocks::MagentaConcretePowder => f.write_str("minecraft:magenta_concrete_powder"),
+            Blocks::LightBlueConcretePowder => f.write_str("minecraft:light_blue_concrete_powder"),
+            Blocks::YellowConcretePowder => f.write_str("minecraft:yellow_concrete_powder"),
+            Blocks::LimeConcretePowder => f.write_str("minecraft:lime_concrete_powder"),
+            Blocks::PinkConcretePowder => f.write_str("minecraft:pink_concrete_powder"),
+            Blocks::GrayConcretePowder => f.write_str("minecraft:gray_concrete_powder"),
+            Blocks::LightGrayConcretePowder => f.write_str("minecraft:light_gray_concrete_powder"),
+            Blocks::CyanConcretePowder => f.write_str("minecraft:cyan_concrete_powder"),
+            Blocks::PurpleConcretePowder => f.write_str("minecraft:purple_concrete_powder"),
+            Blocks::BlueConcretePowder => f.write_str("minecraft:blue_concrete_powder"),
+            Blocks::BrownConcretePowder => f.write_str("minecraft:brown_concrete_powder"),
+            Blocks::GreenConcretePowder => f.write_str("minecraft:green_concrete_powder"),
+            Blocks::RedConcretePowder => f.write_str("minecraft:red_concrete_powder"),
+            Blocks::BlackConcretePowder => f.write_str("minecraft:black_concrete_powder"),
+            Blocks::Kelp(state) => write!(f, "minecraft:kelp[age={}]", state.age()),
+            Blocks::KelpPlant => f.write_str("minecraft:kelp_plant"),
+            Blocks::DriedKelpBlock => f.write_str("minecraft:dried_kelp_block"),
+            Blocks::TurtleEgg(state) => write!(f, "minecraft:turtle_egg[eggs={},hatch={}]", state.eggs(), state.hatch()),
+            Blocks::DeadTubeCoralBlock => f.write_str("minecraft:dead_tube_coral_block"),
+            Blocks::DeadBrainCoralBlock => f.write_str("minecraft:dead_brain_coral_block"),
+            Blocks::DeadBubbleCoralBlock => f.write_str("minecraft:dead_bubble_coral_block"),
+            Blocks::DeadFireCoralBlock => f.write_str("minecraft:dead_fire_coral_block"),
+            Blocks::DeadHornCoralBlock => f.write_str("minecraft:dead_horn_coral_block"),
+            Blocks::TubeCoralBlock => f.write_str("minecraft:tube_coral_block"),
+            Blocks::BrainCoralBlock => f.write_str("minecraft:brain_coral_block"),
+            Blocks::BubbleCoralBlock => f.write_str("minecraft:bubble_coral_block"),
+            Blocks::FireCoralBlock => f.write_str("minecraft:fire_coral_block"),
+            Blocks::HornCoralBlock => f.write_str("minecraft:horn_coral_block"),
+            Blocks::DeadTubeCoral(state) => write!(f, "minecraft:dead_tube_coral[waterlogged={}]", state.waterlogged()),
+            Blocks::DeadBrainCoral(state) => write!(f, "minecraft:dead_brain_coral[waterlogged={}]", state.waterlogged()),
+            Blocks::DeadBubbleCoral(state) => write!(f, "minecraft:dead_bubble_coral[waterlogged={}]", state.waterlogged()),
+            Blocks::DeadFireCoral(state) => write!(f, "minecraft:dead_fire_coral[waterlogged={}]", state.waterlogged()),
+            Blocks::DeadHornCoral(state) => write!(f, "minecraft:dead_horn_coral[waterlogged={}]", state.waterlogged()),
+            Blocks::TubeCoral(state) => write!(f, "minecraft:tube_coral[waterlogged={}]", state.waterlogged()),
+            Blocks::BrainCoral(state) => write!(f, "minecraft:brain_coral[waterlogged={}]", state.waterlogged()),
+            Blocks::BubbleCoral(state) => write!(f, "minecraft:bubble_coral[waterlogged={}]", state.waterlogged()),
+            Blocks::FireCoral(state) => write!(f, "minecraft:fire_coral[waterlogged={}]", state.waterlogged()),
+            Blocks::HornCoral(state) => write!(f, "minecraft:horn_coral[waterlogged={}]", state.waterlogged()),
+            Blocks::DeadTubeCoralFan(state) => write!(f, "minecraft:dead_tube_coral_fan[waterlogged={}]", state.waterlogged()),
+            Blocks::DeadBrainCoralFan(state) => write!(f, "minecraft:dead_brain_coral_fan[waterlogged={}]", state.waterlogged()),
+            Blocks::DeadBubbleCoralFan(state) => write!(f, "minecraft:dead_bubble_coral_fan[waterlogged={}]", state.waterlogged()),
+            Blocks::DeadFireCoralFan(state) => write!(f, "minecraft:dead_fire_coral_fan[waterlogged={}]", state.waterlogged()),
+            Blocks::DeadHornCoralFan(state) => write!(f, "minecraft:dead_horn_coral_fan[waterlogged={}]", state.waterlogged()),
+            Blocks::TubeCoralFan(state) => write!(f, "minecraft:tube_coral_fan[waterlogged={}]", state.waterlogged()),
+            Blocks::BrainCoralFan(state) => write!(f, "minecraft:brain_coral_fan[waterlogged={}]", state.waterlogged()),
+            Blocks::BubbleCoralFan(state) => write!(f, "minecraft:bubble_coral_fan[waterlogged={}]", state.waterlogged()),
+            Blocks::FireCoralFan(state) => write!(f, "minecraft:fire_coral_fan[waterlogged={}]", state.waterlogged()),
+            Blocks::HornCoralFan(state) => write!(f, "minecraft:horn_coral_fan[waterlogged={}]", state.waterlogged()),
+            Blocks::DeadTubeCoralWallFan(state) => {
+                write!(f, "minecraft:dead_tube_coral_wall_fan[facing={},waterlogged={}]", state.facing(), state.waterlogged())
+            },
+            Blocks::DeadBrainCoralWallFan(state) => {
+                write!(f, "minecraft:dead_brain_coral_wall_fan[facing={},waterlogged={}]", state.facing(), state.waterlogged())
+            },
+            Blocks::DeadBubbleCoralWallFan(state) => {
+                write!(f, "minecraft:dead_bubble_coral_wall_fan[facing={},waterlogged={}]", state.facing(), state.waterlogged())
+            },
+            Blocks::DeadFireCoralWallFan(state) => {
+                write!(f, "minecraft:dead_fire_coral_wall_fan[facing={},waterlogged={}]", state.facing(), state.waterlogged())
+            },
+            Blocks::DeadHornCoralWallFan(state) => {
+                write!(f, "minecraft:dead_horn_coral_wall_fan[facing={},waterlogged={}]", state.facing(), state.waterlogged())
+            },
+            Blocks::TubeCoralWallFan(state) => write!(f, "minecraft:tube_coral_wall_fan[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::BrainCoralWallFan(state) => write!(f, "minecraft:brain_coral_wall_fan[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::BubbleCoralWallFan(state) => write!(f, "minecraft:bubble_coral_wall_fan[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::FireCoralWallFan(state) => write!(f, "minecraft:fire_coral_wall_fan[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::HornCoralWallFan(state) => write!(f, "minecraft:horn_coral_wall_fan[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::SeaPickle(state) => write!(f, "minecraft:sea_pickle[pickles={},waterlogged={}]", state.pickles(), state.waterlogged()),
+            Blocks::BlueIce => f.write_str("minecraft:blue_ice"),
+            Blocks::Conduit(state) => write!(f, "minecraft:conduit[waterlogged={}]", state.waterlogged()),
+            Blocks::BambooSapling => f.write_str("minecraft:bamboo_sapling"),
+            Blocks::Bamboo(state) => write!(f, "minecraft:bamboo[age={},leaves={},stage={}]", state.age(), state.leaves(), state.stage()),
+            Blocks::PottedBamboo => f.write_str("minecraft:potted_bamboo"),
+            Blocks::VoidAir => f.write_str("minecraft:void_air"),
+            Blocks::CaveAir => f.write_str("minecraft:cave_air"),
+            Blocks::BubbleColumn(state) => write!(f, "minecraft:bubble_column[drag={}]", state.drag()),
+            Blocks::PolishedGraniteStairs(state) => write!(
+                f,
+                "minecraft:polished_granite_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::SmoothRedSandstoneStairs(state) => write!(
+                f,
+                "minecraft:smooth_red_sandstone_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::MossyStoneBrickStairs(state) => write!(
+                f,
+                "minecraft:mossy_stone_brick_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::PolishedDioriteStairs(state) => write!(
+                f,
+                "minecraft:polished_diorite_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::MossyCobblestoneStairs(state) => write!(
+                f,
+                "minecraft:mossy_cobblestone_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::EndStoneBrickStairs(state) => write!(
+                f,
+                "minecraft:end_stone_brick_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::StoneStairs(state) => {
+                write!(f, "minecraft:stone_stairs[facing={},half={},shape={},waterlogged={}]", state.facing(), state.half(), state.shape(), state.waterlogged())
+            },
+            Blocks::SmoothSandstoneStairs(state) => write!(
+                f,
+                "minecraft:smooth_sandstone_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::SmoothQuartzStairs(state) => write!(
+                f,
+                "minecraft:smooth_quartz_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::GraniteStairs(state) => write!(
+                f,
+                "minecraft:granite_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::AndesiteStairs(state) => write!(
+                f,
+                "minecraft:andesite_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::RedNetherBrickStairs(state) => write!(
+                f,
+                "minecraft:red_nether_brick_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::PolishedAndesiteStairs(state) => write!(
+                f,
+                "minecraft:polished_andesite_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::DioriteStairs(state) => write!(
+                f,
+                "minecraft:diorite_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::PolishedGraniteSlab(state) => write!(f, "minecraft:polished_granite_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::SmoothRedSandstoneSlab(state) => {
+                write!(f, "minecraft:smooth_red_sandstone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged())
+            },
+            Blocks::MossyStoneBrickSlab(state) => write!(f, "minecraft:mossy_stone_brick_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::PolishedDioriteSlab(state) => write!(f, "minecraft:polished_diorite_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::MossyCobblestoneSlab(state) => write!(f, "minecraft:mossy_cobblestone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::EndStoneBrickSlab(state) => write!(f, "minecraft:end_stone_brick_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::SmoothSandstoneSlab(state) => write!(f, "minecraft:smooth_sandstone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::SmoothQuartzSlab(state) => write!(f, "minecraft:smooth_quartz_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::GraniteSlab(state) => write!(f, "minecraft:granite_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::AndesiteSlab(state) => write!(f, "minecraft:andesite_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::RedNetherBrickSlab(state) => write!(f, "minecraft:red_nether_brick_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::PolishedAndesiteSlab(state) => write!(f, "minecraft:polished_andesite_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::DioriteSlab(state) => write!(f, "minecraft:diorite_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::BrickWall(state) => write!(
+                f,
+                "minecraft:brick_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::PrismarineWall(state) => write!(
+                f,
+                "minecraft:prismarine_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::RedSandstoneWall(state) => write!(
+                f,
+                "minecraft:red_sandstone_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::MossyStoneBrickWall(state) => write!(
+                f,
+                "minecraft:mossy_stone_brick_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::GraniteWall(state) => write!(
+                f,
+                "minecraft:granite_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::StoneBrickWall(state) => write!(
+                f,
+                "minecraft:stone_brick_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::NetherBrickWall(state) => write!(
+                f,
+                "minecraft:nether_brick_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::AndesiteWall(state) => write!(
+                f,
+                "minecraft:andesite_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::RedNetherBrickWall(state) => write!(
+                f,
+                "minecraft:red_nether_brick_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::SandstoneWall(state) => write!(
+                f,
+                "minecraft:sandstone_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::EndStoneBrickWall(state) => write!(
+                f,
+                "minecraft:end_stone_brick_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::DioriteWall(state) => write!(
+                f,
+                "minecraft:diorite_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::Scaffolding(state) => {
+                write!(f, "minecraft:scaffolding[bottom={},distance={},waterlogged={}]", state.bottom(), state.distance(), state.waterlogged())
+            },
+            Blocks::Loom(state) => write!(f, "minecraft:loom[facing={}]", state.facing()),
+            Blocks::Barrel(state) => write!(f, "minecraft:barrel[facing={},open={}]", state.facing(), state.open()),
+            Blocks::Smoker(state) => write!(f, "minecraft:smoker[facing={},lit={}]", state.facing(), state.lit()),
+            Blocks::BlastFurnace(state) => write!(f, "minecraft:blast_furnace[facing={},lit={}]", state.facing(), state.lit()),
+            Blocks::CartographyTable => f.write_str("minecraft:cartography_table"),
+            Blocks::FletchingTable => f.write_str("minecraft:fletching_table"),
+            Blocks::Grindstone(state) => write!(f, "minecraft:grindstone[face={},facing={}]", state.face(), state.facing()),
+            Blocks::Lectern(state) => write!(f, "minecraft:lectern[facing={},has_book={},powered={}]", state.facing(), state.has_book(), state.powered()),
+            Blocks::SmithingTable => f.write_str("minecraft:smithing_table"),
+            Blocks::Stonecutter(state) => write!(f, "minecraft:stonecutter[facing={}]", state.facing()),
+            Blocks::Bell(state) => write!(f, "minecraft:bell[attachment={},facing={},powered={}]", state.attachment(), state.facing(), state.powered()),
+            Blocks::Lantern(state) => write!(f, "minecraft:lantern[hanging={},waterlogged={}]", state.hanging(), state.waterlogged()),
+            Blocks::SoulLantern(state) => write!(f, "minecraft:soul_lantern[hanging={},waterlogged={}]", state.hanging(), state.waterlogged()),
+            Blocks::Campfire(state) => write!(
+                f,
+                "minecraft:campfire[facing={},lit={},signal_fire={},waterlogged={}]",
+                state.facing(),
+                state.lit(),
+                state.signal_fire(),
+                state.waterlogged()
+            ),
+            Blocks::SoulCampfire(state) => write!(
+                f,
+                "minecraft:soul_campfire[facing={},lit={},signal_fire={},waterlogged={}]",
+                state.facing(),
+                state.lit(),
+                state.signal_fire(),
+                state.waterlogged()
+            ),
+            Blocks::SweetBerryBush(state) => write!(f, "minecraft:sweet_berry_bush[age={}]", state.age()),
+            Blocks::WarpedStem(state) => write!(f, "minecraft:warped_stem[axis={}]", state.axis()),
+            Blocks::StrippedWarpedStem(state) => write!(f, "minecraft:stripped_warped_stem[axis={}]", state.axis()),
+            Blocks::WarpedHyphae(state) => write!(f, "minecraft:warped_hyphae[axis={}]", state.axis()),
+            Blocks::StrippedWarpedHyphae(state) => write!(f, "minecraft:stripped_warped_hyphae[axis={}]", state.axis()),
+            Blocks::WarpedNylium => f.write_str("minecraft:warped_nylium"),
+            Blocks::WarpedFungus => f.write_str("minecraft:warped_fungus"),
+            Blocks::WarpedWartBlock => f.write_str("minecraft:warped_wart_block"),
+            Blocks::WarpedRoots => f.write_str("minecraft:warped_roots"),
+            Blocks::NetherSprouts => f.write_str("minecraft:nether_sprouts"),
+            Blocks::CrimsonStem(state) => write!(f, "minecraft:crimson_stem[axis={}]", state.axis()),
+            Blocks::StrippedCrimsonStem(state) => write!(f, "minecraft:stripped_crimson_stem[axis={}]", state.axis()),
+            Blocks::CrimsonHyphae(state) => write!(f, "minecraft:crimson_hyphae[axis={}]", state.axis()),
+            Blocks::StrippedCrimsonHyphae(state) => write!(f, "minecraft:stripped_crimson_hyphae[axis={}]", state.axis()),
+            Blocks::CrimsonNylium => f.write_str("minecraft:crimson_nylium"),
+            Blocks::CrimsonFungus => f.write_str("minecraft:crimson_fungus"),
+            Blocks::Shroomlight => f.write_str("minecraft:shroomlight"),
+            Blocks::WeepingVines(state) => write!(f, "minecraft:weeping_vines[age={}]", state.age()),
+            Blocks::WeepingVinesPlant => f.write_str("minecraft:weeping_vines_plant"),
+            Blocks::TwistingVines(state) => write!(f, "minecraft:twisting_vines[age={}]", state.age()),
+            Blocks::TwistingVinesPlant => f.write_str("minecraft:twisting_vines_plant"),
+            Blocks::CrimsonRoots => f.write_str("minecraft:crimson_roots"),
+            Blocks::CrimsonPlanks => f.write_str("minecraft:crimson_planks"),
+            Blocks::WarpedPlanks => f.write_str("minecraft:warped_planks"),
+            Blocks::CrimsonSlab(state) => write!(f, "minecraft:crimson_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::WarpedSlab(state) => write!(f, "minecraft:warped_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::CrimsonPressurePlate(state) => write!(f, "minecraft:crimson_pressure_plate[powered={}]", state.powered()),
+            Blocks::WarpedPressurePlate(state) => write!(f, "minecraft:warped_pressure_plate[powered={}]", state.powered()),
+            Blocks::CrimsonFence(state) => write!(
+                f,
+                "minecraft:crimson_fence[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::WarpedFence(state) => write!(
+                f,
+                "minecraft:warped_fence[east={},north={},south={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::CrimsonTrapdoor(state) => write!(
+                f,
+                "minecraft:crimson_trapdoor[facing={},half={},open={},powered={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.open(),
+                state.powered(),
+                state.waterlogged()
+            ),
+            Blocks::WarpedTrapdoor(state) => write!(
+                f,
+                "minecraft:warped_trapdoor[facing={},half={},open={},powered={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.open(),
+                state.powered(),
+                state.waterlogged()
+            ),
+            Blocks::CrimsonFenceGate(state) => write!(
+                f,
+                "minecraft:crimson_fence_gate[facing={},in_wall={},open={},powered={}]",
+                state.facing(),
+                state.in_wall(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::WarpedFenceGate(state) => write!(
+                f,
+                "minecraft:warped_fence_gate[facing={},in_wall={},open={},powered={}]",
+                state.facing(),
+                state.in_wall(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::CrimsonStairs(state) => write!(
+                f,
+                "minecraft:crimson_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::WarpedStairs(state) => write!(
+                f,
+                "minecraft:warped_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::CrimsonButton(state) => write!(f, "minecraft:crimson_button[face={},facing={},powered={}]", state.face(), state.facing(), state.powered()),
+            Blocks::WarpedButton(state) => write!(f, "minecraft:warped_button[face={},facing={},powered={}]", state.face(), state.facing(), state.powered()),
+            Blocks::CrimsonDoor(state) => write!(
+                f,
+                "minecraft:crimson_door[facing={},half={},hinge={},open={},powered={}]",
+                state.facing(),
+                state.half(),
+                state.hinge(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::WarpedDoor(state) => write!(
+                f,
+                "minecraft:warped_door[facing={},half={},hinge={},open={},powered={}]",
+                state.facing(),
+                state.half(),
+                state.hinge(),
+                state.open(),
+                state.powered()
+            ),
+            Blocks::CrimsonSign(state) => write!(f, "minecraft:crimson_sign[rotation={},waterlogged={}]", state.rotation(), state.waterlogged()),
+            Blocks::WarpedSign(state) => write!(f, "minecraft:warped_sign[rotation={},waterlogged={}]", state.rotation(), state.waterlogged()),
+            Blocks::CrimsonWallSign(state) => write!(f, "minecraft:crimson_wall_sign[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::WarpedWallSign(state) => write!(f, "minecraft:warped_wall_sign[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::StructureBlock(state) => write!(f, "minecraft:structure_block[mode={}]", state.mode()),
+            Blocks::Jigsaw(state) => write!(f, "minecraft:jigsaw[orientation={}]", state.orientation()),
+            Blocks::Composter(state) => write!(f, "minecraft:composter[level={}]", state.level()),
+            Blocks::Target(state) => write!(f, "minecraft:target[power={}]", state.power()),
+            Blocks::BeeNest(state) => write!(f, "minecraft:bee_nest[facing={},honey_level={}]", state.facing(), state.honey_level()),
+            Blocks::Beehive(state) => write!(f, "minecraft:beehive[facing={},honey_level={}]", state.facing(), state.honey_level()),
+            Blocks::HoneyBlock => f.write_str("minecraft:honey_block"),
+            Blocks::HoneycombBlock => f.write_str("minecraft:honeycomb_block"),
+            Blocks::NetheriteBlock => f.write_str("minecraft:netherite_block"),
+            Blocks::AncientDebris => f.write_str("minecraft:ancient_debris"),
+            Blocks::CryingObsidian => f.write_str("minecraft:crying_obsidian"),
+            Blocks::RespawnAnchor(state) => write!(f, "minecraft:respawn_anchor[charges={}]", state.charges()),
+            Blocks::PottedCrimsonFungus => f.write_str("minecraft:potted_crimson_fungus"),
+            Blocks::PottedWarpedFungus => f.write_str("minecraft:potted_warped_fungus"),
+            Blocks::PottedCrimsonRoots => f.write_str("minecraft:potted_crimson_roots"),
+            Blocks::PottedWarpedRoots => f.write_str("minecraft:potted_warped_roots"),
+            Blocks::Lodestone => f.write_str("minecraft:lodestone"),
+            Blocks::Blackstone => f.write_str("minecraft:blackstone"),
+            Blocks::BlackstoneStairs(state) => write!(
+                f,
+                "minecraft:blackstone_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::BlackstoneWall(state) => write!(
+                f,
+                "minecraft:blackstone_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::BlackstoneSlab(state) => write!(f, "minecraft:blackstone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::PolishedBlackstone => f.write_str("minecraft:polished_blackstone"),
+            Blocks::PolishedBlackstoneBricks => f.write_str("minecraft:polished_blackstone_bricks"),
+            Blocks::CrackedPolishedBlackstoneBricks => f.write_str("minecraft:cracked_polished_blackstone_bricks"),
+            Blocks::ChiseledPolishedBlackstone => f.write_str("minecraft:chiseled_polished_blackstone"),
+            Blocks::PolishedBlackstoneBrickSlab(state) => {
+                write!(f, "minecraft:polished_blackstone_brick_slab[type={},waterlogged={}]", state.typed(), state.waterlogged())
+            },
+            Blocks::PolishedBlackstoneBrickStairs(state) => write!(
+                f,
+                "minecraft:polished_blackstone_brick_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::PolishedBlackstoneBrickWall(state) => write!(
+                f,
+                "minecraft:polished_blackstone_brick_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::GildedBlackstone => f.write_str("minecraft:gilded_blackstone"),
+            Blocks::PolishedBlackstoneStairs(state) => write!(
+                f,
+                "minecraft:polished_blackstone_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::PolishedBlackstoneSlab(state) => {
+                write!(f, "minecraft:polished_blackstone_slab[type={},waterlogged={}]", state.typed(), state.waterlogged())
+            },
+            Blocks::PolishedBlackstonePressurePlate(state) => write!(f, "minecraft:polished_blackstone_pressure_plate[powered={}]", state.powered()),
+            Blocks::PolishedBlackstoneButton(state) => {
+                write!(f, "minecraft:polished_blackstone_button[face={},facing={},powered={}]", state.face(), state.facing(), state.powered())
+            },
+            Blocks::PolishedBlackstoneWall(state) => write!(
+                f,
+                "minecraft:polished_blackstone_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::ChiseledNetherBricks => f.write_str("minecraft:chiseled_nether_bricks"),
+            Blocks::CrackedNetherBricks => f.write_str("minecraft:cracked_nether_bricks"),
+            Blocks::QuartzBricks => f.write_str("minecraft:quartz_bricks"),
+            Blocks::Candle(state) => write!(f, "minecraft:candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged()),
+            Blocks::WhiteCandle(state) => {
+                write!(f, "minecraft:white_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::OrangeCandle(state) => {
+                write!(f, "minecraft:orange_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::MagentaCandle(state) => {
+                write!(f, "minecraft:magenta_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::LightBlueCandle(state) => {
+                write!(f, "minecraft:light_blue_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::YellowCandle(state) => {
+                write!(f, "minecraft:yellow_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::LimeCandle(state) => {
+                write!(f, "minecraft:lime_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::PinkCandle(state) => {
+                write!(f, "minecraft:pink_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::GrayCandle(state) => {
+                write!(f, "minecraft:gray_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::LightGrayCandle(state) => {
+                write!(f, "minecraft:light_gray_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::CyanCandle(state) => {
+                write!(f, "minecraft:cyan_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::PurpleCandle(state) => {
+                write!(f, "minecraft:purple_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::BlueCandle(state) => {
+                write!(f, "minecraft:blue_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::BrownCandle(state) => {
+                write!(f, "minecraft:brown_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::GreenCandle(state) => {
+                write!(f, "minecraft:green_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::RedCandle(state) => write!(f, "minecraft:red_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged()),
+            Blocks::BlackCandle(state) => {
+                write!(f, "minecraft:black_candle[candles={},lit={},waterlogged={}]", state.candles(), state.lit(), state.waterlogged())
+            },
+            Blocks::CandleCake(state) => write!(f, "minecraft:candle_cake[lit={}]", state.lit()),
+            Blocks::WhiteCandleCake(state) => write!(f, "minecraft:white_candle_cake[lit={}]", state.lit()),
+            Blocks::OrangeCandleCake(state) => write!(f, "minecraft:orange_candle_cake[lit={}]", state.lit()),
+            Blocks::MagentaCandleCake(state) => write!(f, "minecraft:magenta_candle_cake[lit={}]", state.lit()),
+            Blocks::LightBlueCandleCake(state) => write!(f, "minecraft:light_blue_candle_cake[lit={}]", state.lit()),
+            Blocks::YellowCandleCake(state) => write!(f, "minecraft:yellow_candle_cake[lit={}]", state.lit()),
+            Blocks::LimeCandleCake(state) => write!(f, "minecraft:lime_candle_cake[lit={}]", state.lit()),
+            Blocks::PinkCandleCake(state) => write!(f, "minecraft:pink_candle_cake[lit={}]", state.lit()),
+            Blocks::GrayCandleCake(state) => write!(f, "minecraft:gray_candle_cake[lit={}]", state.lit()),
+            Blocks::LightGrayCandleCake(state) => write!(f, "minecraft:light_gray_candle_cake[lit={}]", state.lit()),
+            Blocks::CyanCandleCake(state) => write!(f, "minecraft:cyan_candle_cake[lit={}]", state.lit()),
+            Blocks::PurpleCandleCake(state) => write!(f, "minecraft:purple_candle_cake[lit={}]", state.lit()),
+            Blocks::BlueCandleCake(state) => write!(f, "minecraft:blue_candle_cake[lit={}]", state.lit()),
+            Blocks::BrownCandleCake(state) => write!(f, "minecraft:brown_candle_cake[lit={}]", state.lit()),
+            Blocks::GreenCandleCake(state) => write!(f, "minecraft:green_candle_cake[lit={}]", state.lit()),
+            Blocks::RedCandleCake(state) => write!(f, "minecraft:red_candle_cake[lit={}]", state.lit()),
+            Blocks::BlackCandleCake(state) => write!(f, "minecraft:black_candle_cake[lit={}]", state.lit()),
+            Blocks::AmethystBlock => f.write_str("minecraft:amethyst_block"),
+            Blocks::BuddingAmethyst => f.write_str("minecraft:budding_amethyst"),
+            Blocks::AmethystCluster(state) => write!(f, "minecraft:amethyst_cluster[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::LargeAmethystBud(state) => write!(f, "minecraft:large_amethyst_bud[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::MediumAmethystBud(state) => write!(f, "minecraft:medium_amethyst_bud[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::SmallAmethystBud(state) => write!(f, "minecraft:small_amethyst_bud[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::Tuff => f.write_str("minecraft:tuff"),
+            Blocks::Calcite => f.write_str("minecraft:calcite"),
+            Blocks::TintedGlass => f.write_str("minecraft:tinted_glass"),
+            Blocks::PowderSnow => f.write_str("minecraft:powder_snow"),
+            Blocks::SculkSensor(state) => write!(
+                f,
+                "minecraft:sculk_sensor[power={},sculk_sensor_phase={},waterlogged={}]",
+                state.power(),
+                state.sculk_sensor_phase(),
+                state.waterlogged()
+            ),
+            Blocks::OxidizedCopper => f.write_str("minecraft:oxidized_copper"),
+            Blocks::WeatheredCopper => f.write_str("minecraft:weathered_copper"),
+            Blocks::ExposedCopper => f.write_str("minecraft:exposed_copper"),
+            Blocks::CopperBlock => f.write_str("minecraft:copper_block"),
+            Blocks::CopperOre => f.write_str("minecraft:copper_ore"),
+            Blocks::DeepslateCopperOre => f.write_str("minecraft:deepslate_copper_ore"),
+            Blocks::OxidizedCutCopper => f.write_str("minecraft:oxidized_cut_copper"),
+            Blocks::WeatheredCutCopper => f.write_str("minecraft:weathered_cut_copper"),
+            Blocks::ExposedCutCopper => f.write_str("minecraft:exposed_cut_copper"),
+            Blocks::CutCopper => f.write_str("minecraft:cut_copper"),
+            Blocks::OxidizedCutCopperStairs(state) => write!(
+                f,
+                "minecraft:oxidized_cut_copper_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::WeatheredCutCopperStairs(state) => write!(
+                f,
+                "minecraft:weathered_cut_copper_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::ExposedCutCopperStairs(state) => write!(
+                f,
+                "minecraft:exposed_cut_copper_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::CutCopperStairs(state) => write!(
+                f,
+                "minecraft:cut_copper_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::OxidizedCutCopperSlab(state) => write!(f, "minecraft:oxidized_cut_copper_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::WeatheredCutCopperSlab(state) => {
+                write!(f, "minecraft:weathered_cut_copper_slab[type={},waterlogged={}]", state.typed(), state.waterlogged())
+            },
+            Blocks::ExposedCutCopperSlab(state) => write!(f, "minecraft:exposed_cut_copper_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::CutCopperSlab(state) => write!(f, "minecraft:cut_copper_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::WaxedCopperBlock => f.write_str("minecraft:waxed_copper_block"),
+            Blocks::WaxedWeatheredCopper => f.write_str("minecraft:waxed_weathered_copper"),
+            Blocks::WaxedExposedCopper => f.write_str("minecraft:waxed_exposed_copper"),
+            Blocks::WaxedOxidizedCopper => f.write_str("minecraft:waxed_oxidized_copper"),
+            Blocks::WaxedOxidizedCutCopper => f.write_str("minecraft:waxed_oxidized_cut_copper"),
+            Blocks::WaxedWeatheredCutCopper => f.write_str("minecraft:waxed_weathered_cut_copper"),
+            Blocks::WaxedExposedCutCopper => f.write_str("minecraft:waxed_exposed_cut_copper"),
+            Blocks::WaxedCutCopper => f.write_str("minecraft:waxed_cut_copper"),
+            Blocks::WaxedOxidizedCutCopperStairs(state) => write!(
+                f,
+                "minecraft:waxed_oxidized_cut_copper_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::WaxedWeatheredCutCopperStairs(state) => write!(
+                f,
+                "minecraft:waxed_weathered_cut_copper_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::WaxedExposedCutCopperStairs(state) => write!(
+                f,
+                "minecraft:waxed_exposed_cut_copper_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::WaxedCutCopperStairs(state) => write!(
+                f,
+                "minecraft:waxed_cut_copper_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::WaxedOxidizedCutCopperSlab(state) => {
+                write!(f, "minecraft:waxed_oxidized_cut_copper_slab[type={},waterlogged={}]", state.typed(), state.waterlogged())
+            },
+            Blocks::WaxedWeatheredCutCopperSlab(state) => {
+                write!(f, "minecraft:waxed_weathered_cut_copper_slab[type={},waterlogged={}]", state.typed(), state.waterlogged())
+            },
+            Blocks::WaxedExposedCutCopperSlab(state) => {
+                write!(f, "minecraft:waxed_exposed_cut_copper_slab[type={},waterlogged={}]", state.typed(), state.waterlogged())
+            },
+            Blocks::WaxedCutCopperSlab(state) => write!(f, "minecraft:waxed_cut_copper_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::LightningRod(state) => {
+                write!(f, "minecraft:lightning_rod[facing={},powered={},waterlogged={}]", state.facing(), state.powered(), state.waterlogged())
+            },
+            Blocks::PointedDripstone(state) => write!(
+                f,
+                "minecraft:pointed_dripstone[thickness={},vertical_direction={},waterlogged={}]",
+                state.thickness(),
+                state.vertical_direction(),
+                state.waterlogged()
+            ),
+            Blocks::DripstoneBlock => f.write_str("minecraft:dripstone_block"),
+            Blocks::CaveVines(state) => write!(f, "minecraft:cave_vines[age={},berries={}]", state.age(), state.berries()),
+            Blocks::CaveVinesPlant(state) => write!(f, "minecraft:cave_vines_plant[berries={}]", state.berries()),
+            Blocks::SporeBlossom => f.write_str("minecraft:spore_blossom"),
+            Blocks::Azalea => f.write_str("minecraft:azalea"),
+            Blocks::FloweringAzalea => f.write_str("minecraft:flowering_azalea"),
+            Blocks::MossCarpet => f.write_str("minecraft:moss_carpet"),
+            Blocks::MossBlock => f.write_str("minecraft:moss_block"),
+            Blocks::BigDripleaf(state) => {
+                write!(f, "minecraft:big_dripleaf[facing={},tilt={},waterlogged={}]", state.facing(), state.tilt(), state.waterlogged())
+            },
+            Blocks::BigDripleafStem(state) => write!(f, "minecraft:big_dripleaf_stem[facing={},waterlogged={}]", state.facing(), state.waterlogged()),
+            Blocks::SmallDripleaf(state) => {
+                write!(f, "minecraft:small_dripleaf[facing={},half={},waterlogged={}]", state.facing(), state.half(), state.waterlogged())
+            },
+            Blocks::HangingRoots(state) => write!(f, "minecraft:hanging_roots[waterlogged={}]", state.waterlogged()),
+            Blocks::RootedDirt => f.write_str("minecraft:rooted_dirt"),
+            Blocks::Deepslate(state) => write!(f, "minecraft:deepslate[axis={}]", state.axis()),
+            Blocks::CobbledDeepslate => f.write_str("minecraft:cobbled_deepslate"),
+            Blocks::CobbledDeepslateStairs(state) => write!(
+                f,
+                "minecraft:cobbled_deepslate_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::CobbledDeepslateSlab(state) => write!(f, "minecraft:cobbled_deepslate_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::CobbledDeepslateWall(state) => write!(
+                f,
+                "minecraft:cobbled_deepslate_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::PolishedDeepslate => f.write_str("minecraft:polished_deepslate"),
+            Blocks::PolishedDeepslateStairs(state) => write!(
+                f,
+                "minecraft:polished_deepslate_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::PolishedDeepslateSlab(state) => write!(f, "minecraft:polished_deepslate_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::PolishedDeepslateWall(state) => write!(
+                f,
+                "minecraft:polished_deepslate_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::DeepslateTiles => f.write_str("minecraft:deepslate_tiles"),
+            Blocks::DeepslateTileStairs(state) => write!(
+                f,
+                "minecraft:deepslate_tile_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::DeepslateTileSlab(state) => write!(f, "minecraft:deepslate_tile_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::DeepslateTileWall(state) => write!(
+                f,
+                "minecraft:deepslate_tile_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::DeepslateBricks => f.write_str("minecraft:deepslate_bricks"),
+            Blocks::DeepslateBrickStairs(state) => write!(
+                f,
+                "minecraft:deepslate_brick_stairs[facing={},half={},shape={},waterlogged={}]",
+                state.facing(),
+                state.half(),
+                state.shape(),
+                state.waterlogged()
+            ),
+            Blocks::DeepslateBrickSlab(state) => write!(f, "minecraft:deepslate_brick_slab[type={},waterlogged={}]", state.typed(), state.waterlogged()),
+            Blocks::DeepslateBrickWall(state) => write!(
+                f,
+                "minecraft:deepslate_brick_wall[east={},north={},south={},up={},waterlogged={},west={}]",
+                state.east(),
+                state.north(),
+                state.south(),
+                state.up(),
+                state.waterlogged(),
+                state.west()
+            ),
+            Blocks::ChiseledDeepslate => f.write_str("minecraft:chiseled_deepslate"),
+            Blocks::CrackedDeepslateBricks => f.write_str("minecraft:cracked_deepslate_bricks"),
+            Blocks::CrackedDeepslateTiles => f.write_str("minecraft:cracked_deepslate_tiles"),
+            Blocks::InfestedDeepslate(state) => write!(f, "minecraft:infested_deepslate[axis={}]", state.axis()),
+            Blocks::SmoothBasalt => f.write_str("minecraft:smooth_basalt"),
+            Blocks::RawIronBlock => f.write_str("minecraft:raw_iron_block"),
+            Blocks::RawCopperBlock => f.write_str("minecraft:raw_copper_block"),
+            Blocks::RawGoldBlock => f.write_str("minecraft:raw_gold_block"),
+            Blocks::PottedAzaleaBush => f.write_str("minecraft:potted_azalea_bush"),
+            Blocks::PottedFloweringAzaleaBush => f.write_str("minecraft:potted_flowering_azalea_bush"),
+        }
+    }
+}
+
+impl Display for PortalAxis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PortalAxis::X => "x",
+            PortalAxis::Z => "z",
+        })
+    }
+}
+
+impl Display for HorizontalFacing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            HorizontalFacing::North => "north",
+            HorizontalFacing::South => "south",
+            HorizontalFacing::West => "west",
+            HorizontalFacing::East => "east",
+        })
+    }
+}
+
+impl Display for HopperFacing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            HopperFacing::Down => "down",
+            HopperFacing::North => "north",
+            HopperFacing::South => "south",
+            HopperFacing::West => "west",
+            HopperFacing::East => "east",
+        })
+    }
+}
+
+impl Display for AllFacing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AllFacing::North => "north",
+            AllFacing::East => "east",
+            AllFacing::South => "south",
+            AllFacing::West => "west",
+            AllFacing::Up => "up",
+            AllFacing::Down => "down",
+        })
+    }
+}
+
+impl Display for DoubleBlockHalf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DoubleBlockHalf::Upper => "upper",
+            DoubleBlockHalf::Lower => "lower",
+        })
+    }
+}
+
+impl Display for SingleBlockHalf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SingleBlockHalf::Top => "top",
+            SingleBlockHalf::Bottom => "bottom",
+        })
+    }
+}
+
+impl Display for ComparatorMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ComparatorMode::Compare => "compare",
+            ComparatorMode::Subtract => "subtract",
+        })
+    }
+}
+
+impl Display for StructureBlockMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            StructureBlockMode::Save => "save",
+            StructureBlockMode::Load => "load",
+            StructureBlockMode::Corner => "corner",
+            StructureBlockMode::Data => "data",
+        })
+    }
+}
+
+impl Display for StraightRailShape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            StraightRailShape::NorthSouth => "north_south",
+            StraightRailShape::EastWest => "east_west",
+            StraightRailShape::AscendingEast => "ascending_east",
+            StraightRailShape::AscendingWest => "ascending_west",
+            StraightRailShape::AscendingNorth => "ascending_north",
+            StraightRailShape::AscendingSouth => "ascending_south",
+        })
+    }
+}
+
+impl Display for RailShape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RailShape::NorthSouth => "north_south",
+            RailShape::EastWest => "east_west",
+            RailShape::AscendingEast => "ascending_east",
+            RailShape::AscendingWest => "ascending_west",
+            RailShape::AscendingNorth => "ascending_north",
+            RailShape::AscendingSouth => "ascending_south",
+            RailShape::SouthEast => "south_east",
+            RailShape::SouthWest => "south_west",
+            RailShape::NorthWest => "north_west",
+            RailShape::NorthEast => "north_east",
+        })
+    }
+}
+
+impl Display for StairShape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            StairShape::Straight => "straight",
+            StairShape::InnerLeft => "inner_left",
+            StairShape::InnerRight => "inner_right",
+            StairShape::OuterLeft => "outer_left",
+            StairShape::OuterRight => "outer_right",
+        })
+    }
+}
+
+impl Display for ChestType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ChestType::Single => "single",
+            ChestType::Left => "left",
+            ChestType::Right => "right",
+        })
+    }
+}
+
+impl Display for PistonType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PistonType::Normal => "normal",
+            PistonType::Sticky => "sticky",
+        })
+    }
+}
+
+impl Display for RedstoneType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RedstoneType::Up => "up",
+            RedstoneType::Side => "side",
+            RedstoneType::None => "none",
+        })
+    }
+}
+
+impl Display for SlabType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SlabType::Top => "top",
+            SlabType::Bottom => "bottom",
+            SlabType::Double => "double",
+        })
+    }
+}
+
+impl Display for WallType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WallType::None => "none",
+            WallType::Low => "low",
+            WallType::Tall => "tall",
+        })
+    }
+}
+
+impl Display for Axis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Axis::X => "x",
+            Axis::Y => "y",
+            Axis::Z => "z",
+        })
+    }
+}
+
+impl Display for Instrument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Instrument::Harp => "harp",
+            Instrument::Basedrum => "basedrum",
+            Instrument::Snare => "snare",
+            Instrument::Hat => "hat",
+            Instrument::Bass => "bass",
+            Instrument::Flute => "flute",
+            Instrument::Bell => "bell",
+            Instrument::Guitar => "guitar",
+            Instrument::Chime => "chime",
+            Instrument::Xylophone => "xylophone",
+            Instrument::IronXylophone => "iron_xylophone",
+            Instrument::CowBell => "cow_bell",
+            Instrument::Didgeridoo => "didgeridoo",
+            Instrument::Bit => "bit",
+            Instrument::Banjo => "banjo",
+            Instrument::Pling => "pling",
+        })
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Part::Head => "head",
+            Part::Foot => "foot",
+        })
+    }
+}
+
+impl Display for Hinge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Hinge::Left => "left",
+            Hinge::Right => "right",
+        })
+    }
+}
+
+impl Display for Face {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Face::Floor => "floor",
+            Face::Wall => "wall",
+            Face::Ceiling => "ceiling",
+        })
+    }
+}
+
+impl Display for Leaves {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Leaves::None => "none",
+            Leaves::Small => "small",
+            Leaves::Large => "large",
+        })
+    }
+}
+
+impl Display for Attachment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Attachment::Floor => "floor",
+            Attachment::Ceiling => "ceiling",
+            Attachment::SingleWall => "single_wall",
+            Attachment::DoubleWall => "double_wall",
+        })
+    }
+}
+
+impl Display for Orientation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Orientation::DownEast => "down_east",
+            Orientation::DownNorth => "down_north",
+            Orientation::DownSouth => "down_south",
+            Orientation::DownWest => "down_west",
+            Orientation::UpEast => "up_east",
+            Orientation::UpNorth => "up_north",
+            Orientation::UpSouth => "up_south",
+            Orientation::UpWest => "up_west",
+            Orientation::WestUp => "west_up",
+            Orientation::EastUp => "east_up",
+            Orientation::NorthUp => "north_up",
+            Orientation::SouthUp => "south_up",
+        })
+    }
+}
+
+impl Display for SculkSensorPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SculkSensorPhase::Inactive => "inactive",
+            SculkSensorPhase::Active => "active",
+            SculkSensorPhase::Cooldown => "cooldown",
+        })
+    }
+}
+
+impl Display for Thickness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Thickness::TipMerge => "tip_merge",
+            Thickness::Tip => "tip",
+            Thickness::Frustum => "frustum",
+            Thickness::Middle => "middle",
+            Thickness::Base => "base",
+        })
+    }
+}
+
+impl Display for VerticalDirection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            VerticalDirection::Up => "up",
+            VerticalDirection::Down => "down",
+        })
+    }
+}
+
+impl Display for Tilt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Tilt::None => "none",
+            Tilt::Unstable => "unstable",
+            Tilt::Partial => "partial",
+            Tilt::Full => "full",
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::world::blocks::{Blocks, SlabType};
+
+    #[test]
+    pub fn test_block_state_round_trip() {
+        let state = "minecraft:stone_slab[type=top,waterlogged=false]";
+        let block = Blocks::parse_state(state).unwrap();
+        assert!(matches!(block, Blocks::StoneSlab(slab) if slab.typed() == SlabType::Top));
+        assert_eq!(block.to_string(), state);
+        let chest = "minecraft:chest[facing=east,type=left,waterlogged=true]";
+        assert_eq!(Blocks::parse_state(chest).unwrap().to_string(), chest);
+    }
+}
@@ -1,5 +1,29 @@
 use crate::world::blocks::Blocks;
 
+/// Blocks that a placed block replaces instead of being placed next to them.
+pub fn is_replaceable(block: &Blocks) -> bool {
+    matches!(
+        block,
+        Blocks::Air
+            | Blocks::CaveAir
+            | Blocks::VoidAir
+            | Blocks::Water(_)
+            | Blocks::Lava(_)
+            | Blocks::Grass
+            | Blocks::Fern
+            | Blocks::DeadBush
+            | Blocks::Seagrass
+            | Blocks::TallSeagrass(_)
+            | Blocks::TallGrass(_)
+            | Blocks::LargeFern(_)
+            | Blocks::Vine(_)
+            | Blocks::StructureVoid
+            | Blocks::WarpedRoots
+            | Blocks::CrimsonRoots
+            | Blocks::NetherSprouts
+    )
+}
+
 /// According to Fabric, these are the values that
 /// are checked by the heightmap.
 ///
@@ -209,3 +233,632 @@ pub fn blocks_movement(block: &Blocks) -> bool {
             | Blocks::FireCoral(_)
     )
 }
+
+/// How much a block reduces light passing through it, from 0 for
+/// transparent blocks to 15 for opaque ones.
+///
+/// Corresponds to `state.getLightBlock()`, blocks that don't block
+/// movement are treated as transparent.
+pub fn light_opacity(block: &Blocks) -> u8 {
+    match block {
+        Blocks::Water(_)
+        | Blocks::Lava(_)
+        | Blocks::Cobweb
+        | Blocks::Seagrass
+        | Blocks::TallSeagrass(_)
+        | Blocks::Spawner
+        | Blocks::Ice
+        | Blocks::Beacon
+        | Blocks::SlimeBlock
+        | Blocks::FrostedIce(_)
+        | Blocks::Kelp(_)
+        | Blocks::KelpPlant
+        | Blocks::BubbleColumn(_)
+        | Blocks::HoneyBlock
+        | Blocks::OakLeaves(_)
+        | Blocks::SpruceLeaves(_)
+        | Blocks::BirchLeaves(_)
+        | Blocks::JungleLeaves(_)
+        | Blocks::AcaciaLeaves(_)
+        | Blocks::DarkOakLeaves(_)
+        | Blocks::AzaleaLeaves(_)
+        | Blocks::FloweringAzaleaLeaves(_) => 1,
+        Blocks::Air
+        | Blocks::OakSapling(_)
+        | Blocks::SpruceSapling(_)
+        | Blocks::BirchSapling(_)
+        | Blocks::JungleSapling(_)
+        | Blocks::AcaciaSapling(_)
+        | Blocks::DarkOakSapling(_)
+        | Blocks::Glass
+        | Blocks::WhiteBed(_)
+        | Blocks::OrangeBed(_)
+        | Blocks::MagentaBed(_)
+        | Blocks::LightBlueBed(_)
+        | Blocks::YellowBed(_)
+        | Blocks::LimeBed(_)
+        | Blocks::PinkBed(_)
+        | Blocks::GrayBed(_)
+        | Blocks::LightGrayBed(_)
+        | Blocks::CyanBed(_)
+        | Blocks::PurpleBed(_)
+        | Blocks::BlueBed(_)
+        | Blocks::BrownBed(_)
+        | Blocks::GreenBed(_)
+        | Blocks::RedBed(_)
+        | Blocks::BlackBed(_)
+        | Blocks::PoweredRail(_)
+        | Blocks::DetectorRail(_)
+        | Blocks::PistonHead(_)
+        | Blocks::MovingPiston(_)
+        | Blocks::Torch
+        | Blocks::WallTorch(_)
+        | Blocks::Fire(_)
+        | Blocks::SoulFire
+        | Blocks::OakStairs(_)
+        | Blocks::Chest(_)
+        | Blocks::RedstoneWire(_)
+        | Blocks::Farmland(_)
+        | Blocks::OakSign(_)
+        | Blocks::SpruceSign(_)
+        | Blocks::BirchSign(_)
+        | Blocks::AcaciaSign(_)
+        | Blocks::JungleSign(_)
+        | Blocks::DarkOakSign(_)
+        | Blocks::OakDoor(_)
+        | Blocks::Ladder(_)
+        | Blocks::Rail(_)
+        | Blocks::CobblestoneStairs(_)
+        | Blocks::OakWallSign(_)
+        | Blocks::SpruceWallSign(_)
+        | Blocks::BirchWallSign(_)
+        | Blocks::AcaciaWallSign(_)
+        | Blocks::JungleWallSign(_)
+        | Blocks::DarkOakWallSign(_)
+        | Blocks::Lever(_)
+        | Blocks::StonePressurePlate(_)
+        | Blocks::IronDoor(_)
+        | Blocks::OakPressurePlate(_)
+        | Blocks::SprucePressurePlate(_)
+        | Blocks::BirchPressurePlate(_)
+        | Blocks::JunglePressurePlate(_)
+        | Blocks::AcaciaPressurePlate(_)
+        | Blocks::DarkOakPressurePlate(_)
+        | Blocks::RedstoneTorch(_)
+        | Blocks::RedstoneWallTorch(_)
+        | Blocks::StoneButton(_)
+        | Blocks::Snow(_)
+        | Blocks::Cactus(_)
+        | Blocks::SugarCane(_)
+        | Blocks::OakFence(_)
+        | Blocks::SoulTorch
+        | Blocks::SoulWallTorch(_)
+        | Blocks::NetherPortal(_)
+        | Blocks::Cake(_)
+        | Blocks::Repeater(_)
+        | Blocks::WhiteStainedGlass
+        | Blocks::OrangeStainedGlass
+        | Blocks::MagentaStainedGlass
+        | Blocks::LightBlueStainedGlass
+        | Blocks::YellowStainedGlass
+        | Blocks::LimeStainedGlass
+        | Blocks::PinkStainedGlass
+        | Blocks::GrayStainedGlass
+        | Blocks::LightGrayStainedGlass
+        | Blocks::CyanStainedGlass
+        | Blocks::PurpleStainedGlass
+        | Blocks::BlueStainedGlass
+        | Blocks::BrownStainedGlass
+        | Blocks::GreenStainedGlass
+        | Blocks::RedStainedGlass
+        | Blocks::BlackStainedGlass
+        | Blocks::OakTrapdoor(_)
+        | Blocks::SpruceTrapdoor(_)
+        | Blocks::BirchTrapdoor(_)
+        | Blocks::JungleTrapdoor(_)
+        | Blocks::AcaciaTrapdoor(_)
+        | Blocks::DarkOakTrapdoor(_)
+        | Blocks::IronBars(_)
+        | Blocks::Chain(_)
+        | Blocks::GlassPane(_)
+        | Blocks::AttachedPumpkinStem(_)
+        | Blocks::AttachedMelonStem(_)
+        | Blocks::PumpkinStem(_)
+        | Blocks::MelonStem(_)
+        | Blocks::Vine(_)
+        | Blocks::GlowLichen(_)
+        | Blocks::OakFenceGate(_)
+        | Blocks::BrickStairs(_)
+        | Blocks::StoneBrickStairs(_)
+        | Blocks::LilyPad
+        | Blocks::NetherBrickFence(_)
+        | Blocks::NetherBrickStairs(_)
+        | Blocks::EnchantingTable
+        | Blocks::BrewingStand(_)
+        | Blocks::Cauldron
+        | Blocks::WaterCauldron(_)
+        | Blocks::LavaCauldron
+        | Blocks::PowderSnowCauldron(_)
+        | Blocks::EndPortal
+        | Blocks::EndPortalFrame(_)
+        | Blocks::DragonEgg
+        | Blocks::Cocoa(_)
+        | Blocks::SandstoneStairs(_)
+        | Blocks::EnderChest(_)
+        | Blocks::TripwireHook(_)
+        | Blocks::Tripwire(_)
+        | Blocks::SpruceStairs(_)
+        | Blocks::BirchStairs(_)
+        | Blocks::JungleStairs(_)
+        | Blocks::CobblestoneWall(_)
+        | Blocks::MossyCobblestoneWall(_)
+        | Blocks::FlowerPot
+        | Blocks::PottedOakSapling
+        | Blocks::PottedSpruceSapling
+        | Blocks::PottedBirchSapling
+        | Blocks::PottedJungleSapling
+        | Blocks::PottedAcaciaSapling
+        | Blocks::PottedDarkOakSapling
+        | Blocks::PottedFern
+        | Blocks::PottedDandelion
+        | Blocks::PottedPoppy
+        | Blocks::PottedBlueOrchid
+        | Blocks::PottedAllium
+        | Blocks::PottedAzureBluet
+        | Blocks::PottedRedTulip
+        | Blocks::PottedOrangeTulip
+        | Blocks::PottedWhiteTulip
+        | Blocks::PottedPinkTulip
+        | Blocks::PottedOxeyeDaisy
+        | Blocks::PottedCornflower
+        | Blocks::PottedLilyOfTheValley
+        | Blocks::PottedWitherRose
+        | Blocks::PottedRedMushroom
+        | Blocks::PottedBrownMushroom
+        | Blocks::PottedDeadBush
+        | Blocks::PottedCactus
+        | Blocks::OakButton(_)
+        | Blocks::SpruceButton(_)
+        | Blocks::BirchButton(_)
+        | Blocks::JungleButton(_)
+        | Blocks::AcaciaButton(_)
+        | Blocks::DarkOakButton(_)
+        | Blocks::SkeletonSkull(_)
+        | Blocks::SkeletonWallSkull(_)
+        | Blocks::WitherSkeletonSkull(_)
+        | Blocks::WitherSkeletonWallSkull(_)
+        | Blocks::ZombieHead(_)
+        | Blocks::ZombieWallHead(_)
+        | Blocks::PlayerHead(_)
+        | Blocks::PlayerWallHead(_)
+        | Blocks::CreeperHead(_)
+        | Blocks::CreeperWallHead(_)
+        | Blocks::DragonHead(_)
+        | Blocks::DragonWallHead(_)
+        | Blocks::Anvil(_)
+        | Blocks::ChippedAnvil(_)
+        | Blocks::DamagedAnvil(_)
+        | Blocks::TrappedChest(_)
+        | Blocks::LightWeightedPressurePlate(_)
+        | Blocks::HeavyWeightedPressurePlate(_)
+        | Blocks::Comparator(_)
+        | Blocks::DaylightDetector(_)
+        | Blocks::Hopper(_)
+        | Blocks::QuartzStairs(_)
+        | Blocks::ActivatorRail(_)
+        | Blocks::WhiteStainedGlassPane(_)
+        | Blocks::OrangeStainedGlassPane(_)
+        | Blocks::MagentaStainedGlassPane(_)
+        | Blocks::LightBlueStainedGlassPane(_)
+        | Blocks::YellowStainedGlassPane(_)
+        | Blocks::LimeStainedGlassPane(_)
+        | Blocks::PinkStainedGlassPane(_)
+        | Blocks::GrayStainedGlassPane(_)
+        | Blocks::LightGrayStainedGlassPane(_)
+        | Blocks::CyanStainedGlassPane(_)
+        | Blocks::PurpleStainedGlassPane(_)
+        | Blocks::BlueStainedGlassPane(_)
+        | Blocks::BrownStainedGlassPane(_)
+        | Blocks::GreenStainedGlassPane(_)
+        | Blocks::RedStainedGlassPane(_)
+        | Blocks::BlackStainedGlassPane(_)
+        | Blocks::AcaciaStairs(_)
+        | Blocks::DarkOakStairs(_)
+        | Blocks::Barrier
+        | Blocks::Light(_)
+        | Blocks::IronTrapdoor(_)
+        | Blocks::PrismarineStairs(_)
+        | Blocks::PrismarineBrickStairs(_)
+        | Blocks::DarkPrismarineStairs(_)
+        | Blocks::PrismarineSlab(_)
+        | Blocks::PrismarineBrickSlab(_)
+        | Blocks::DarkPrismarineSlab(_)
+        | Blocks::WhiteCarpet
+        | Blocks::OrangeCarpet
+        | Blocks::MagentaCarpet
+        | Blocks::LightBlueCarpet
+        | Blocks::YellowCarpet
+        | Blocks::LimeCarpet
+        | Blocks::PinkCarpet
+        | Blocks::GrayCarpet
+        | Blocks::LightGrayCarpet
+        | Blocks::CyanCarpet
+        | Blocks::PurpleCarpet
+        | Blocks::BlueCarpet
+        | Blocks::BrownCarpet
+        | Blocks::GreenCarpet
+        | Blocks::RedCarpet
+        | Blocks::BlackCarpet
+        | Blocks::WhiteBanner(_)
+        | Blocks::OrangeBanner(_)
+        | Blocks::MagentaBanner(_)
+        | Blocks::LightBlueBanner(_)
+        | Blocks::YellowBanner(_)
+        | Blocks::LimeBanner(_)
+        | Blocks::PinkBanner(_)
+        | Blocks::GrayBanner(_)
+        | Blocks::LightGrayBanner(_)
+        | Blocks::CyanBanner(_)
+        | Blocks::PurpleBanner(_)
+        | Blocks::BlueBanner(_)
+        | Blocks::BrownBanner(_)
+        | Blocks::GreenBanner(_)
+        | Blocks::RedBanner(_)
+        | Blocks::BlackBanner(_)
+        | Blocks::WhiteWallBanner(_)
+        | Blocks::OrangeWallBanner(_)
+        | Blocks::MagentaWallBanner(_)
+        | Blocks::LightBlueWallBanner(_)
+        | Blocks::YellowWallBanner(_)
+        | Blocks::LimeWallBanner(_)
+        | Blocks::PinkWallBanner(_)
+        | Blocks::GrayWallBanner(_)
+        | Blocks::LightGrayWallBanner(_)
+        | Blocks::CyanWallBanner(_)
+        | Blocks::PurpleWallBanner(_)
+        | Blocks::BlueWallBanner(_)
+        | Blocks::BrownWallBanner(_)
+        | Blocks::GreenWallBanner(_)
+        | Blocks::RedWallBanner(_)
+        | Blocks::BlackWallBanner(_)
+        | Blocks::RedSandstoneStairs(_)
+        | Blocks::OakSlab(_)
+        | Blocks::SpruceSlab(_)
+        | Blocks::BirchSlab(_)
+        | Blocks::JungleSlab(_)
+        | Blocks::AcaciaSlab(_)
+        | Blocks::DarkOakSlab(_)
+        | Blocks::StoneSlab(_)
+        | Blocks::SmoothStoneSlab(_)
+        | Blocks::SandstoneSlab(_)
+        | Blocks::CutSandstoneSlab(_)
+        | Blocks::PetrifiedOakSlab(_)
+        | Blocks::CobblestoneSlab(_)
+        | Blocks::BrickSlab(_)
+        | Blocks::StoneBrickSlab(_)
+        | Blocks::NetherBrickSlab(_)
+        | Blocks::QuartzSlab(_)
+        | Blocks::RedSandstoneSlab(_)
+        | Blocks::CutRedSandstoneSlab(_)
+        | Blocks::PurpurSlab(_)
+        | Blocks::SpruceFenceGate(_)
+        | Blocks::BirchFenceGate(_)
+        | Blocks::JungleFenceGate(_)
+        | Blocks::AcaciaFenceGate(_)
+        | Blocks::DarkOakFenceGate(_)
+        | Blocks::SpruceFence(_)
+        | Blocks::BirchFence(_)
+        | Blocks::JungleFence(_)
+        | Blocks::AcaciaFence(_)
+        | Blocks::DarkOakFence(_)
+        | Blocks::SpruceDoor(_)
+        | Blocks::BirchDoor(_)
+        | Blocks::JungleDoor(_)
+        | Blocks::AcaciaDoor(_)
+        | Blocks::DarkOakDoor(_)
+        | Blocks::EndRod(_)
+        | Blocks::ChorusPlant(_)
+        | Blocks::ChorusFlower(_)
+        | Blocks::PurpurStairs(_)
+        | Blocks::DirtPath
+        | Blocks::EndGateway
+        | Blocks::StructureVoid
+        | Blocks::TurtleEgg(_)
+        | Blocks::DeadTubeCoral(_)
+        | Blocks::DeadBrainCoral(_)
+        | Blocks::DeadBubbleCoral(_)
+        | Blocks::DeadFireCoral(_)
+        | Blocks::DeadHornCoral(_)
+        | Blocks::TubeCoral(_)
+        | Blocks::BrainCoral(_)
+        | Blocks::BubbleCoral(_)
+        | Blocks::FireCoral(_)
+        | Blocks::HornCoral(_)
+        | Blocks::DeadTubeCoralFan(_)
+        | Blocks::DeadBrainCoralFan(_)
+        | Blocks::DeadBubbleCoralFan(_)
+        | Blocks::DeadFireCoralFan(_)
+        | Blocks::DeadHornCoralFan(_)
+        | Blocks::TubeCoralFan(_)
+        | Blocks::BrainCoralFan(_)
+        | Blocks::BubbleCoralFan(_)
+        | Blocks::FireCoralFan(_)
+        | Blocks::HornCoralFan(_)
+        | Blocks::DeadTubeCoralWallFan(_)
+        | Blocks::DeadBrainCoralWallFan(_)
+        | Blocks::DeadBubbleCoralWallFan(_)
+        | Blocks::DeadFireCoralWallFan(_)
+        | Blocks::DeadHornCoralWallFan(_)
+        | Blocks::TubeCoralWallFan(_)
+        | Blocks::BrainCoralWallFan(_)
+        | Blocks::BubbleCoralWallFan(_)
+        | Blocks::FireCoralWallFan(_)
+        | Blocks::HornCoralWallFan(_)
+        | Blocks::SeaPickle(_)
+        | Blocks::Conduit(_)
+        | Blocks::BambooSapling
+        | Blocks::Bamboo(_)
+        | Blocks::PottedBamboo
+        | Blocks::VoidAir
+        | Blocks::CaveAir
+        | Blocks::PolishedGraniteStairs(_)
+        | Blocks::SmoothRedSandstoneStairs(_)
+        | Blocks::MossyStoneBrickStairs(_)
+        | Blocks::PolishedDioriteStairs(_)
+        | Blocks::MossyCobblestoneStairs(_)
+        | Blocks::EndStoneBrickStairs(_)
+        | Blocks::StoneStairs(_)
+        | Blocks::SmoothSandstoneStairs(_)
+        | Blocks::SmoothQuartzStairs(_)
+        | Blocks::GraniteStairs(_)
+        | Blocks::AndesiteStairs(_)
+        | Blocks::RedNetherBrickStairs(_)
+        | Blocks::PolishedAndesiteStairs(_)
+        | Blocks::DioriteStairs(_)
+        | Blocks::PolishedGraniteSlab(_)
+        | Blocks::SmoothRedSandstoneSlab(_)
+        | Blocks::MossyStoneBrickSlab(_)
+        | Blocks::PolishedDioriteSlab(_)
+        | Blocks::MossyCobblestoneSlab(_)
+        | Blocks::EndStoneBrickSlab(_)
+        | Blocks::SmoothSandstoneSlab(_)
+        | Blocks::SmoothQuartzSlab(_)
+        | Blocks::GraniteSlab(_)
+        | Blocks::AndesiteSlab(_)
+        | Blocks::RedNetherBrickSlab(_)
+        | Blocks::PolishedAndesiteSlab(_)
+        | Blocks::DioriteSlab(_)
+        | Blocks::BrickWall(_)
+        | Blocks::PrismarineWall(_)
+        | Blocks::RedSandstoneWall(_)
+        | Blocks::MossyStoneBrickWall(_)
+        | Blocks::GraniteWall(_)
+        | Blocks::StoneBrickWall(_)
+        | Blocks::NetherBrickWall(_)
+        | Blocks::AndesiteWall(_)
+        | Blocks::RedNetherBrickWall(_)
+        | Blocks::SandstoneWall(_)
+        | Blocks::EndStoneBrickWall(_)
+        | Blocks::DioriteWall(_)
+        | Blocks::Scaffolding(_)
+        | Blocks::Grindstone(_)
+        | Blocks::Lectern(_)
+        | Blocks::Stonecutter(_)
+        | Blocks::Bell(_)
+        | Blocks::Lantern(_)
+        | Blocks::SoulLantern(_)
+        | Blocks::Campfire(_)
+        | Blocks::SoulCampfire(_)
+        | Blocks::SweetBerryBush(_)
+        | Blocks::WarpedFungus
+        | Blocks::WarpedRoots
+        | Blocks::CrimsonFungus
+        | Blocks::WeepingVines(_)
+        | Blocks::WeepingVinesPlant
+        | Blocks::TwistingVines(_)
+        | Blocks::TwistingVinesPlant
+        | Blocks::CrimsonRoots
+        | Blocks::CrimsonSlab(_)
+        | Blocks::WarpedSlab(_)
+        | Blocks::CrimsonPressurePlate(_)
+        | Blocks::WarpedPressurePlate(_)
+        | Blocks::CrimsonFence(_)
+        | Blocks::WarpedFence(_)
+        | Blocks::CrimsonTrapdoor(_)
+        | Blocks::WarpedTrapdoor(_)
+        | Blocks::CrimsonFenceGate(_)
+        | Blocks::WarpedFenceGate(_)
+        | Blocks::CrimsonStairs(_)
+        | Blocks::WarpedStairs(_)
+        | Blocks::CrimsonButton(_)
+        | Blocks::WarpedButton(_)
+        | Blocks::CrimsonDoor(_)
+        | Blocks::WarpedDoor(_)
+        | Blocks::CrimsonSign(_)
+        | Blocks::WarpedSign(_)
+        | Blocks::CrimsonWallSign(_)
+        | Blocks::WarpedWallSign(_)
+        | Blocks::Composter(_)
+        | Blocks::PottedCrimsonFungus
+        | Blocks::PottedWarpedFungus
+        | Blocks::PottedCrimsonRoots
+        | Blocks::PottedWarpedRoots
+        | Blocks::BlackstoneStairs(_)
+        | Blocks::BlackstoneWall(_)
+        | Blocks::BlackstoneSlab(_)
+        | Blocks::PolishedBlackstoneBrickSlab(_)
+        | Blocks::PolishedBlackstoneBrickStairs(_)
+        | Blocks::PolishedBlackstoneBrickWall(_)
+        | Blocks::PolishedBlackstoneStairs(_)
+        | Blocks::PolishedBlackstoneSlab(_)
+        | Blocks::PolishedBlackstonePressurePlate(_)
+        | Blocks::PolishedBlackstoneButton(_)
+        | Blocks::PolishedBlackstoneWall(_)
+        | Blocks::Candle(_)
+        | Blocks::WhiteCandle(_)
+        | Blocks::OrangeCandle(_)
+        | Blocks::MagentaCandle(_)
+        | Blocks::LightBlueCandle(_)
+        | Blocks::YellowCandle(_)
+        | Blocks::LimeCandle(_)
+        | Blocks::PinkCandle(_)
+        | Blocks::GrayCandle(_)
+        | Blocks::LightGrayCandle(_)
+        | Blocks::CyanCandle(_)
+        | Blocks::PurpleCandle(_)
+        | Blocks::BlueCandle(_)
+        | Blocks::BrownCandle(_)
+        | Blocks::GreenCandle(_)
+        | Blocks::RedCandle(_)
+        | Blocks::BlackCandle(_)
+        | Blocks::CandleCake(_)
+        | Blocks::WhiteCandleCake(_)
+        | Blocks::OrangeCandleCake(_)
+        | Blocks::MagentaCandleCake(_)
+        | Blocks::LightBlueCandleCake(_)
+        | Blocks::YellowCandleCake(_)
+        | Blocks::LimeCandleCake(_)
+        | Blocks::PinkCandleCake(_)
+        | Blocks::GrayCandleCake(_)
+        | Blocks::LightGrayCandleCake(_)
+        | Blocks::CyanCandleCake(_)
+        | Blocks::PurpleCandleCake(_)
+        | Blocks::BlueCandleCake(_)
+        | Blocks::BrownCandleCake(_)
+        | Blocks::GreenCandleCake(_)
+        | Blocks::RedCandleCake(_)
+        | Blocks::BlackCandleCake(_)
+        | Blocks::AmethystCluster(_)
+        | Blocks::LargeAmethystBud(_)
+        | Blocks::MediumAmethystBud(_)
+        | Blocks::SmallAmethystBud(_)
+        | Blocks::OxidizedCutCopperStairs(_)
+        | Blocks::WeatheredCutCopperStairs(_)
+        | Blocks::ExposedCutCopperStairs(_)
+        | Blocks::CutCopperStairs(_)
+        | Blocks::OxidizedCutCopperSlab(_)
+        | Blocks::WeatheredCutCopperSlab(_)
+        | Blocks::ExposedCutCopperSlab(_)
+        | Blocks::CutCopperSlab(_)
+        | Blocks::WaxedOxidizedCutCopperStairs(_)
+        | Blocks::WaxedWeatheredCutCopperStairs(_)
+        | Blocks::WaxedExposedCutCopperStairs(_)
+        | Blocks::WaxedCutCopperStairs(_)
+        | Blocks::WaxedOxidizedCutCopperSlab(_)
+        | Blocks::WaxedWeatheredCutCopperSlab(_)
+        | Blocks::WaxedExposedCutCopperSlab(_)
+        | Blocks::WaxedCutCopperSlab(_)
+        | Blocks::LightningRod(_)
+        | Blocks::PointedDripstone(_)
+        | Blocks::CaveVines(_)
+        | Blocks::CaveVinesPlant(_)
+        | Blocks::SporeBlossom
+        | Blocks::Azalea
+        | Blocks::FloweringAzalea
+        | Blocks::MossCarpet
+        | Blocks::BigDripleaf(_)
+        | Blocks::BigDripleafStem(_)
+        | Blocks::SmallDripleaf(_)
+        | Blocks::HangingRoots(_)
+        | Blocks::CobbledDeepslateStairs(_)
+        | Blocks::CobbledDeepslateSlab(_)
+        | Blocks::CobbledDeepslateWall(_)
+        | Blocks::PolishedDeepslateStairs(_)
+        | Blocks::PolishedDeepslateSlab(_)
+        | Blocks::PolishedDeepslateWall(_)
+        | Blocks::DeepslateTileStairs(_)
+        | Blocks::DeepslateTileSlab(_)
+        | Blocks::DeepslateTileWall(_)
+        | Blocks::DeepslateBrickStairs(_)
+        | Blocks::DeepslateBrickSlab(_)
+        | Blocks::DeepslateBrickWall(_)
+        | Blocks::PottedAzaleaBush
+        | Blocks::PottedFloweringAzaleaBush => 0,
+        _ if !blocks_movement(block) => 0,
+        _ => 15,
+    }
+}
+
+/// The light level a block emits.
+///
+/// Corresponds to `state.getLightEmission()`.
+pub fn light_emission(block: &Blocks) -> u8 {
+    match block {
+        Blocks::Lava(_)
+        | Blocks::Fire(_)
+        | Blocks::Glowstone
+        | Blocks::JackOLantern(_)
+        | Blocks::Beacon
+        | Blocks::SeaLantern
+        | Blocks::EndPortal
+        | Blocks::EndGateway
+        | Blocks::Conduit(_)
+        | Blocks::Lantern(_)
+        | Blocks::Shroomlight
+        | Blocks::LavaCauldron => 15,
+        Blocks::RedstoneLamp(state) if state.lit() => 15,
+        Blocks::Campfire(state) if state.lit() => 15,
+        Blocks::EndRod(_) | Blocks::Torch | Blocks::WallTorch(_) => 14,
+        Blocks::CaveVines(state) if state.berries() => 14,
+        Blocks::CaveVinesPlant(state) if state.berries() => 14,
+        Blocks::Furnace(state) if state.lit() => 13,
+        Blocks::BlastFurnace(state) if state.lit() => 13,
+        Blocks::Smoker(state) if state.lit() => 13,
+        Blocks::NetherPortal(_) => 11,
+        Blocks::SoulFire | Blocks::SoulTorch | Blocks::SoulWallTorch(_) | Blocks::SoulLantern(_) | Blocks::CryingObsidian => 10,
+        Blocks::SoulCampfire(state) if state.lit() => 10,
+        Blocks::RedstoneOre(state) if state.lit() => 9,
+        Blocks::DeepslateRedstoneOre(state) if state.lit() => 9,
+        Blocks::EnchantingTable | Blocks::EnderChest(_) | Blocks::GlowLichen(_) => 7,
+        Blocks::RedstoneTorch(state) if state.lit() => 7,
+        Blocks::RedstoneWallTorch(state) if state.lit() => 7,
+        Blocks::AmethystCluster(_) => 5,
+        Blocks::LargeAmethystBud(_) => 4,
+        Blocks::MagmaBlock => 3,
+        Blocks::MediumAmethystBud(_) => 2,
+        Blocks::SmallAmethystBud(_)
+        | Blocks::BrewingStand(_)
+        | Blocks::BrownMushroom
+        | Blocks::DragonEgg
+        | Blocks::EndPortalFrame(_)
+        | Blocks::SculkSensor(_) => 1,
+        Blocks::SeaPickle(state) if state.waterlogged() => 3 * state.pickles() as u8 + 3,
+        Blocks::RespawnAnchor(state) => (state.charges() * 15 / 4) as u8,
+        Blocks::Light(state) => state.level() as u8,
+        Blocks::CandleCake(state) if state.lit() => 3,
+        Blocks::WhiteCandleCake(state) if state.lit() => 3,
+        Blocks::OrangeCandleCake(state) if state.lit() => 3,
+        Blocks::MagentaCandleCake(state) if state.lit() => 3,
+        Blocks::LightBlueCandleCake(state) if state.lit() => 3,
+        Blocks::YellowCandleCake(state) if state.lit() => 3,
+        Blocks::LimeCandleCake(state) if state.lit() => 3,
+        Blocks::PinkCandleCake(state) if state.lit() => 3,
+        Blocks::GrayCandleCake(state) if state.lit() => 3,
+        Blocks::LightGrayCandleCake(state) if state.lit() => 3,
+        Blocks::CyanCandleCake(state) if state.lit() => 3,
+        Blocks::PurpleCandleCake(state) if state.lit() => 3,
+        Blocks::BlueCandleCake(state) if state.lit() => 3,
+        Blocks::BrownCandleCake(state) if state.lit() => 3,
+        Blocks::GreenCandleCake(state) if state.lit() => 3,
+        Blocks::RedCandleCake(state) if state.lit() => 3,
+        Blocks::BlackCandleCake(state) if state.lit() => 3,
+        Blocks::Candle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::WhiteCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::OrangeCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::MagentaCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::LightBlueCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::YellowCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::LimeCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::PinkCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::GrayCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::LightGrayCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::CyanCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::PurpleCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::BlueCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::BrownCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::GreenCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::RedCandle(state) if state.lit() => 3 * state.candles() as u8,
+        Blocks::BlackCandle(state) if state.lit() => 3 * state.candles() as u8,
+        _ => 0,
+    }
+}
@@ -1,61 +1,273 @@
+use crate::world::biome::Biome;
+use crate::world::bit_storage::BitStorage;
+use crate::world::block_entity::BlockEntity;
 use crate::world::blocks::Blocks;
+use crate::world::dimension::Dimension;
+use crate::world::light::{compute_light, SectionLight};
 use crate::world::palette::Palette;
 
+/// The sections clients up to 1.16 know, from y 0 to 256.
 pub const SECTIONS_NUM: u16 = 16;
 pub const SECTION_WIDTH: u16 = 16;
 pub const SECTION_LENGTH: u16 = 16;
 pub const SECTION_HEIGHT: u16 = 16;
+const SECTION_VOLUME: usize = (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH) as usize;
+/// Biomes are stored per cell of 4x4x4 blocks.
+pub const BIOME_CELL_SIZE: u16 = 4;
+pub const BIOME_CELLS_WIDTH: u16 = SECTION_WIDTH / BIOME_CELL_SIZE;
+pub const BIOME_CELLS_LENGTH: u16 = SECTION_LENGTH / BIOME_CELL_SIZE;
+/// The biome cells clients up to 1.16 know, from y 0 to 256.
+pub const BIOME_CELLS_HEIGHT: u16 = SECTIONS_NUM * SECTION_HEIGHT / BIOME_CELL_SIZE;
+pub const BIOME_CELLS: usize = (BIOME_CELLS_WIDTH * BIOME_CELLS_LENGTH * BIOME_CELLS_HEIGHT) as usize;
+const BIOME_CELLS_PER_SECTION: usize = (BIOME_CELLS_WIDTH * BIOME_CELLS_LENGTH * (SECTION_HEIGHT / BIOME_CELL_SIZE)) as usize;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Chunk {
-    sections: [Option<ChunkSection>; SECTIONS_NUM as usize],
-    bitmask: i32,
+    /// From the lowest section up.
+    sections: Vec<Option<ChunkSection>>,
+    /// The section y of the lowest section, negative below y 0.
+    min_section: i32,
     pos: ChunkPos,
+    biomes: Option<Vec<Biome>>,
+    light: Option<Vec<SectionLight>>,
+    block_entities: Vec<BlockEntity>,
     dirty: bool,
 }
 
 impl Chunk {
-    pub fn empty(pos: ChunkPos) -> Self {
+    /// A chunk as high as the dimension.
+    pub fn empty(pos: ChunkPos, dimension: &Dimension) -> Self { Chunk::with_height(pos, dimension.min_section(), dimension.section_count()) }
+
+    /// A chunk of `section_count` sections, the lowest one at `min_section`.
+    pub fn with_height(pos: ChunkPos, min_section: i32, section_count: usize) -> Self {
         Chunk {
-            sections: Default::default(),
-            bitmask: 0,
+            sections: (0..section_count).map(|_| None).collect(),
+            min_section,
             pos,
+            biomes: None,
+            light: None,
+            block_entities: Vec::new(),
             dirty: true,
         }
     }
 
-    pub fn set_block_at(&mut self, x: u16, y: u16, z: u16, block_state: Blocks) {
-        let section_y = y / SECTION_HEIGHT;
-        if let Some(section) = &mut self.sections[section_y as usize] {
-            section.set_block_at(x, y - (section_y * SECTION_HEIGHT), z, block_state);
+    /// Changes the height of the chunk, blocks and block entities outside
+    /// of it are dropped. New biome cells copy the nearest section. This
+    /// doesn't mark the chunk dirty, so an unchanged chunk keeps its saved
+    /// data outside the new height.
+    pub fn set_height(&mut self, min_section: i32, section_count: usize) {
+        if min_section == self.min_section && section_count == self.sections.len() {
+            return;
+        }
+        let mut sections: Vec<Option<ChunkSection>> = (0..section_count).map(|_| None).collect();
+        for (index, section) in self.sections.drain(..).enumerate() {
+            if let Some(new_index) = usize::try_from(self.min_section + index as i32 - min_section)
+                .ok()
+                .filter(|new_index| *new_index < section_count)
+            {
+                sections[new_index] = section;
+            }
+        }
+        if let Some(biomes) = &self.biomes {
+            let old_count = biomes.len() / BIOME_CELLS_PER_SECTION;
+            self.biomes = (old_count > 0).then(|| {
+                (0..section_count as i32)
+                    .flat_map(|index| {
+                        let old_index = (min_section + index - self.min_section).clamp(0, old_count as i32 - 1) as usize;
+                        biomes[old_index * BIOME_CELLS_PER_SECTION..(old_index + 1) * BIOME_CELLS_PER_SECTION]
+                            .iter()
+                            .copied()
+                    })
+                    .collect()
+            });
+        }
+        self.sections = sections;
+        self.min_section = min_section;
+        let (min_y, max_y) = (self.min_y(), self.min_y() + self.height());
+        self.block_entities
+            .retain(|block_entity| (min_y..max_y).contains(&block_entity.position().get_y()));
+        self.light = None;
+    }
+
+    /// The section y of the lowest section.
+    pub fn min_section(&self) -> i32 { self.min_section }
+
+    pub fn section_count(&self) -> usize { self.sections.len() }
+
+    /// The y of the lowest blocks.
+    pub fn min_y(&self) -> i32 { self.min_section * SECTION_HEIGHT as i32 }
+
+    /// The number of blocks from the bottom to the top of the chunk.
+    pub fn height(&self) -> i32 { self.sections.len() as i32 * SECTION_HEIGHT as i32 }
+
+    /// Also removes the block entity at this position, positions
+    /// outside the chunk height are ignored.
+    pub fn set_block_at(&mut self, x: u16, y: i32, z: u16, block_state: Blocks) {
+        let index = match self.section_index(y.div_euclid(SECTION_HEIGHT as i32)) {
+            Some(index) => index,
+            None => return,
+        };
+        let section_y = y.rem_euclid(SECTION_HEIGHT as i32) as u16;
+        self.dirty = true;
+        self.light = None;
+        self.block_entities.retain(|entity| !is_at(entity, x, y, z));
+        if let Some(section) = &mut self.sections[index] {
+            section.set_block_at(x, section_y, z, block_state);
             if section.block_count == 0 {
-                self.sections[section_y as usize] = None;
-                self.bitmask ^= 1 << section_y;
+                self.sections[index] = None;
             }
         } else if block_state != Blocks::Air {
             let mut section = ChunkSection::empty();
-            section.set_block_at(x, y - (section_y * SECTION_HEIGHT), z, block_state);
-            self.sections[section_y as usize] = Some(section);
-            self.bitmask ^= 1 << section_y;
+            section.set_block_at(x, section_y, z, block_state);
+            self.sections[index] = Some(section);
         }
     }
 
-    pub fn get_bit_mask(&self) -> i32 { self.bitmask }
+    /// The block at a position, air outside the chunk height.
+    pub fn block_at(&self, x: u16, y: i32, z: u16) -> Blocks {
+        self.section(y.div_euclid(SECTION_HEIGHT as i32))
+            .map_or(Blocks::Air, |section| *section.block_at(x, y.rem_euclid(SECTION_HEIGHT as i32) as u16, z))
+    }
+
+    /// The section at `section_y`, `None` if it has no blocks or is
+    /// outside the chunk height.
+    pub fn section(&self, section_y: i32) -> Option<&ChunkSection> { self.section_index(section_y).and_then(|index| self.sections[index].as_ref()) }
+
+    /// Replaces a whole section, empty sections are removed and sections
+    /// outside the chunk height are ignored.
+    pub fn set_section(&mut self, section_y: i32, section: ChunkSection) {
+        let index = match self.section_index(section_y) {
+            Some(index) => index,
+            None => return,
+        };
+        self.dirty = true;
+        self.light = None;
+        self.sections[index] = (section.block_count > 0).then_some(section);
+    }
+
+    fn section_index(&self, section_y: i32) -> Option<usize> {
+        usize::try_from(section_y - self.min_section)
+            .ok()
+            .filter(|index| *index < self.sections.len())
+    }
+
+    /// The biome of a 4x4x4 cell, `None` if this chunk has no biome data
+    /// or the cell is outside the chunk height.
+    pub fn biome_at(&self, cell_x: u16, cell_y: i32, cell_z: u16) -> Option<Biome> {
+        let cell_y = self.biome_cell_y(cell_y)?;
+        self.biomes.as_ref().map(|biomes| biomes[Chunk::biome_index(cell_x, cell_y, cell_z)])
+    }
+
+    /// All cell biomes in the order of [`biome_index`](Self::biome_index).
+    pub fn biomes(&self) -> Option<&[Biome]> { self.biomes.as_deref() }
+
+    /// Replaces all cell biomes from the bottom of the chunk up,
+    /// missing cells become the last biome.
+    pub fn set_biomes(&mut self, mut biomes: Vec<Biome>) {
+        self.dirty = true;
+        if let Some(last) = biomes.last().copied() {
+            biomes.resize(self.sections.len() * BIOME_CELLS_PER_SECTION, last);
+            self.biomes = Some(biomes);
+        }
+    }
+
+    /// Sets the biome of a single cell, the other cells become `default` if
+    /// this chunk had no biome data yet. Cells outside the chunk height are
+    /// ignored.
+    pub fn set_biome(&mut self, cell_x: u16, cell_y: i32, cell_z: u16, biome: Biome, default: Biome) {
+        let cell_y = match self.biome_cell_y(cell_y) {
+            Some(cell_y) => cell_y,
+            None => return,
+        };
+        self.dirty = true;
+        let cells = self.sections.len() * BIOME_CELLS_PER_SECTION;
+        self.biomes.get_or_insert_with(|| vec![default; cells])[Chunk::biome_index(cell_x, cell_y, cell_z)] = biome;
+    }
+
+    /// The index of a cell, `cell_y` counts from the bottom of the chunk.
+    pub fn biome_index(cell_x: u16, cell_y: u16, cell_z: u16) -> usize {
+        cell_x as usize + cell_z as usize * BIOME_CELLS_WIDTH as usize + cell_y as usize * (BIOME_CELLS_WIDTH * BIOME_CELLS_LENGTH) as usize
+    }
+
+    /// The cell y counted from the bottom of the chunk.
+    fn biome_cell_y(&self, cell_y: i32) -> Option<u16> {
+        let cells_per_section = (SECTION_HEIGHT / BIOME_CELL_SIZE) as i32;
+        u16::try_from(cell_y - self.min_section * cells_per_section)
+            .ok()
+            .filter(|cell_y| (*cell_y as usize) < self.sections.len() * cells_per_section as usize)
+    }
+
+    /// Block entities with their absolute position.
+    pub fn block_entities(&self) -> &[BlockEntity] { &self.block_entities }
+
+    /// Adds a block entity, replacing the one at the same position.
+    /// Its position has to lie within this chunk.
+    pub fn set_block_entity(&mut self, block_entity: BlockEntity) {
+        self.dirty = true;
+        let pos = block_entity.position();
+        let (x, y, z) = (pos.get_x().rem_euclid(SECTION_WIDTH as i32) as u16, pos.get_y(), pos.get_z().rem_euclid(SECTION_LENGTH as i32) as u16);
+        self.block_entities.retain(|entity| !is_at(entity, x, y, z));
+        self.block_entities.push(block_entity);
+    }
+
+    /// The light of every section from the lowest one up, `None` until
+    /// [`update_light`](Self::update_light) is called and again after a
+    /// block changed.
+    pub fn light(&self) -> Option<&[SectionLight]> { self.light.as_deref() }
+
+    /// The light of the section at `section_y`, `None` if the light wasn't
+    /// computed or the section is outside the chunk height.
+    pub fn section_light(&self, section_y: i32) -> Option<&SectionLight> {
+        let index = self.section_index(section_y)?;
+        self.light.as_ref().map(|light| &light[index])
+    }
+
+    /// Recomputes the light of this chunk as if it had no neighbours.
+    pub fn update_light(&mut self, has_skylight: bool) { self.light = Some(compute_light(self, has_skylight, |_, _| None)); }
+
+    /// Sets light computed with [`compute_light`] and the neighbours of this
+    /// chunk.
+    pub fn set_light(&mut self, light: Vec<SectionLight>) { self.light = Some(light); }
+
+    /// Drops the light so it gets recomputed, used when a neighbour changed.
+    pub fn clear_light(&mut self) { self.light = None; }
+
+    /// The sections from y 0 to 256 that have blocks, which are the
+    /// sections clients up to 1.16 can be sent.
+    pub fn get_bit_mask(&self) -> i32 {
+        (0..SECTIONS_NUM as i32)
+            .filter(|section_y| self.section(*section_y).is_some())
+            .fold(0, |mask, section_y| mask | 1 << section_y)
+    }
 
     pub fn get_position(&self) -> &ChunkPos { &self.pos }
 
+    /// Dirty chunks have changes that aren't saved yet. Chunks are encoded
+    /// anew whenever they are sent, there is no encoded chunk to invalidate.
     pub fn mark_dirty(&mut self, dirty: bool) { self.dirty = dirty; }
 
     pub fn is_dirty(&self) -> bool { self.dirty }
 
-    pub fn get_chunk_sections(&self) -> &[Option<ChunkSection>; SECTIONS_NUM as usize] { &self.sections }
+    /// Every section from the lowest one up, the first one is at
+    /// [`min_section`](Self::min_section).
+    pub fn get_chunk_sections(&self) -> &[Option<ChunkSection>] { &self.sections }
+}
+
+fn is_at(block_entity: &BlockEntity, x: u16, y: i32, z: u16) -> bool {
+    let pos = block_entity.position();
+    pos.get_x().rem_euclid(SECTION_WIDTH as i32) == x as i32 && pos.get_y() == y && pos.get_z().rem_euclid(SECTION_LENGTH as i32) == z as i32
 }
 
 #[derive(Clone, Debug)]
 pub struct ChunkSection {
     block_count: u16,
+    /// Every entry is used by at least one block.
     palette: Palette<Blocks>,
-    blocks: Vec<u16>,
+    /// How many blocks use each palette entry.
+    counts: Vec<u16>,
+    /// Palette indices with just enough bits for the palette,
+    /// none if the palette has a single entry.
+    blocks: BitStorage,
 }
 
 impl ChunkSection {
@@ -63,36 +275,131 @@ impl ChunkSection {
         ChunkSection {
             block_count: 0,
             palette: Palette::new(vec![Blocks::Air]),
-            blocks: vec![0u16; (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH) as usize],
+            counts: vec![SECTION_VOLUME as u16],
+            blocks: BitStorage::new(0, SECTION_VOLUME),
+        }
+    }
+
+    /// Creates a section from a palette and a palette index for every block,
+    /// in the order of [`calculate_index`](Self::calculate_index).
+    /// Indices that aren't part of the palette become air and unused palette
+    /// entries are left out. Loaders should prefer this over setting every
+    /// block on its own.
+    pub fn from_palette(palette: &[Blocks], indices: &[u16]) -> Self {
+        let indices = &indices[..indices.len().min(SECTION_VOLUME)];
+        let mut used = vec![false; palette.len()];
+        let mut has_air = indices.len() < SECTION_VOLUME;
+        for index in indices {
+            match used.get_mut(*index as usize) {
+                Some(used) => *used = true,
+                None => has_air = true,
+            }
+        }
+        let mut section_palette = Palette::empty();
+        let mut add = |block: Blocks| match section_palette.get_index(&block) {
+            Some(value) => value as u16,
+            None => section_palette.push(block) as u16,
+        };
+        let mapping: Vec<u16> = palette
+            .iter()
+            .zip(used)
+            .map(|(block, used)| {
+                if used {
+                    add(*block)
+                } else {
+                    0
+                }
+            })
+            .collect();
+        let air = if has_air {
+            add(Blocks::Air)
+        } else {
+            0
+        };
+        let values: Vec<u16> = (0..SECTION_VOLUME)
+            .map(|i| indices.get(i).and_then(|index| mapping.get(*index as usize)).copied().unwrap_or(air))
+            .collect();
+        let mut counts = vec![0u16; section_palette.items().len()];
+        for value in &values {
+            counts[*value as usize] += 1;
+        }
+        let air_count = section_palette.get_index(&Blocks::Air).map_or(0, |air| counts[air]);
+        ChunkSection {
+            block_count: SECTION_VOLUME as u16 - air_count,
+            blocks: BitStorage::from_values(Self::bits_per_block(counts.len()), SECTION_VOLUME, values),
+            palette: section_palette,
+            counts,
         }
     }
 
+    /// Palette entries that no block uses anymore are removed.
     pub fn set_block_at(&mut self, x: u16, y: u16, z: u16, block_state: Blocks) {
-        let old_value = self.blocks[Self::calculate_index(x, y, z)];
-        if block_state == Blocks::Air && old_value != 0 {
+        let index = Self::calculate_index(x, y, z);
+        let old_value = self.blocks.get(index);
+        let value = self.palette_value(block_state);
+        if value == old_value {
+            return;
+        }
+        self.blocks.set(index, value);
+        self.counts[value as usize] += 1;
+        self.counts[old_value as usize] -= 1;
+        if self.palette.at(old_value as usize) == Some(&Blocks::Air) {
+            self.block_count += 1;
+        } else if block_state == Blocks::Air {
             self.block_count -= 1;
-            if !self.blocks.contains(&old_value) {
-                let last = self.palette.remove(old_value as usize) as u16;
-                for value in self.blocks.iter_mut() {
-                    if *value == last {
-                        *value = old_value;
-                    }
+        }
+        if self.counts[old_value as usize] == 0 {
+            self.remove_value(old_value);
+        }
+    }
+
+    /// The bits needed to store indices of a palette with `len` entries,
+    /// at least 4 like clients expect unless there is a single entry.
+    pub fn bits_per_block(len: usize) -> u8 {
+        match len {
+            0 | 1 => 0,
+            len => (usize::BITS - (len - 1).leading_zeros()).max(4) as u8,
+        }
+    }
+
+    /// The palette index of a block, adding it to the palette if needed.
+    fn palette_value(&mut self, block_state: Blocks) -> u16 {
+        match self.palette.get_index(&block_state) {
+            Some(index) => index as u16,
+            None => {
+                self.counts.push(0);
+                let value = self.palette.push(block_state);
+                self.resize_blocks();
+                value as u16
+            },
+        }
+    }
+
+    /// Removes an unused palette entry, the last entry takes its place.
+    fn remove_value(&mut self, value: u16) {
+        let last = self.palette.remove(value as usize) as u16;
+        self.counts.swap_remove(value as usize);
+        if last != value {
+            for index in 0..SECTION_VOLUME {
+                if self.blocks.get(index) == last {
+                    self.blocks.set(index, value);
                 }
             }
-        } else if block_state != Blocks::Air && old_value == 0 {
-            self.block_count += 1;
         }
-        if let Some(index) = self.palette.get_index(&block_state) {
-            self.blocks[(x + z * SECTION_WIDTH + y * SECTION_WIDTH * SECTION_LENGTH) as usize] = index as u16;
-        } else {
-            let index = self.palette.push(block_state);
-            self.blocks[(x + z * SECTION_WIDTH + y * SECTION_WIDTH * SECTION_LENGTH) as usize] = index as u16;
+        self.resize_blocks();
+    }
+
+    /// Repacks the blocks if the palette needs more or fewer bits.
+    fn resize_blocks(&mut self) {
+        let bits = Self::bits_per_block(self.palette.items().len());
+        if bits != self.blocks.bits() {
+            self.blocks = self.blocks.resized(bits);
         }
     }
 
     pub fn block_at(&self, x: u16, y: u16, z: u16) -> &Blocks {
         self.palette
-            .at(self.blocks[ChunkSection::calculate_index(x, y, z)] as usize)
+            .at(self.blocks.get(ChunkSection::calculate_index(x, y, z)) as usize)
             .unwrap()
     }
 
@@ -103,7 +410,10 @@ impl ChunkSection {
 
     pub fn get_palette(&self) -> &Palette<Blocks> { &self.palette }
 
-    pub fn get_block_data(&self) -> &Vec<u16> { &self.blocks }
+    /// How many blocks use each palette entry.
+    pub fn get_palette_counts(&self) -> &[u16] { &self.counts }
+
+    pub fn get_block_data(&self) -> &BitStorage { &self.blocks }
 
     pub fn calculate_index(x: u16, y: u16, z: u16) -> usize { (x + z * SECTION_WIDTH + y * SECTION_WIDTH * SECTION_LENGTH) as usize }
 }
@@ -133,3 +443,54 @@ impl From<&ChunkPos> for (i32, i32) {
 impl From<(i32, i32)> for ChunkPos {
     fn from((x, z): (i32, i32)) -> Self { ChunkPos::new(x, z) }
 }
+
+#[cfg(test)]
+mod test {
+    use super::ChunkSection;
+    use crate::world::blocks::Blocks;
+
+    #[test]
+    pub fn test_section_palette() {
+        let mut section = ChunkSection::empty();
+        section.set_block_at(0, 0, 0, Blocks::Stone);
+        section.set_block_at(1, 0, 0, Blocks::Granite);
+        section.set_block_at(2, 0, 0, Blocks::Dirt);
+        section.set_block_at(0, 0, 0, Blocks::Dirt);
+        assert_eq!(section.get_palette().items(), [Blocks::Air, Blocks::Dirt, Blocks::Granite]);
+        assert_eq!(*section.block_at(1, 0, 0), Blocks::Granite);
+        assert_eq!(section.get_block_count(), 3);
+        section.set_block_at(1, 0, 0, Blocks::Air);
+        assert_eq!(section.get_palette().items(), [Blocks::Air, Blocks::Dirt]);
+        assert_eq!(section.get_palette().get_index(&Blocks::Granite), None);
+        assert_eq!(section.get_block_count(), 2);
+
+        assert_eq!(section.get_block_data().bits(), 4);
+        section.set_block_at(0, 0, 0, Blocks::Air);
+        section.set_block_at(2, 0, 0, Blocks::Air);
+        assert_eq!(section.get_palette().items(), [Blocks::Air]);
+        assert!(section.get_block_data().data().is_empty());
+
+        let palette = [Blocks::Stone, Blocks::Air, Blocks::Bedrock, Blocks::Stone];
+        let section = ChunkSection::from_palette(&palette, &[3, 1, 0, 7]);
+        assert_eq!(section.get_palette().items(), [Blocks::Stone, Blocks::Air]);
+        assert_eq!(section.get_palette_counts(), [2, 4094]);
+        assert_eq!(*section.block_at(0, 0, 0), Blocks::Stone);
+        assert_eq!(*section.block_at(3, 0, 0), Blocks::Air);
+        assert_eq!(section.get_block_count(), 2);
+    }
+
+    #[test]
+    pub fn test_single_value_section() {
+        let mut section = ChunkSection::from_palette(&[Blocks::Stone], &[0; 4096]);
+        assert_eq!(section.get_block_count(), 4096);
+        assert!(section.get_block_data().data().is_empty());
+        section.set_block_at(5, 5, 5, Blocks::Granite);
+        assert_eq!(section.get_block_data().data().len(), 256);
+        assert_eq!(*section.block_at(5, 5, 5), Blocks::Granite);
+        assert_eq!(*section.block_at(5, 5, 6), Blocks::Stone);
+        section.set_block_at(5, 5, 5, Blocks::Stone);
+        assert_eq!(section.get_palette().items(), [Blocks::Stone]);
+        assert!(section.get_block_data().data().is_empty());
+        assert_eq!(section.get_block_count(), 4096);
+    }
+}
@@ -1,19 +1,161 @@
-use std::borrow::Cow;
+use crate::data::Identifier;
+use crate::world::chunks::SECTION_HEIGHT;
 
+/// A dimension type, 1.16+ clients receive all of its properties
+/// while older clients only know the id.
+#[derive(Clone, Debug)]
 pub struct Dimension {
-    name: Cow<'static, str>,
+    name: Identifier,
     id: i32,
+    natural: bool,
+    ambient_light: f32,
+    fixed_time: Option<i64>,
+    has_ceiling: bool,
+    has_skylight: bool,
+    shrunk: bool,
+    ultrawarm: bool,
+    has_raids: bool,
+    respawn_anchor_works: bool,
+    bed_works: bool,
+    piglin_safe: bool,
+    logical_height: i32,
+    infiniburn: Identifier,
+    min_y: i32,
+    height: i32,
 }
 
 impl Dimension {
-    pub fn new<S: Into<Cow<'static, str>>>(name: S, id: i32) -> Self {
+    pub fn overworld() -> Self {
         Dimension {
-            name: name.into(),
-            id,
+            name: Identifier::location_static("overworld"),
+            id: 0,
+            natural: true,
+            ambient_light: 0.0,
+            fixed_time: None,
+            has_ceiling: false,
+            has_skylight: true,
+            shrunk: false,
+            ultrawarm: false,
+            has_raids: true,
+            respawn_anchor_works: false,
+            bed_works: true,
+            piglin_safe: false,
+            logical_height: 256,
+            infiniburn: Identifier::location_static("infiniburn_overworld"),
+            min_y: 0,
+            height: 256,
         }
     }
 
-    pub fn name(&self) -> &str { &self.name }
+    pub fn nether() -> Self {
+        Dimension {
+            name: Identifier::location_static("the_nether"),
+            id: -1,
+            natural: false,
+            ambient_light: 0.1,
+            fixed_time: Some(18000),
+            has_ceiling: true,
+            has_skylight: false,
+            shrunk: true,
+            ultrawarm: true,
+            has_raids: false,
+            respawn_anchor_works: true,
+            bed_works: false,
+            piglin_safe: true,
+            logical_height: 128,
+            infiniburn: Identifier::location_static("infiniburn_nether"),
+            min_y: 0,
+            height: 256,
+        }
+    }
+
+    pub fn end() -> Self {
+        Dimension {
+            name: Identifier::location_static("the_end"),
+            id: 1,
+            natural: false,
+            ambient_light: 0.0,
+            fixed_time: Some(6000),
+            has_ceiling: false,
+            has_skylight: false,
+            shrunk: false,
+            ultrawarm: false,
+            has_raids: true,
+            respawn_anchor_works: false,
+            bed_works: false,
+            piglin_safe: false,
+            logical_height: 256,
+            infiniburn: Identifier::location_static("infiniburn_end"),
+            min_y: 0,
+            height: 256,
+        }
+    }
+
+    /// Changes where blocks can be, both values are rounded down to whole
+    /// sections and the height is at least one section. Clients up to 1.16
+    /// only get the blocks from y 0 to 256.
+    pub fn with_height(mut self, min_y: i32, height: i32) -> Self {
+        let section_height = SECTION_HEIGHT as i32;
+        self.min_y = min_y.div_euclid(section_height) * section_height;
+        self.height = (height / section_height).max(1) * section_height;
+        self
+    }
+
+    /// Finds the preset called `name`, with or without the
+    /// `minecraft` namespace.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.strip_prefix("minecraft:").unwrap_or(name) {
+            "overworld" => Some(Dimension::overworld()),
+            "the_nether" | "nether" => Some(Dimension::nether()),
+            "the_end" | "end" => Some(Dimension::end()),
+            _ => None,
+        }
+    }
 
+    pub fn name(&self) -> &Identifier { &self.name }
+
+    /// The id clients before 1.16 use, -1 for the nether, 0 for the
+    /// overworld and 1 for the end.
     pub fn id(&self) -> i32 { self.id }
+
+    pub fn natural(&self) -> bool { self.natural }
+
+    pub fn ambient_light(&self) -> f32 { self.ambient_light }
+
+    pub fn fixed_time(&self) -> Option<i64> { self.fixed_time }
+
+    pub fn has_ceiling(&self) -> bool { self.has_ceiling }
+
+    pub fn has_skylight(&self) -> bool { self.has_skylight }
+
+    pub fn shrunk(&self) -> bool { self.shrunk }
+
+    pub fn ultrawarm(&self) -> bool { self.ultrawarm }
+
+    pub fn has_raids(&self) -> bool { self.has_raids }
+
+    pub fn respawn_anchor_works(&self) -> bool { self.respawn_anchor_works }
+
+    pub fn bed_works(&self) -> bool { self.bed_works }
+
+    pub fn piglin_safe(&self) -> bool { self.piglin_safe }
+
+    pub fn logical_height(&self) -> i32 { self.logical_height }
+
+    pub fn infiniburn(&self) -> &Identifier { &self.infiniburn }
+
+    /// The y of the lowest blocks.
+    pub fn min_y(&self) -> i32 { self.min_y }
+
+    /// The number of blocks from `min_y` up.
+    pub fn height(&self) -> i32 { self.height }
+
+    /// The section y of the lowest section.
+    pub fn min_section(&self) -> i32 { self.min_y / SECTION_HEIGHT as i32 }
+
+    pub fn section_count(&self) -> usize { (self.height / SECTION_HEIGHT as i32) as usize }
+}
+
+impl Default for Dimension {
+    fn default() -> Self { Dimension::overworld() }
 }
@@ -0,0 +1,471 @@
+//! Entities that never move, like armor stands used as holograms, item
+//! frames and paintings. They are loaded from schematics in the format
+//! of 1.13 and newer.
+
+use std::collections::HashMap;
+
+use fastnbt::Value;
+use uuid::Uuid;
+
+use crate::player::data::{LookAngles, Position};
+use crate::world::BlockPosition;
+
+/// Painting motives with their width and height in blocks, the
+/// index is the id clients from 1.13 to 1.18 use.
+pub const PAINTING_MOTIVES: [(&str, u8, u8); 26] = [
+    ("kebab", 1, 1),
+    ("aztec", 1, 1),
+    ("alban", 1, 1),
+    ("aztec2", 1, 1),
+    ("bomb", 1, 1),
+    ("plant", 1, 1),
+    ("wasteland", 1, 1),
+    ("pool", 2, 1),
+    ("courbet", 2, 1),
+    ("sea", 2, 1),
+    ("sunset", 2, 1),
+    ("creebet", 2, 1),
+    ("wanderer", 1, 2),
+    ("graham", 1, 2),
+    ("match", 2, 2),
+    ("bust", 2, 2),
+    ("stage", 2, 2),
+    ("void", 2, 2),
+    ("skull_and_roses", 2, 2),
+    ("wither", 2, 2),
+    ("fighters", 4, 2),
+    ("pointer", 4, 4),
+    ("pigscene", 4, 4),
+    ("burning_skull", 4, 4),
+    ("skeleton", 4, 3),
+    ("donkey_kong", 4, 3),
+];
+
+/// How far hanging entities stick out of the block they hang at.
+const HANGING_OFFSET: f64 = 0.46875;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Down,
+    Up,
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Direction {
+    const ALL: [Direction; 6] = [Direction::Down, Direction::Up, Direction::North, Direction::South, Direction::West, Direction::East];
+    const HORIZONTAL: [Direction; 4] = [Direction::South, Direction::West, Direction::North, Direction::East];
+
+    /// `0 down, 1 up, 2 north, 3 south, 4 west, 5 east`
+    pub fn from_id(id: i32) -> Option<Self> { Direction::ALL.get(usize::try_from(id).ok()?).copied() }
+
+    /// `0 south, 1 west, 2 north, 3 east`
+    pub fn from_horizontal_id(id: i32) -> Option<Self> { Direction::HORIZONTAL.get(usize::try_from(id).ok()?).copied() }
+
+    pub fn id(&self) -> i32 { Direction::ALL.iter().position(|d| d == self).unwrap() as i32 }
+
+    /// `None` for up and down.
+    pub fn horizontal_id(&self) -> Option<i32> { Direction::HORIZONTAL.iter().position(|d| d == self).map(|id| id as i32) }
+
+    pub fn offset(&self) -> (i32, i32, i32) {
+        match self {
+            Direction::Down => (0, -1, 0),
+            Direction::Up => (0, 1, 0),
+            Direction::North => (0, 0, -1),
+            Direction::South => (0, 0, 1),
+            Direction::West => (-1, 0, 0),
+            Direction::East => (1, 0, 0),
+        }
+    }
+
+    /// Rotates a horizontal direction counter clockwise.
+    pub fn counter_clockwise(&self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+            vertical => *vertical,
+        }
+    }
+}
+
+/// Head, body, left arm, right arm, left leg and right leg
+/// rotations of an armor stand in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArmorStandPose {
+    pub head: [f32; 3],
+    pub body: [f32; 3],
+    pub left_arm: [f32; 3],
+    pub right_arm: [f32; 3],
+    pub left_leg: [f32; 3],
+    pub right_leg: [f32; 3],
+}
+
+impl Default for ArmorStandPose {
+    fn default() -> Self {
+        ArmorStandPose {
+            head: [0.0; 3],
+            body: [0.0; 3],
+            left_arm: [-10.0, 0.0, -10.0],
+            right_arm: [-15.0, 0.0, 10.0],
+            left_leg: [-1.0, 0.0, -1.0],
+            right_leg: [1.0, 0.0, 1.0],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Item {
+    pub id: String,
+    pub count: i8,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EntityKind {
+    ArmorStand {
+        small: bool,
+        show_arms: bool,
+        no_base_plate: bool,
+        marker: bool,
+        pose: ArmorStandPose,
+    },
+    ItemFrame {
+        hanging_at: BlockPosition,
+        facing: Direction,
+        item: Option<Item>,
+        /// Eighths of a full turn.
+        item_rotation: u8,
+    },
+    Painting {
+        hanging_at: BlockPosition,
+        facing: Direction,
+        /// Index into [`PAINTING_MOTIVES`].
+        motive: u8,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct Entity {
+    kind: EntityKind,
+    uuid: Option<Uuid>,
+    position: Position,
+    look: LookAngles,
+    /// A JSON text component.
+    custom_name: Option<String>,
+    custom_name_visible: bool,
+    invisible: bool,
+    glowing: bool,
+    no_gravity: bool,
+    /// The data the entity was read from, written back by
+    /// [`to_schematic`](Self::to_schematic).
+    nbt: HashMap<String, Value>,
+}
+
+impl Entity {
+    /// Reads an entity of a schematic, these store their position relative
+    /// to the schematic in `Pos` and version 3 schematics nest the data in
+    /// `Data`. Returns `None` for entities that aren't supported.
+    pub fn from_schematic(nbt: Value) -> Option<Self> {
+        let mut data = match nbt {
+            Value::Compound(data) => data,
+            _ => return None,
+        };
+        if let Some(Value::Compound(nested)) = data.remove("Data") {
+            data.extend(nested);
+        }
+        let position = match data.get("Pos") {
+            Some(Value::List(pos)) if pos.len() == 3 => Position::new(double(&pos[0])?, double(&pos[1])?, double(&pos[2])?),
+            _ => return None,
+        };
+        let id = match data.get("Id").or_else(|| data.get("id")) {
+            Some(Value::String(id)) => id.strip_prefix("minecraft:").unwrap_or(id).to_owned(),
+            _ => return None,
+        };
+        let kind = match id.as_str() {
+            "armor_stand" => EntityKind::ArmorStand {
+                small: flag(&data, "Small"),
+                show_arms: flag(&data, "ShowArms"),
+                no_base_plate: flag(&data, "NoBasePlate"),
+                marker: flag(&data, "Marker"),
+                pose: armor_stand_pose(&data),
+            },
+            "item_frame" | "glow_item_frame" => {
+                let facing = Direction::from_id(int(data.get("Facing")?)?)?;
+                EntityKind::ItemFrame {
+                    hanging_at: hanging_at(position, facing, 0.0, 0.0),
+                    facing,
+                    item: item(&data),
+                    item_rotation: data.get("ItemRotation").and_then(int).unwrap_or(0) as u8 & 7,
+                }
+            },
+            "painting" => {
+                let facing = match data.get("Facing").or_else(|| data.get("facing")) {
+                    Some(facing) => Direction::from_horizontal_id(int(facing)?)?,
+                    None => return None,
+                };
+                let motive = match data.get("Motive").or_else(|| data.get("variant")) {
+                    Some(Value::String(motive)) => motive.strip_prefix("minecraft:").unwrap_or(motive).to_owned(),
+                    _ => return None,
+                };
+                let index = PAINTING_MOTIVES.iter().position(|(name, _, _)| *name == motive)?;
+                let (_, width, height) = PAINTING_MOTIVES[index];
+                EntityKind::Painting {
+                    hanging_at: hanging_at(position, facing, centre_offset(width), centre_offset(height)),
+                    facing,
+                    motive: index as u8,
+                }
+            },
+            _ => return None,
+        };
+        let look = match data.get("Rotation") {
+            Some(Value::List(rotation)) if rotation.len() == 2 => LookAngles::new(double(&rotation[0])? as f32, double(&rotation[1])? as f32),
+            _ => LookAngles::default(),
+        };
+        let custom_name = match data.get("CustomName") {
+            Some(Value::String(name)) if !name.is_empty() => Some(name.clone()),
+            _ => None,
+        };
+        Some(Entity {
+            kind,
+            uuid: uuid(&data),
+            position,
+            look,
+            custom_name,
+            custom_name_visible: flag(&data, "CustomNameVisible"),
+            invisible: flag(&data, "Invisible"),
+            glowing: flag(&data, "Glowing"),
+            no_gravity: flag(&data, "NoGravity"),
+            nbt: data,
+        })
+    }
+
+    /// The entity the way version 2 schematics store it, the data it was
+    /// read from with its current position in `Pos`.
+    pub fn to_schematic(&self) -> Value {
+        let mut nbt = self.nbt.clone();
+        if let Some(id) = nbt.remove("id") {
+            nbt.entry(String::from("Id")).or_insert(id);
+        }
+        let Position { x, y, z } = self.position;
+        nbt.insert(String::from("Pos"), Value::List(vec![Value::Double(x), Value::Double(y), Value::Double(z)]));
+        Value::Compound(nbt)
+    }
+
+    /// Moves the entity by whole blocks.
+    pub fn translate(&mut self, x: i32, y: i32, z: i32) {
+        self.position = Position::new(self.position.x + x as f64, self.position.y + y as f64, self.position.z + z as f64);
+        match &mut self.kind {
+            EntityKind::ItemFrame { hanging_at, .. } | EntityKind::Painting { hanging_at, .. } => {
+                *hanging_at = BlockPosition::new(hanging_at.get_x() + x, hanging_at.get_y() + y, hanging_at.get_z() + z);
+            },
+            EntityKind::ArmorStand { .. } => {},
+        }
+    }
+
+    /// The block this entity is in, hanging entities are in the block
+    /// they hang at.
+    pub fn block_position(&self) -> BlockPosition {
+        match &self.kind {
+            EntityKind::ItemFrame { hanging_at, .. } | EntityKind::Painting { hanging_at, .. } => *hanging_at,
+            EntityKind::ArmorStand { .. } => BlockPosition::from(self.position),
+        }
+    }
+
+    pub fn kind(&self) -> &EntityKind { &self.kind }
+
+    /// `None` if the schematic didn't store one.
+    pub fn uuid(&self) -> Option<Uuid> { self.uuid }
+
+    pub fn position(&self) -> Position { self.position }
+
+    pub fn look(&self) -> LookAngles { self.look }
+
+    pub fn custom_name(&self) -> Option<&str> { self.custom_name.as_deref() }
+
+    pub fn custom_name_visible(&self) -> bool { self.custom_name_visible }
+
+    pub fn invisible(&self) -> bool { self.invisible }
+
+    pub fn glowing(&self) -> bool { self.glowing }
+
+    pub fn no_gravity(&self) -> bool { self.no_gravity }
+}
+
+fn double(value: &Value) -> Option<f64> {
+    match value {
+        Value::Double(v) => Some(*v),
+        Value::Float(v) => Some(*v as f64),
+        _ => None,
+    }
+}
+
+fn int(value: &Value) -> Option<i32> {
+    match value {
+        Value::Byte(v) => Some(*v as i32),
+        Value::Short(v) => Some(*v as i32),
+        Value::Int(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn flag(data: &HashMap<String, Value>, key: &str) -> bool { data.get(key).and_then(int).is_some_and(|v| v != 0) }
+
+/// `UUID` int array since 1.16, `UUIDMost` and `UUIDLeast` before.
+fn uuid(data: &HashMap<String, Value>) -> Option<Uuid> {
+    match (data.get("UUID"), data.get("UUIDMost"), data.get("UUIDLeast")) {
+        (Some(Value::IntArray(ints)), _, _) if ints.len() == 4 => Some(Uuid::from_u128(ints.iter().fold(0u128, |id, part| id << 32 | *part as u32 as u128))),
+        (_, Some(Value::Long(most)), Some(Value::Long(least))) => Some(Uuid::from_u64_pair(*most as u64, *least as u64)),
+        _ => None,
+    }
+}
+
+/// `Count` until 1.20.5, `count` after.
+fn item(data: &HashMap<String, Value>) -> Option<Item> {
+    let item = match data.get("Item") {
+        Some(Value::Compound(item)) => item,
+        _ => return None,
+    };
+    let id = match item.get("id") {
+        Some(Value::String(id)) if id != "minecraft:air" => id.clone(),
+        _ => return None,
+    };
+    let count = item.get("Count").or_else(|| item.get("count")).and_then(int).unwrap_or(1);
+    Some(Item {
+        id,
+        count: count as i8,
+    })
+}
+
+fn armor_stand_pose(data: &HashMap<String, Value>) -> ArmorStandPose {
+    let mut pose = ArmorStandPose::default();
+    if let Some(Value::Compound(parts)) = data.get("Pose") {
+        for (key, rotation) in [
+            ("Head", &mut pose.head),
+            ("Body", &mut pose.body),
+            ("LeftArm", &mut pose.left_arm),
+            ("RightArm", &mut pose.right_arm),
+            ("LeftLeg", &mut pose.left_leg),
+            ("RightLeg", &mut pose.right_leg),
+        ] {
+            if let Some(Value::List(values)) = parts.get(key) {
+                let values: Vec<f32> = values.iter().filter_map(double).map(|v| v as f32).collect();
+                if let Ok(values) = values.try_into() {
+                    *rotation = values;
+                }
+            }
+        }
+    }
+    pose
+}
+
+/// Paintings with an even size are centred on the edge between two blocks.
+fn centre_offset(blocks: u8) -> f64 {
+    if blocks.is_multiple_of(2) {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+/// The block a hanging entity hangs at, the reverse of how the
+/// game positions it.
+fn hanging_at(position: Position, facing: Direction, width_offset: f64, height_offset: f64) -> BlockPosition {
+    let (fx, fy, fz) = facing.offset();
+    let (sx, _, sz) = facing.counter_clockwise().offset();
+    let x = position.x - 0.5 + fx as f64 * HANGING_OFFSET - sx as f64 * width_offset;
+    let y = position.y - 0.5 + fy as f64 * HANGING_OFFSET - height_offset;
+    let z = position.z - 0.5 + fz as f64 * HANGING_OFFSET - sz as f64 * width_offset;
+    BlockPosition::new(x.round() as i32, y.round() as i32, z.round() as i32)
+}
+
+#[cfg(test)]
+mod test {
+    use fastnbt::Value;
+
+    use super::{Direction, Entity, EntityKind, PAINTING_MOTIVES};
+
+    fn compound(entries: Vec<(&str, Value)>) -> Value { Value::Compound(entries.into_iter().map(|(key, value)| (String::from(key), value)).collect()) }
+
+    fn pos(x: f64, y: f64, z: f64) -> Value { Value::List(vec![Value::Double(x), Value::Double(y), Value::Double(z)]) }
+
+    #[test]
+    pub fn test_entities() {
+        let stand = compound(vec![
+            ("Id", Value::String(String::from("minecraft:armor_stand"))),
+            ("Pos", pos(1.5, 2.0, 3.5)),
+            (
+                "Data",
+                compound(vec![
+                    ("CustomName", Value::String(String::from("{\"text\":\"Lobby\"}"))),
+                    ("CustomNameVisible", Value::Byte(1)),
+                    ("Invisible", Value::Byte(1)),
+                    ("Marker", Value::Byte(1)),
+                    ("Pose", compound(vec![("Head", Value::List(vec![Value::Float(10.0), Value::Float(20.0), Value::Float(30.0)]))])),
+                ]),
+            ),
+        ]);
+        let stand = Entity::from_schematic(stand).unwrap();
+        assert_eq!(stand.custom_name(), Some("{\"text\":\"Lobby\"}"));
+        assert!(stand.custom_name_visible() && stand.invisible());
+        match stand.kind() {
+            EntityKind::ArmorStand {
+                marker,
+                pose,
+                small,
+                ..
+            } => {
+                assert!(*marker && !*small);
+                assert_eq!(pose.head, [10.0, 20.0, 30.0]);
+                assert_eq!(pose.right_leg, [1.0, 0.0, 1.0]);
+            },
+            kind => panic!("expected an armor stand, got {:?}", kind),
+        }
+        assert_eq!(stand.block_position().get_z(), 3);
+
+        // hangs at (4, 5, 6) on the north side of a block
+        let frame = compound(vec![
+            ("Id", Value::String(String::from("minecraft:item_frame"))),
+            ("Pos", pos(4.5, 5.5, 6.96875)),
+            ("Facing", Value::Byte(2)),
+            ("Item", compound(vec![("id", Value::String(String::from("minecraft:diamond"))), ("Count", Value::Byte(1))])),
+        ]);
+        let mut frame = Entity::from_schematic(frame).unwrap();
+        frame.translate(-10, 0, 0);
+        match frame.kind() {
+            EntityKind::ItemFrame {
+                hanging_at,
+                facing,
+                item,
+                ..
+            } => {
+                assert_eq!((hanging_at.get_x(), hanging_at.get_y(), hanging_at.get_z()), (-6, 5, 6));
+                assert_eq!(*facing, Direction::North);
+                assert_eq!(item.as_ref().unwrap().id, "minecraft:diamond");
+            },
+            kind => panic!("expected an item frame, got {:?}", kind),
+        }
+
+        // a 2x2 painting facing east whose bottom left block is (0, 64, 0)
+        let painting = compound(vec![
+            ("id", Value::String(String::from("minecraft:painting"))),
+            ("Pos", pos(0.03125, 65.0, 0.0)),
+            ("Facing", Value::Byte(3)),
+            ("Motive", Value::String(String::from("minecraft:match"))),
+        ]);
+        match Entity::from_schematic(painting).unwrap().kind() {
+            EntityKind::Painting {
+                hanging_at, motive, ..
+            } => {
+                assert_eq!(PAINTING_MOTIVES[*motive as usize].0, "match");
+                assert_eq!((hanging_at.get_x(), hanging_at.get_y(), hanging_at.get_z()), (0, 64, 0));
+            },
+            kind => panic!("expected a painting, got {:?}", kind),
+        }
+
+        let zombie = compound(vec![("Id", Value::String(String::from("minecraft:zombie"))), ("Pos", pos(0.0, 0.0, 0.0))]);
+        assert!(Entity::from_schematic(zombie).is_none());
+    }
+}
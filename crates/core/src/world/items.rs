@@ -0,0 +1,119 @@
+//! Which block an item places. Item ids change between versions, so they
+//! are read from the `reports/registries.json` the vanilla data generator
+//! writes for every version.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use ahash::AHashMap;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::error::Result;
+use crate::world::blocks::Blocks;
+
+/// The blocks placed by the items of one version.
+#[derive(Debug, Default)]
+pub struct ItemRegistry {
+    blocks: AHashMap<i32, Blocks>,
+}
+
+impl ItemRegistry {
+    /// Reads the `minecraft:item` registry of a registries report. Items
+    /// place the default state of the block with the same name, items
+    /// without such a block don't place anything.
+    pub fn from_report(json: &str) -> Result<Self> {
+        let report: RawReport = serde_json::from_str(json)?;
+        let blocks = report
+            .item
+            .entries
+            .into_iter()
+            .filter_map(|(name, entry)| Some((entry.protocol_id, Blocks::from_str(&name).ok()?)))
+            .filter(|(_, block)| *block != Blocks::Air)
+            .collect();
+        Ok(ItemRegistry { blocks })
+    }
+
+    pub fn block(&self, item: i32) -> Option<Blocks> { self.blocks.get(&item).copied() }
+}
+
+/// The item registries of every version that has a report.
+#[derive(Debug, Default)]
+pub struct ItemRegistries {
+    registries: AHashMap<i32, ItemRegistry>,
+}
+
+impl ItemRegistries {
+    /// Loads the reports in `dir`, which are named after their protocol
+    /// version like `736.json`. Other files are ignored.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let mut registries = AHashMap::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let protocol = match (path.extension(), path.file_stem().and_then(|stem| stem.to_str())) {
+                (Some(extension), Some(stem)) if extension == "json" => match i32::from_str(stem) {
+                    Ok(protocol) => protocol,
+                    Err(_) => continue,
+                },
+                _ => continue,
+            };
+            let registry = ItemRegistry::from_report(&std::fs::read_to_string(&path)?)?;
+            debug!(protocol, items = registry.blocks.len(), "Loaded item registry");
+            registries.insert(protocol, registry);
+        }
+        Ok(ItemRegistries { registries })
+    }
+
+    pub fn insert(&mut self, protocol: i32, registry: ItemRegistry) { self.registries.insert(protocol, registry); }
+
+    /// The block an item places for players on `protocol`, `None` for
+    /// versions without a report.
+    pub fn block(&self, protocol: i32, item: i32) -> Option<Blocks> { self.registries.get(&protocol)?.block(item) }
+}
+
+#[derive(Deserialize)]
+struct RawReport {
+    #[serde(rename = "minecraft:item")]
+    item: RawRegistry,
+}
+
+#[derive(Deserialize)]
+struct RawRegistry {
+    entries: HashMap<String, RawEntry>,
+}
+
+#[derive(Deserialize)]
+struct RawEntry {
+    protocol_id: i32,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ItemRegistries, ItemRegistry};
+    use crate::world::blocks::Blocks;
+
+    #[test]
+    pub fn test_item_registry() {
+        let report = r#"{
+            "minecraft:block": {"entries": {"minecraft:stone": {"protocol_id": 1}}},
+            "minecraft:item": {
+                "default": "minecraft:air",
+                "protocol_id": 6,
+                "entries": {
+                    "minecraft:air": {"protocol_id": 0},
+                    "minecraft:stone": {"protocol_id": 1},
+                    "minecraft:oak_log": {"protocol_id": 35},
+                    "minecraft:diamond": {"protocol_id": 579}
+                }
+            }
+        }"#;
+        let mut registries = ItemRegistries::default();
+        registries.insert(736, ItemRegistry::from_report(report).unwrap());
+        assert_eq!(registries.block(736, 1), Some(Blocks::Stone));
+        assert_eq!(registries.block(736, 35), Some(Blocks::OakLog(Default::default())));
+        assert_eq!(registries.block(736, 0), None);
+        assert_eq!(registries.block(736, 579), None);
+        assert_eq!(registries.block(578, 1), None);
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::VecDeque;
+
+use crate::world::block_util::{light_emission, light_opacity};
+use crate::world::chunks::{Chunk, SECTION_HEIGHT, SECTION_LENGTH, SECTION_WIDTH};
+
+pub const MAX_LIGHT_LEVEL: u8 = 15;
+/// Bytes of a section light array, every byte holds two light levels.
+pub const LIGHT_ARRAY_LEN: usize = SECTION_VOLUME / 2;
+
+const SECTION_VOLUME: usize = (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH) as usize;
+const LAYER_AREA: usize = (SECTION_WIDTH * SECTION_LENGTH) as usize;
+/// Light reaches this many blocks from where it has the full level, so
+/// this much of the neighbouring chunks is part of the computation.
+const MARGIN: usize = MAX_LIGHT_LEVEL as usize - 1;
+const GRID_WIDTH: usize = SECTION_WIDTH as usize + 2 * MARGIN;
+const GRID_AREA: usize = GRID_WIDTH * GRID_WIDTH;
+
+/// The block and sky light of a section in the order of
+/// [`ChunkSection::calculate_index`](crate::world::chunks::ChunkSection::calculate_index),
+/// the even index of a byte is stored in the low bits.
+#[derive(Clone, Debug)]
+pub struct SectionLight {
+    block: Vec<u8>,
+    sky: Option<Vec<u8>>,
+}
+
+impl SectionLight {
+    /// A section without block light, fully lit by the sky if `has_skylight`.
+    pub fn new(has_skylight: bool) -> Self {
+        SectionLight {
+            block: vec![0; LIGHT_ARRAY_LEN],
+            sky: has_skylight.then(|| vec![0xFF; LIGHT_ARRAY_LEN]),
+        }
+    }
+
+    pub fn block_light_at(&self, x: u16, y: u16, z: u16) -> u8 { get_nibble(&self.block, index(x, y, z)) }
+
+    /// Always 0 in dimensions without sky light.
+    pub fn sky_light_at(&self, x: u16, y: u16, z: u16) -> u8 { self.sky.as_ref().map_or(0, |sky| get_nibble(sky, index(x, y, z))) }
+
+    pub fn block_light(&self) -> &[u8] { &self.block }
+
+    /// `None` in dimensions without sky light.
+    pub fn sky_light(&self) -> Option<&[u8]> { self.sky.as_deref() }
+}
+
+/// Computes the light of every section of a chunk, from the lowest one up. Sky
+/// light shines straight down until it hits a block that reduces light, from
+/// there on it spreads like block light, losing at least one level per block.
+///
+/// Light spreads over chunk borders, `neighbour` gives the chunk next to
+/// this one at an offset of -1 to 1 on the x and z axis. Missing
+/// neighbours have no sky light but let light through.
+pub fn compute_light<'a>(chunk: &Chunk, has_skylight: bool, neighbour: impl Fn(i32, i32) -> Option<&'a Chunk>) -> Vec<SectionLight> {
+    let chunk_height = chunk.height() as usize;
+    let volume = GRID_AREA * chunk_height;
+    let mut opacity = vec![0u8; volume];
+    let mut block = vec![0u8; volume];
+    let mut has_sky = vec![false; GRID_AREA];
+    let mut queue = VecDeque::new();
+    for (offset_x, offset_z) in (-1..=1).flat_map(|x| (-1..=1).map(move |z| (x, z))) {
+        let current = match (offset_x, offset_z) {
+            (0, 0) => chunk,
+            _ => match neighbour(offset_x, offset_z) {
+                Some(current) => current,
+                None => continue,
+            },
+        };
+        for (x, z) in (0..GRID_WIDTH).flat_map(|x| (0..GRID_WIDTH).map(move |z| (x, z))) {
+            let (chunk_x, chunk_z) =
+                ((x + SECTION_WIDTH as usize - MARGIN) / SECTION_WIDTH as usize, (z + SECTION_LENGTH as usize - MARGIN) / SECTION_LENGTH as usize);
+            if (chunk_x as i32 - 1, chunk_z as i32 - 1) == (offset_x, offset_z) {
+                has_sky[grid_index(x, 0, z)] = true;
+            }
+        }
+        for (section_index, section) in current
+            .get_chunk_sections()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, section)| section.as_ref().map(|section| (i, section)))
+        {
+            let section_y = current.min_section() + section_index as i32 - chunk.min_section();
+            if section_y < 0 || section_y as usize >= chunk.section_count() {
+                continue;
+            }
+            let properties: Vec<(u8, u8)> = section
+                .get_palette()
+                .items()
+                .iter()
+                .map(|block| (light_opacity(block), light_emission(block)))
+                .collect();
+            for (i, value) in section.get_block_data().iter().enumerate() {
+                let (x, z, y) = (i % SECTION_WIDTH as usize, i / SECTION_WIDTH as usize % SECTION_LENGTH as usize, i / LAYER_AREA);
+                let grid_x = x as i32 + offset_x * SECTION_WIDTH as i32 + MARGIN as i32;
+                let grid_z = z as i32 + offset_z * SECTION_LENGTH as i32 + MARGIN as i32;
+                if !(0..GRID_WIDTH as i32).contains(&grid_x) || !(0..GRID_WIDTH as i32).contains(&grid_z) {
+                    continue;
+                }
+                let index = grid_index(grid_x as usize, section_y as usize * SECTION_HEIGHT as usize + y, grid_z as usize);
+                let (block_opacity, emission) = properties[value as usize];
+                opacity[index] = block_opacity;
+                if emission > 0 {
+                    block[index] = emission;
+                    queue.push_back(index);
+                }
+            }
+        }
+    }
+    spread(&mut block, &opacity, queue, chunk_height);
+
+    let sky = has_skylight.then(|| {
+        let mut sky = vec![0u8; volume];
+        let mut queue = VecDeque::new();
+        for column in (0..GRID_AREA).filter(|column| has_sky[*column]) {
+            let mut level = MAX_LIGHT_LEVEL;
+            for y in (0..chunk_height).rev() {
+                let index = column + y * GRID_AREA;
+                level = level.saturating_sub(opacity[index]);
+                if level == 0 {
+                    break;
+                }
+                sky[index] = level;
+                if level > 1 {
+                    queue.push_back(index);
+                }
+            }
+        }
+        spread(&mut sky, &opacity, queue, chunk_height);
+        sky
+    });
+
+    (0..chunk.section_count())
+        .map(|section_y| SectionLight {
+            block: pack_section(&block, section_y),
+            sky: sky.as_ref().map(|sky| pack_section(sky, section_y)),
+        })
+        .collect()
+}
+
+/// Spreads the light of the queued blocks to their neighbours.
+fn spread(levels: &mut [u8], opacity: &[u8], mut queue: VecDeque<usize>, chunk_height: usize) {
+    while let Some(index) = queue.pop_front() {
+        let level = levels[index];
+        for neighbour in neighbours(index, chunk_height) {
+            let next = level.saturating_sub(opacity[neighbour].max(1));
+            if next > levels[neighbour] {
+                levels[neighbour] = next;
+                queue.push_back(neighbour);
+            }
+        }
+    }
+}
+
+fn neighbours(index: usize, chunk_height: usize) -> impl Iterator<Item = usize> {
+    let (x, z, y) = (index % GRID_WIDTH, index / GRID_WIDTH % GRID_WIDTH, index / GRID_AREA);
+    [
+        (x > 0).then(|| index - 1),
+        (x + 1 < GRID_WIDTH).then(|| index + 1),
+        (z > 0).then(|| index - GRID_WIDTH),
+        (z + 1 < GRID_WIDTH).then(|| index + GRID_WIDTH),
+        (y > 0).then(|| index - GRID_AREA),
+        (y + 1 < chunk_height).then(|| index + GRID_AREA),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Packs the light levels of the chunk in the middle of the grid for a section.
+fn pack_section(levels: &[u8], section_y: usize) -> Vec<u8> {
+    let levels: Vec<u8> = (0..SECTION_VOLUME)
+        .map(|i| {
+            let (x, z, y) = (i % SECTION_WIDTH as usize, i / SECTION_WIDTH as usize % SECTION_LENGTH as usize, i / LAYER_AREA);
+            levels[grid_index(x + MARGIN, section_y * SECTION_HEIGHT as usize + y, z + MARGIN)]
+        })
+        .collect();
+    pack(&levels)
+}
+
+fn grid_index(x: usize, y: usize, z: usize) -> usize { x + z * GRID_WIDTH + y * GRID_AREA }
+
+fn pack(levels: &[u8]) -> Vec<u8> { levels.chunks(2).map(|pair| pair[0] | pair[1] << 4).collect() }
+
+fn index(x: u16, y: u16, z: u16) -> usize { (x + z * SECTION_WIDTH + y * SECTION_WIDTH * SECTION_LENGTH) as usize }
+
+fn get_nibble(data: &[u8], index: usize) -> u8 { (data[index / 2] >> (index % 2 * 4)) & 0xF }
+
+#[cfg(test)]
+mod test {
+    use super::compute_light;
+    use crate::world::blocks::Blocks;
+    use crate::world::chunks::{Chunk, ChunkPos};
+    use crate::world::dimension::Dimension;
+
+    #[test]
+    pub fn test_light() {
+        let mut chunk = Chunk::empty(ChunkPos::new(0, 0), &Dimension::overworld());
+        // a stone roof at y = 20 with a glowstone block below it
+        for x in 0..16 {
+            for z in 0..16 {
+                chunk.set_block_at(x, 20, z, Blocks::Stone);
+            }
+        }
+        chunk.set_block_at(8, 10, 8, Blocks::Glowstone);
+
+        let light = compute_light(&chunk, true, |_, _| None);
+        assert_eq!(light[15].sky_light_at(0, 15, 0), 15);
+        assert_eq!(light[1].sky_light_at(0, 5, 0), 15);
+        assert_eq!(light[1].sky_light_at(0, 4, 0), 0);
+        assert_eq!(light[0].sky_light_at(0, 0, 0), 0);
+        assert_eq!(light[0].block_light_at(8, 10, 8), 15);
+        assert_eq!(light[0].block_light_at(8, 11, 8), 14);
+        assert_eq!(light[0].block_light_at(5, 10, 8), 12);
+        assert_eq!(light[0].block_light_at(0, 0, 0), 0);
+
+        let light = compute_light(&chunk, false, |_, _| None);
+        assert!(light[0].sky_light().is_none());
+        assert_eq!(light[0].block_light_at(8, 10, 8), 15);
+    }
+
+    #[test]
+    pub fn test_light_over_borders() {
+        let dimension = Dimension::overworld();
+        let mut chunk = Chunk::empty(ChunkPos::new(0, 0), &dimension);
+        chunk.set_block_at(15, 10, 8, Blocks::Glowstone);
+        let mut neighbour = Chunk::empty(ChunkPos::new(1, 0), &dimension);
+        neighbour.set_block_at(0, 10, 8, Blocks::Stone);
+
+        let light = compute_light(&neighbour, true, |x, z| (x == -1 && z == 0).then_some(&chunk));
+        assert_eq!(light[0].block_light_at(0, 10, 8), 0);
+        assert_eq!(light[0].block_light_at(1, 10, 8), 11);
+        assert_eq!(light[0].block_light_at(0, 11, 8), 13);
+        assert_eq!(light[0].block_light_at(14, 10, 8), 0);
+
+        let light = compute_light(&neighbour, true, |_, _| None);
+        assert_eq!(light[0].block_light_at(0, 11, 8), 0);
+    }
+}
@@ -1,12 +1,23 @@
+use std::path::Path;
+
 use crate::player::data::Position;
 
+pub mod anvil;
+pub mod biome;
+pub mod bit_storage;
+pub mod block_entity;
+mod block_state;
 pub mod block_util;
 pub mod blocks;
 pub mod chunks;
 pub mod dimension;
+pub mod entity;
+pub mod items;
+pub mod light;
 pub mod palette;
+pub mod upgrade;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct BlockPosition {
     x: i32,
     y: i32,
@@ -32,3 +43,13 @@ impl BlockPosition {
 impl From<Position> for BlockPosition {
     fn from(pos: Position) -> Self { BlockPosition::new(pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32) }
 }
+
+/// Writes to a temporary file next to `path` first and renames it
+/// afterwards, so a crash never leaves a half written file behind.
+pub fn write_atomically<P: AsRef<Path>>(path: P, data: &[u8]) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    std::fs::write(&tmp, data)?;
+    std::fs::rename(&tmp, path)
+}
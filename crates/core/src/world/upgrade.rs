@@ -0,0 +1,208 @@
+//! Block state remapping for worlds and schematics written by other
+//! Minecraft versions than [`REQUIRED_DATA_VERSION`].
+//!
+//! Only renames are handled here, states that still don't exist
+//! after remapping get replaced by a fallback block. Biomes work the
+//! same way with a fallback biome.
+
+use std::borrow::Cow;
+use std::ops::RangeInclusive;
+
+use ahash::AHashMap;
+use tracing::warn;
+
+use crate::schematic::REQUIRED_DATA_VERSION;
+use crate::world::biome::Biome;
+use crate::world::blocks::Blocks;
+
+/// From 1.13 (the flattening) up to 1.21.1.
+pub const SUPPORTED_DATA_VERSIONS: RangeInclusive<i32> = 1519..=3955;
+/// 18w43a, `stone_slab` was renamed to `smooth_stone_slab`.
+const SMOOTH_STONE_SLAB_DATA_VERSION: i32 = 1901;
+
+/// Plain renames, the old names don't exist in [`REQUIRED_DATA_VERSION`].
+const RENAMES: [(&str, &str); 4] = [
+    // 1.14
+    ("sign", "oak_sign"),
+    ("wall_sign", "oak_wall_sign"),
+    // 1.17
+    ("grass_path", "dirt_path"),
+    // 1.20.3
+    ("short_grass", "grass"),
+];
+
+const WALL_SIDES: [&str; 4] = ["north", "east", "south", "west"];
+
+/// Converts block states of a given data version to [`Blocks`],
+/// keeping count of the states it could not convert.
+#[derive(Debug)]
+pub struct BlockStateUpgrader {
+    data_version: i32,
+    fallback: Blocks,
+    fallback_biome: Biome,
+    unknown: AHashMap<String, usize>,
+    unknown_biomes: AHashMap<String, usize>,
+}
+
+impl BlockStateUpgrader {
+    pub fn new(data_version: i32, fallback: Blocks) -> Self {
+        BlockStateUpgrader {
+            data_version,
+            fallback,
+            fallback_biome: Biome::default(),
+            unknown: AHashMap::new(),
+            unknown_biomes: AHashMap::new(),
+        }
+    }
+
+    /// Sets the biome unknown biomes become, plains by default.
+    pub fn with_fallback_biome(mut self, biome: Biome) -> Self {
+        self.fallback_biome = biome;
+        self
+    }
+
+    pub fn fallback_biome(&self) -> Biome { self.fallback_biome }
+
+    pub fn data_version(&self) -> i32 { self.data_version }
+
+    pub fn set_data_version(&mut self, data_version: i32) { self.data_version = data_version; }
+
+    /// Remaps a block state string (`namespace:name[key=value,...]`)
+    /// to its [`REQUIRED_DATA_VERSION`] equivalent.
+    pub fn upgrade<'a>(&self, state: &'a str) -> Cow<'a, str> {
+        if self.data_version == REQUIRED_DATA_VERSION {
+            return Cow::Borrowed(state);
+        }
+        let (namespace, stripped) = state.split_once(':').unwrap_or(("minecraft", state));
+        let (name, properties) = match stripped.split_once('[') {
+            Some((name, properties)) => (name, properties.trim_end_matches(']')),
+            None => (stripped, ""),
+        };
+
+        let mut new_name = RENAMES.iter().find(|(old, _)| *old == name).map_or(name, |(_, new)| new);
+        if name == "stone_slab" && self.data_version < SMOOTH_STONE_SLAB_DATA_VERSION {
+            new_name = "smooth_stone_slab";
+        }
+        let is_wall = name.ends_with("_wall");
+        let mut changed = new_name != name;
+        let properties = properties
+            .split(',')
+            .filter_map(|property| property.split_once('='))
+            .map(|(key, value)| {
+                // 1.16 wall sides have a height instead of a boolean
+                if is_wall && WALL_SIDES.contains(&key) && (value == "true" || value == "false") {
+                    changed = true;
+                    (
+                        key,
+                        if value == "true" {
+                            "low"
+                        } else {
+                            "none"
+                        },
+                    )
+                } else {
+                    (key, value)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if !changed {
+            return Cow::Borrowed(state);
+        }
+        if properties.is_empty() {
+            Cow::Owned(format!("{}:{}", namespace, new_name))
+        } else {
+            let properties: Vec<String> = properties.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+            Cow::Owned(format!("{}:{}[{}]", namespace, new_name, properties.join(",")))
+        }
+    }
+
+    /// Upgrades and parses a block state, unknown states
+    /// are counted and become the fallback block.
+    pub fn parse(&mut self, state: &str) -> Blocks {
+        let upgraded = self.upgrade(state);
+        match Blocks::parse_state(&upgraded) {
+            Ok(block) => block,
+            Err(_) => self.unknown(upgraded.into_owned()),
+        }
+    }
+
+    /// Counts a state that has no equivalent and returns the fallback block.
+    pub fn unknown(&mut self, state: String) -> Blocks {
+        *self.unknown.entry(state).or_default() += 1;
+        self.fallback
+    }
+
+    /// Parses a biome name, unknown biomes are counted
+    /// and become the fallback biome.
+    pub fn parse_biome(&mut self, name: &str) -> Biome {
+        match Biome::from_name(name) {
+            Some(biome) => biome,
+            None => self.unknown_biome(name.to_string()),
+        }
+    }
+
+    /// Looks up a numeric biome id as stored by worlds before 1.18.
+    pub fn biome_from_id(&mut self, id: i32) -> Biome {
+        match Biome::from_id(id) {
+            Some(biome) => biome,
+            None => self.unknown_biome(format!("#{}", id)),
+        }
+    }
+
+    fn unknown_biome(&mut self, biome: String) -> Biome {
+        *self.unknown_biomes.entry(biome).or_default() += 1;
+        self.fallback_biome
+    }
+
+    /// Unknown block states and how many times they were encountered.
+    pub fn unknown_states(&self) -> &AHashMap<String, usize> { &self.unknown }
+
+    pub fn unknown_biomes(&self) -> &AHashMap<String, usize> { &self.unknown_biomes }
+
+    pub fn log_unknown(&self) {
+        if !self.unknown_biomes.is_empty() {
+            for (biome, count) in self.unknown_biomes.iter() {
+                warn!(%biome, count, "Unknown biome");
+            }
+            warn!(biomes = self.unknown_biomes.len(), fallback = %self.fallback_biome, "Replaced unknown biomes with the fallback biome");
+        }
+        if self.unknown.is_empty() {
+            return;
+        }
+        let mut unknown: Vec<_> = self.unknown.iter().collect();
+        unknown.sort_unstable_by(|a, b| b.1.cmp(a.1));
+        for (state, count) in unknown {
+            warn!(%state, count, "Unknown block state");
+        }
+        warn!(states = self.unknown.len(), fallback = ?self.fallback, "Replaced unknown block states with the fallback block");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BlockStateUpgrader;
+    use crate::world::blocks::Blocks;
+
+    #[test]
+    pub fn test_upgrade() {
+        let upgrader = BlockStateUpgrader::new(2230, Blocks::Air);
+        assert_eq!(upgrader.upgrade("minecraft:grass_path"), "minecraft:dirt_path");
+        assert_eq!(
+            upgrader.upgrade("minecraft:cobblestone_wall[east=true,north=false,south=false,up=true,waterlogged=false,west=true]"),
+            "minecraft:cobblestone_wall[east=low,north=none,south=none,up=true,waterlogged=false,west=low]"
+        );
+        assert_eq!(upgrader.upgrade("minecraft:stone"), "minecraft:stone");
+        assert_eq!(upgrader.upgrade("minecraft:stone_slab[type=top]"), "minecraft:stone_slab[type=top]");
+        assert_eq!(BlockStateUpgrader::new(1631, Blocks::Air).upgrade("stone_slab[type=top]"), "minecraft:smooth_stone_slab[type=top]");
+    }
+
+    #[test]
+    pub fn test_fallback() {
+        let mut upgrader = BlockStateUpgrader::new(3120, Blocks::Stone);
+        assert_eq!(upgrader.parse("minecraft:short_grass"), Blocks::Grass);
+        assert_eq!(upgrader.parse("minecraft:mud"), Blocks::Stone);
+        assert_eq!(upgrader.parse("minecraft:mud"), Blocks::Stone);
+        assert_eq!(upgrader.unknown_states().get("minecraft:mud"), Some(&2));
+    }
+}
@@ -0,0 +1,89 @@
+use ahash::AHashMap;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use falcon_core::schematic::SchematicData;
+use falcon_core::world::biome::Biome;
+use falcon_core::world::blocks::Blocks;
+use falcon_core::world::chunks::{ChunkSection, SECTION_HEIGHT, SECTION_LENGTH, SECTION_WIDTH};
+use falcon_logic::FalconWorld;
+use fastnbt::borrow::ByteArray;
+
+const PALETTE: [Blocks; 8] =
+    [Blocks::Air, Blocks::Stone, Blocks::Granite, Blocks::Diorite, Blocks::Andesite, Blocks::Dirt, Blocks::Cobblestone, Blocks::Bedrock];
+
+/// Air and 120 stair states, like the many states of a detailed build.
+fn stairs_palette() -> Vec<Blocks> {
+    let mut palette = vec![Blocks::Air];
+    for material in ["oak", "stone_brick", "quartz"] {
+        for facing in ["north", "south", "west", "east"] {
+            for half in ["top", "bottom"] {
+                for shape in ["straight", "inner_left", "inner_right", "outer_left", "outer_right"] {
+                    let state = format!("minecraft:{}_stairs[facing={},half={},shape={},waterlogged=false]", material, facing, half, shape);
+                    palette.push(state.parse().unwrap());
+                }
+            }
+        }
+    }
+    palette
+}
+
+/// A schematic where the lower half uses every palette entry and the upper half
+/// is air.
+fn schematic_data(width: usize, height: usize, length: usize, palette_len: usize) -> Vec<i8> {
+    let mut data = Vec::with_capacity(width * height * length);
+    for y in 0..height {
+        for z in 0..length {
+            for x in 0..width {
+                let block = if y < height / 2 {
+                    1 + (x * 7 + y * 3 + z) % (palette_len - 1)
+                } else {
+                    0
+                };
+                data.push(block as i8);
+            }
+        }
+    }
+    data
+}
+
+fn world_loading(c: &mut Criterion) {
+    let (width, height, length) = (128, 128, 128);
+    for (name, palette) in [("from_schematic 128x128x128", PALETTE.to_vec()), ("from_schematic 128x128x128 stairs", stairs_palette())] {
+        let data = schematic_data(width, height, length, palette.len());
+        let palette: AHashMap<i32, Blocks> = palette.iter().enumerate().map(|(i, block)| (i as i32, *block)).collect();
+        c.bench_function(name, |b| {
+            b.iter(|| {
+                let schematic = SchematicData::new(width as u16, height as u16, length as u16, [0, 0, 0], palette.clone(), ByteArray::new(&data));
+                black_box(FalconWorld::from_schematic(schematic, [0, 0, 0], Biome::default()).unwrap())
+            })
+        });
+    }
+}
+
+fn section_edits(c: &mut Criterion) {
+    c.bench_function("fill and clear a section", |b| {
+        b.iter_batched(
+            ChunkSection::empty,
+            |mut section| {
+                for y in 0..SECTION_HEIGHT {
+                    for z in 0..SECTION_LENGTH {
+                        for x in 0..SECTION_WIDTH {
+                            section.set_block_at(x, y, z, PALETTE[1 + (x + y + z) as usize % (PALETTE.len() - 1)]);
+                        }
+                    }
+                }
+                for y in 0..SECTION_HEIGHT {
+                    for z in 0..SECTION_LENGTH {
+                        for x in 0..SECTION_WIDTH {
+                            section.set_block_at(x, y, z, Blocks::Air);
+                        }
+                    }
+                }
+                section
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, world_loading, section_edits);
+criterion_main!(benches);
@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use falcon_core::server::config::ConnectionSettings;
+use falcon_packet_core::WriteError;
+use tokio::time::Instant;
+
+use super::writer::SocketWrite;
+
+pub type DeferredWrite = Box<dyn FnOnce(&mut SocketWrite, i32) -> Result<(), WriteError> + Send + Sync>;
+
+/// Keeps track of how far a connection is lagging behind.
+///
+/// Both the amount of queued [`ConnectionTask`](super::ConnectionTask)s and
+/// the amount of unsent bytes are compared against the limits in
+/// [`ConnectionSettings`](falcon_core::server::config::ConnectionSettings).
+/// Non-essential packets are held back while the connection is congested.
+pub struct Backpressure {
+    settings: &'static ConnectionSettings,
+    queued_tasks: Arc<AtomicUsize>,
+    deferred: VecDeque<DeferredWrite>,
+    congested_since: Option<Instant>,
+}
+
+impl Backpressure {
+    pub fn new(queued_tasks: Arc<AtomicUsize>, settings: &'static ConnectionSettings) -> Self {
+        Backpressure {
+            settings,
+            queued_tasks,
+            deferred: VecDeque::new(),
+            congested_since: None,
+        }
+    }
+
+    pub fn queued_tasks(&self) -> usize { self.queued_tasks.load(Ordering::Relaxed) }
+
+    pub fn task_received(&self) { self.queued_tasks.fetch_sub(1, Ordering::Relaxed); }
+
+    pub fn deferred_len(&self) -> usize { self.deferred.len() }
+
+    pub fn is_congested(&self, pending_bytes: usize) -> bool {
+        pending_bytes > self.settings.max_pending_bytes || self.queued_tasks() > self.settings.max_queued_tasks
+    }
+
+    /// Packets get deferred when the connection is congested or
+    /// when earlier packets are still waiting, to preserve ordering.
+    pub fn should_defer(&self, pending_bytes: usize) -> bool { !self.deferred.is_empty() || self.is_congested(pending_bytes) }
+
+    /// Holds back a packet, returns `false` without doing so when
+    /// [`max_deferred_packets`](ConnectionSettings::max_deferred_packets)
+    /// are held back already.
+    #[must_use]
+    pub fn defer(&mut self, write: DeferredWrite) -> bool {
+        if self.deferred.len() >= self.settings.max_deferred_packets {
+            return false;
+        }
+        self.deferred.push_back(write);
+        true
+    }
+
+    pub fn clear_deferred(&mut self) { self.deferred.clear(); }
+
+    /// Writes deferred packets to the buffer for as long as the connection
+    /// is not congested.
+    pub fn flush(&mut self, buffer: &mut SocketWrite, protocol: i32) -> Result<(), WriteError> {
+        while !self.is_congested(buffer.pending_len()) {
+            match self.deferred.pop_front() {
+                Some(write) => {
+                    write(buffer, protocol)?;
+                    buffer.finish();
+                },
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Updates the congestion state and returns how long the connection
+    /// has been congested for if this exceeds the configured timeout.
+    pub fn update(&mut self, pending_bytes: usize) -> Option<Duration> {
+        if !self.is_congested(pending_bytes) {
+            self.congested_since = None;
+            return None;
+        }
+        let since = *self.congested_since.get_or_insert_with(Instant::now);
+        let elapsed = since.elapsed();
+        if elapsed >= Duration::from_secs(self.settings.slow_client_timeout) {
+            Some(elapsed)
+        } else {
+            None
+        }
+    }
+}
+
+impl Debug for Backpressure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Backpressure")
+            .field("queued_tasks", &self.queued_tasks())
+            .field("deferred", &self.deferred.len())
+            .field("congested_since", &self.congested_since)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use bytes::{Buf, BufMut};
+    use falcon_core::server::config::ConnectionSettings;
+    use falcon_packet_core::special::PacketPrepare;
+
+    use super::{Backpressure, DeferredWrite};
+    use crate::connection::writer::SocketWrite;
+
+    fn packet() -> DeferredWrite {
+        Box::new(|buffer, _| {
+            buffer.prepare(59);
+            buffer.put_bytes(1, 59);
+            Ok(())
+        })
+    }
+
+    #[test]
+    pub fn test_defer_and_flush() {
+        let settings = Box::leak(Box::new(ConnectionSettings {
+            max_pending_bytes: 100,
+            max_queued_tasks: 2,
+            max_deferred_packets: 3,
+            ..Default::default()
+        }));
+        let queued_tasks = Arc::new(AtomicUsize::new(0));
+        let mut backpressure = Backpressure::new(queued_tasks.clone(), settings);
+        assert!(!backpressure.should_defer(0));
+        assert!(backpressure.should_defer(101));
+        queued_tasks.store(3, Ordering::Relaxed);
+        assert!(backpressure.should_defer(0));
+        queued_tasks.store(0, Ordering::Relaxed);
+
+        assert!(backpressure.defer(packet()));
+        assert!(backpressure.defer(packet()));
+        assert!(backpressure.defer(packet()));
+        assert!(!backpressure.defer(packet()), "the queue is full");
+        assert!(backpressure.should_defer(0), "later packets wait for the deferred ones");
+
+        // two packets fit before the buffer is congested
+        let mut buffer = SocketWrite::new(-1);
+        backpressure.flush(&mut buffer, 0).unwrap();
+        assert_eq!(backpressure.deferred_len(), 1);
+        assert_eq!(buffer.pending_len(), 120);
+        backpressure.flush(&mut buffer, 0).unwrap();
+        assert_eq!(backpressure.deferred_len(), 1);
+
+        buffer.advance(buffer.pending_len());
+        backpressure.flush(&mut buffer, 0).unwrap();
+        assert_eq!(backpressure.deferred_len(), 0);
+        assert!(!backpressure.should_defer(buffer.pending_len()));
+    }
+}
@@ -0,0 +1,228 @@
+//! Packet capture files.
+//!
+//! A capture starts with a small header followed by one record per decoded
+//! frame (uncompressed packet id + packet data), all numbers are big-endian:
+//!
+//! | Field       | Type   | Notes                                  |
+//! |-------------|--------|----------------------------------------|
+//! | time        | u64    | microseconds since the capture started |
+//! | direction   | u8     | 0 = inbound, 1 = outbound              |
+//! | state       | u8     | connection state before handling       |
+//! | protocol    | i32    | protocol id of the connection          |
+//! | packet id   | i32    |                                        |
+//! | frame len   | u32    |                                        |
+//! | frame       | bytes  | packet id VarInt followed by the data  |
+
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use falcon_core::network::{ConnectionState, PacketHandlerState};
+use falcon_packet_core::{PacketRead, VarI32};
+use tokio::time::Instant;
+
+const MAGIC: &[u8; 4] = b"FCAP";
+const VERSION: u8 = 1;
+const RECORD_HEADER_LEN: usize = 8 + 1 + 1 + 4 + 4 + 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Inbound => write!(f, "C->S"),
+            Direction::Outbound => write!(f, "S->C"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CaptureRecord {
+    pub time: Duration,
+    pub direction: Direction,
+    pub state: ConnectionState,
+    pub protocol: i32,
+    pub packet_id: i32,
+    pub frame: Bytes,
+}
+
+impl CaptureRecord {
+    /// The packet data without the packet id.
+    pub fn data(&self) -> Bytes {
+        let mut data = self.frame.clone();
+        VarI32::read(&mut data).ok();
+        data
+    }
+}
+
+/// Writes every frame of a single connection to a capture file.
+#[derive(Debug)]
+pub struct PacketCapture {
+    start: Instant,
+    output: BufWriter<File>,
+}
+
+impl PacketCapture {
+    /// Creates a new capture file for `addr` in the directory `dir`.
+    pub fn create<P: AsRef<Path>>(dir: P, addr: &SocketAddr) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        let unix_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+        let file_name = format!("{}_{}-{}.fcap", addr.ip(), addr.port(), unix_time);
+        let mut output = BufWriter::new(File::create(dir.as_ref().join(file_name))?);
+        output.write_all(MAGIC)?;
+        output.write_all(&[VERSION])?;
+        output.write_all(&unix_time.to_be_bytes())?;
+        Ok(PacketCapture {
+            start: Instant::now(),
+            output,
+        })
+    }
+
+    pub fn record(&mut self, direction: Direction, state: &PacketHandlerState, frame: &Bytes) -> std::io::Result<()> {
+        let packet_id = VarI32::read(&mut frame.clone()).map(|id| id.val()).unwrap_or(-1);
+        let mut header = BytesMut::with_capacity(RECORD_HEADER_LEN);
+        header.put_u64(self.start.elapsed().as_micros() as u64);
+        header.put_u8(direction as u8);
+        header.put_u8(state_to_u8(state.connection_state));
+        header.put_i32(state.protocol_id);
+        header.put_i32(packet_id);
+        header.put_u32(frame.len() as u32);
+        self.output.write_all(&header)?;
+        self.output.write_all(frame)
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> { self.output.flush() }
+}
+
+/// Reads the records of a capture file written by [`PacketCapture`].
+pub struct CaptureReader<R: Read> {
+    input: BufReader<R>,
+    unix_start: u64,
+}
+
+impl<R: Read> CaptureReader<R> {
+    pub fn new(input: R) -> std::io::Result<Self> {
+        let mut input = BufReader::new(input);
+        let mut header = [0u8; 13];
+        input.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(std::io::Error::new(ErrorKind::InvalidData, "Not a packet capture file"));
+        }
+        if header[4] != VERSION {
+            return Err(std::io::Error::new(ErrorKind::InvalidData, format!("Unsupported capture version {}", header[4])));
+        }
+        Ok(CaptureReader {
+            input,
+            unix_start: (&header[5..]).get_u64(),
+        })
+    }
+
+    /// Unix time in milliseconds at which the capture was started.
+    pub fn unix_start(&self) -> u64 { self.unix_start }
+
+    fn read_record(&mut self) -> std::io::Result<Option<CaptureRecord>> {
+        let mut header = [0u8; RECORD_HEADER_LEN];
+        match self.input.read_exact(&mut header) {
+            Ok(_) => {},
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(error),
+        }
+        let mut header = &header[..];
+        let time = Duration::from_micros(header.get_u64());
+        let direction = match header.get_u8() {
+            0 => Direction::Inbound,
+            _ => Direction::Outbound,
+        };
+        let state = state_from_u8(header.get_u8()).ok_or_else(|| std::io::Error::new(ErrorKind::InvalidData, "Invalid connection state"))?;
+        let protocol = header.get_i32();
+        let packet_id = header.get_i32();
+        let mut frame = vec![0u8; header.get_u32() as usize];
+        self.input.read_exact(&mut frame)?;
+        Ok(Some(CaptureRecord {
+            time,
+            direction,
+            state,
+            protocol,
+            packet_id,
+            frame: frame.into(),
+        }))
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = std::io::Result<CaptureRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> { self.read_record().transpose() }
+}
+
+fn state_to_u8(state: ConnectionState) -> u8 {
+    match state {
+        ConnectionState::Handshake => 0,
+        ConnectionState::Status => 1,
+        ConnectionState::Login => 2,
+        ConnectionState::Play => 3,
+        ConnectionState::Disconnected => 4,
+    }
+}
+
+fn state_from_u8(state: u8) -> Option<ConnectionState> {
+    match state {
+        0 => Some(ConnectionState::Handshake),
+        1 => Some(ConnectionState::Status),
+        2 => Some(ConnectionState::Login),
+        3 => Some(ConnectionState::Play),
+        4 => Some(ConnectionState::Disconnected),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+
+    use bytes::Bytes;
+    use falcon_core::network::{ConnectionState, PacketHandlerState};
+
+    use super::{CaptureReader, Direction, PacketCapture};
+
+    #[test]
+    pub fn test_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("falcon_capture_{}", std::process::id()));
+        let mut capture = PacketCapture::create(&dir, &"127.0.0.1:25565".parse().unwrap()).unwrap();
+        let mut state = PacketHandlerState::new(-1);
+        capture
+            .record(Direction::Inbound, &state, &Bytes::from_static(&[0x00, 0x01, 0x02]))
+            .unwrap();
+        state.connection_state = ConnectionState::Play;
+        state.protocol_id = 578;
+        capture
+            .record(Direction::Outbound, &state, &Bytes::from_static(&[0x22, 0xff]))
+            .unwrap();
+        capture.flush().unwrap();
+
+        let file = std::fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+        let records: Vec<_> = CaptureReader::new(File::open(file).unwrap())
+            .unwrap()
+            .collect::<std::io::Result<_>>()
+            .unwrap();
+        std::fs::remove_dir_all(dir).ok();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].direction, Direction::Inbound);
+        assert_eq!(records[0].state, ConnectionState::Handshake);
+        assert_eq!(records[0].packet_id, 0x00);
+        assert_eq!(records[0].data().as_ref(), &[0x01, 0x02]);
+        assert_eq!(records[1].direction, Direction::Outbound);
+        assert_eq!(records[1].state, ConnectionState::Play);
+        assert_eq!(records[1].protocol, 578);
+        assert_eq!(records[1].packet_id, 0x22);
+    }
+}
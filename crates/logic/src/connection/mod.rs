@@ -2,22 +2,30 @@ use std::error::Error;
 use std::future::Future;
 use std::net::SocketAddr;
 use std::pin::Pin;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
 use bytes::Bytes;
 use falcon_core::network::{ConnectionState, PacketHandlerState, UNKNOWN_PROTOCOL};
+use falcon_core::server::config::FalconConfig;
 use falcon_core::ShutdownHandle;
 use falcon_packet_core::WriteError;
-use mc_chat::ChatComponent;
+use mc_chat::{ChatComponent, ComponentStyle};
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 use tokio::time::{interval, Interval, MissedTickBehavior};
-use tracing::{instrument, trace};
+use tracing::{info, instrument, trace, warn};
 pub use wrapper::ConnectionWrapper;
 
+use self::backpressure::Backpressure;
+use self::capture::{Direction, PacketCapture};
 use self::writer::SocketWrite;
+use crate::metrics::DisconnectReason;
 use crate::server::ServerWrapper;
 
+mod backpressure;
+pub mod capture;
 // mod codec;
 pub mod handler;
 pub mod reader;
@@ -49,8 +57,12 @@ pub struct FalconConnection {
     task_rx: UnboundedReceiver<ConnectionTask>,
     wrapper: ConnectionWrapper,
     timeout: Interval,
+    congestion_check: Interval,
     addr: SocketAddr,
     write_buffer: SocketWrite,
+    backpressure: Backpressure,
+    capture: Option<PacketCapture>,
+    disconnect_reason: Option<DisconnectReason>,
     state: PacketHandlerState,
 }
 
@@ -59,15 +71,22 @@ impl FalconConnection {
         let mut timeout = interval(Duration::from_secs(30));
         timeout.set_missed_tick_behavior(MissedTickBehavior::Delay);
         timeout.tick().await;
+        let mut congestion_check = interval(Duration::from_secs(1));
+        congestion_check.set_missed_tick_behavior(MissedTickBehavior::Skip);
         let (sender, receiver) = unbounded_channel();
+        let queued_tasks = Arc::new(AtomicUsize::new(0));
         Self {
             shutdown,
             server,
-            wrapper: ConnectionWrapper::new(sender),
+            wrapper: ConnectionWrapper::new(sender, queued_tasks.clone()),
             task_rx: receiver,
             timeout,
+            congestion_check,
             addr,
             write_buffer: SocketWrite::new(-1),
+            backpressure: Backpressure::new(queued_tasks, &FalconConfig::global().connection),
+            capture: None,
+            disconnect_reason: None,
             state: PacketHandlerState::new(UNKNOWN_PROTOCOL),
         }
     }
@@ -86,6 +105,13 @@ impl FalconConnection {
 
     pub fn state_mut(&mut self) -> &mut falcon_core::network::PacketHandlerState { &mut self.state }
 
+    /// Amount of bytes that are waiting to be sent to the client.
+    pub fn pending_bytes(&self) -> usize { self.write_buffer.pending_len() }
+
+    /// Whether this connection is currently over one of its
+    /// configured high-water marks.
+    pub fn is_congested(&self) -> bool { self.backpressure.is_congested(self.pending_bytes()) }
+
     #[instrument(level = "trace", skip_all)]
     pub fn send<F>(&mut self, write_fn: F) -> Result<(), WriteError>
     where
@@ -96,6 +122,7 @@ impl FalconConnection {
         }
         write_fn(&mut self.write_buffer, self.state.protocol_id)?;
         self.write_buffer.finish();
+        self.record_outbound();
         Ok(())
     }
 
@@ -111,8 +138,93 @@ impl FalconConnection {
         })
     }
 
+    /// Sends a non-essential packet. When the connection is congested the
+    /// packet is held back until the client has caught up again.
+    pub fn send_packet_deferrable<T, F>(&mut self, packet: T, write_fn: F) -> Result<(), WriteError>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(T, &mut SocketWrite, i32) -> Result<bool, WriteError> + Send + Sync + 'static,
+    {
+        if self.state.connection_state == ConnectionState::Disconnected {
+            return Ok(());
+        }
+        if self.backpressure.should_defer(self.pending_bytes()) {
+            trace!(deferred = self.backpressure.deferred_len(), "Deferring packet");
+            let deferred = self
+                .backpressure
+                .defer(Box::new(move |buffer, protocol| write_fn(packet, buffer, protocol).map(|_| ())));
+            if !deferred {
+                info!(
+                    pending_bytes = self.pending_bytes(),
+                    deferred = self.backpressure.deferred_len(),
+                    "Disconnecting slow client, too many held back packets"
+                );
+                let style = ComponentStyle::with_version(self.state.protocol_id.unsigned_abs());
+                self.disconnect_for(DisconnectReason::SlowClient, ChatComponent::from_text("Connection too slow!", style));
+            }
+            return Ok(());
+        }
+        self.send_packet(packet, write_fn)
+    }
+
+    /// Drops held back packets, e.g. chunks of a world the player just left.
+    pub fn discard_deferred(&mut self) { self.backpressure.clear_deferred(); }
+
+    /// Writes held back packets for as long as the connection isn't
+    /// congested.
+    fn flush_deferred(&mut self) -> Result<(), WriteError> {
+        if self.state.connection_state == ConnectionState::Disconnected {
+            return Ok(());
+        }
+        let result = self.backpressure.flush(&mut self.write_buffer, self.state.protocol_id);
+        self.record_outbound();
+        result
+    }
+
+    /// Starts recording every frame of this connection to a
+    /// new capture file in `dir`.
+    pub fn start_capture(&mut self, dir: &str) -> std::io::Result<()> {
+        self.capture = Some(PacketCapture::create(dir, &self.addr)?);
+        self.write_buffer.set_capture(true);
+        Ok(())
+    }
+
+    pub(crate) fn record_inbound(&mut self, frame: &Bytes) {
+        if let Some(capture) = &mut self.capture {
+            if let Err(error) = capture.record(Direction::Inbound, &self.state, frame) {
+                self.stop_capture(error);
+            }
+        }
+    }
+
+    fn record_outbound(&mut self) {
+        if let Some(capture) = &mut self.capture {
+            let result = self
+                .write_buffer
+                .take_captured()
+                .try_for_each(|frame| capture.record(Direction::Outbound, &self.state, &frame));
+            if let Err(error) = result {
+                self.stop_capture(error);
+            }
+        }
+    }
+
+    fn stop_capture(&mut self, error: std::io::Error) {
+        warn!(%error, "Could not write packet capture, disabling capture for this connection");
+        self.capture = None;
+        self.write_buffer.set_capture(false);
+    }
+
+    /// Disconnects the client, recording `cause` as the reason
+    /// in the metrics.
+    pub fn disconnect_for(&mut self, cause: DisconnectReason, reason: ChatComponent) {
+        self.disconnect_reason.get_or_insert(cause);
+        self.disconnect(reason);
+    }
+
     #[instrument(level = "trace", skip_all)]
     pub fn disconnect(&mut self, reason: ChatComponent) {
+        self.disconnect_reason.get_or_insert(DisconnectReason::Kicked);
         match self.state.connection_state {
             ConnectionState::Play => self.send_packet(reason, falcon_send::write_play_disconnect).ok(),
             _ => self.send_packet(reason, falcon_send::write_login_disconnect).ok(),
@@ -3,9 +3,14 @@ use std::ptr;
 
 use bytes::buf::UninitSlice;
 use bytes::{Buf, BufMut, Bytes, BytesMut};
+use falcon_core::error::FalconCoreError;
+use falcon_core::network::{MAX_PACKET_LEN, MAX_UNCOMPRESSED_LEN};
 use flate2::{Decompress, FlushDecompress, Status};
 
 const COMPRESSION_BUF_LEN: usize = 4096;
+/// Packet lengths can only be 3 bytes.
+const MAX_LENGTH_VARINT_LEN: usize = 3;
+const MAX_VARINT_LEN: usize = 5;
 
 #[derive(Debug)]
 pub struct SocketRead {
@@ -13,10 +18,13 @@ pub struct SocketRead {
     decompress: Decompress,
     decompress_pos: usize,
     compression_threshold: i32,
+    max_packet_len: usize,
     output_buf: BytesMut,
     next_is_compressed: bool,
+    next_uncompressed: usize,
     ready_pos: usize,
     next_expected: usize,
+    error: Option<FalconCoreError>,
 }
 
 impl SocketRead {
@@ -26,46 +34,68 @@ impl SocketRead {
             decompress: Decompress::new(true),
             decompress_pos: 0,
             compression_threshold,
+            max_packet_len: MAX_PACKET_LEN,
             output_buf: BytesMut::with_capacity(COMPRESSION_BUF_LEN),
             next_is_compressed: false,
+            next_uncompressed: 0,
             ready_pos: 0,
             next_expected: 0,
+            error: None,
         }
     }
 
-    pub fn next_packet(&mut self) -> Option<Bytes> {
+    /// Sets the largest frame length that is accepted from now on,
+    /// this is capped at [`MAX_PACKET_LEN`].
+    pub fn set_max_packet_len(&mut self, max_packet_len: usize) { self.max_packet_len = max_packet_len.min(MAX_PACKET_LEN); }
+
+    /// Returns the next complete packet, if any.
+    ///
+    /// An error means the client sent invalid data and
+    /// the connection should be closed, after which this
+    /// reader should no longer be used.
+    pub fn next_packet(&mut self) -> Result<Option<Bytes>, FalconCoreError> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
         if self.decompress_pos > 0 {
-            match self.flush_buffer() {
-                Some(n) => self.decompress_pos = n,
-                None => self.decompress_pos = 0,
-            }
+            self.decompress_pos = self.flush_buffer()?;
         }
 
         if self.ready_pos != 0 {
             let len = self.output_buf.get_i32();
             let data = self.output_buf.split_to(len as usize);
             self.ready_pos -= size_of::<i32>() + len as usize;
-            return Some(data.freeze());
+            return Ok(Some(data.freeze()));
         }
-        None
+        Ok(None)
     }
 
-    fn flush_buffer(&mut self) -> Option<usize> {
+    fn flush_buffer(&mut self) -> Result<usize, FalconCoreError> {
         let mut success = true;
         let mut start = 0;
         while start != self.decompress_pos && success {
             if self.next_expected == 0 {
-                if let Some((length, cnt)) = read_varint_size(&self.decompress_buf[start..self.decompress_pos]) {
+                if let Some((length, cnt)) = read_varint_size(&self.decompress_buf[start..self.decompress_pos], MAX_LENGTH_VARINT_LEN)? {
+                    if length as usize > self.max_packet_len {
+                        return Err(FalconCoreError::PacketTooLarge(length as usize, self.max_packet_len));
+                    }
                     start += cnt;
                     if self.compression_threshold >= 0 {
-                        if let Some((uncomp_len, cnt)) = read_varint_size(&self.decompress_buf[start..self.decompress_pos]) {
+                        if let Some((uncomp_len, cnt)) = read_varint_size(&self.decompress_buf[start..self.decompress_pos], MAX_VARINT_LEN)? {
+                            if cnt > length as usize {
+                                return Err(FalconCoreError::InvalidUncompressedSize(uncomp_len));
+                            }
                             self.next_expected = length as usize - cnt;
                             if uncomp_len == 0 {
                                 self.next_is_compressed = false;
-                                self.output_buf.reserve(length as usize + size_of::<i32>());
-                                self.output_buf.put_i32(length);
+                                self.output_buf.reserve(self.next_expected + size_of::<i32>());
+                                self.output_buf.put_i32(self.next_expected as i32);
                             } else {
+                                if uncomp_len < self.compression_threshold || uncomp_len as usize > MAX_UNCOMPRESSED_LEN {
+                                    return Err(FalconCoreError::InvalidUncompressedSize(uncomp_len));
+                                }
                                 self.next_is_compressed = true;
+                                self.next_uncompressed = uncomp_len as usize;
                                 self.output_buf.reserve(uncomp_len as usize + size_of::<i32>());
                                 self.output_buf.put_i32(uncomp_len);
                             }
@@ -90,6 +120,9 @@ impl SocketRead {
             }
             if success && self.next_expected == 0 {
                 if self.next_is_compressed {
+                    if self.decompress.total_out() as usize != self.next_uncompressed {
+                        return Err(FalconCoreError::InvalidUncompressedSize(self.next_uncompressed as i32));
+                    }
                     self.decompress.reset(true);
                 }
                 self.ready_pos = self.output_buf.len();
@@ -116,10 +149,10 @@ impl SocketRead {
                 unsafe { ptr::copy(self.decompress_buf.as_ptr().add(start), self.decompress_buf.as_mut_ptr(), remaining) }
             }
         }
-        Some(remaining)
+        Ok(remaining)
     }
 
-    fn read_next(&mut self, mut start: usize) -> Option<usize> {
+    fn read_next(&mut self, mut start: usize) -> Result<usize, FalconCoreError> {
         let end = self.decompress_pos.min(start + self.next_expected);
         let len = end - start;
         if self.next_is_compressed {
@@ -135,21 +168,25 @@ impl SocketRead {
             self.output_buf.put_slice(buf);
         }
         self.next_expected -= len;
-        Some(len)
+        Ok(len)
     }
 
-    fn read_compressed(&mut self, start: usize, end: usize) -> Option<usize> {
+    fn read_compressed(&mut self, start: usize, end: usize) -> Result<usize, FalconCoreError> {
         loop {
             let input = &self.decompress_buf[start..end];
             let eof = input.is_empty();
             let before_out = self.decompress.total_out();
             let before_in = self.decompress.total_in();
+            // never decompress past the declared size
+            let limit = self.next_uncompressed - before_out as usize;
             let flush = if eof {
                 FlushDecompress::Finish
             } else {
                 FlushDecompress::None
             };
-            let ret = self.decompress.decompress(input, Self::output_mut(&mut self.output_buf), flush);
+            let ret = self
+                .decompress
+                .decompress(input, Self::output_mut(&mut self.output_buf, limit), flush);
             let read = (self.decompress.total_out() - before_out) as usize;
             let consumed = (self.decompress.total_in() - before_in) as usize;
             unsafe {
@@ -157,38 +194,47 @@ impl SocketRead {
             }
 
             match ret {
-                Ok(Status::Ok) | Ok(Status::BufError) if read == 0 && !eof => continue,
-                Ok(Status::Ok) | Ok(Status::BufError) | Ok(Status::StreamEnd) => return Some(consumed),
-                Err(_) => return None,
+                Ok(Status::Ok) | Ok(Status::BufError) if read == 0 && consumed == 0 && !eof => {
+                    if limit == 0 {
+                        return Err(FalconCoreError::InvalidUncompressedSize(self.next_uncompressed as i32));
+                    }
+                    continue;
+                },
+                Ok(Status::Ok) | Ok(Status::BufError) | Ok(Status::StreamEnd) => return Ok(consumed),
+                Err(_) => return Err(FalconCoreError::InvalidCompression),
             }
         }
     }
 
-    fn output_mut(buffer: &mut BytesMut) -> &mut [u8] {
+    fn output_mut(buffer: &mut BytesMut, limit: usize) -> &mut [u8] {
         // TODO: explain unsafe
         unsafe {
             let chunk = buffer.chunk_mut();
             // This probably runs UB risk because of uninitialized memory
             // But because this is only used for writing and
             // not for reading, it seems to be working correctly so far.
-            std::slice::from_raw_parts_mut(chunk.as_mut_ptr(), chunk.len())
+            std::slice::from_raw_parts_mut(chunk.as_mut_ptr(), chunk.len().min(limit))
         }
     }
 }
 
-fn read_varint_size(buf: &[u8]) -> Option<(i32, usize)> {
+/// Reads a VarInt of at most `max_len` bytes, returns `None`
+/// if the buffer does not contain the entire VarInt yet.
+fn read_varint_size(buf: &[u8], max_len: usize) -> Result<Option<(i32, usize)>, FalconCoreError> {
     let mut result = 0i32;
-    // packet lengths can only be 3 bytes
-    for (i, byte) in buf.iter().enumerate().take(3.min(buf.len()) + 1) {
-        if i > 2 {
-            return Some((result, i));
+    for (i, byte) in buf.iter().enumerate() {
+        if i >= max_len {
+            return Err(FalconCoreError::PacketTooLong);
         }
         result |= ((byte & 0x7f) as i32) << (i * 7);
         if byte & 0x80 == 0 {
-            return Some((result, i + 1));
+            return Ok(Some((result, i + 1)));
         }
     }
-    None
+    if buf.len() >= max_len {
+        return Err(FalconCoreError::PacketTooLong);
+    }
+    Ok(None)
 }
 
 // TODO: explain unsafe
@@ -201,8 +247,11 @@ unsafe impl BufMut for SocketRead {
 
         if self.decompress_pos >= COMPRESSION_BUF_LEN {
             match self.flush_buffer() {
-                Some(n) => self.decompress_pos = n,
-                None => self.decompress_pos = 0,
+                Ok(n) => self.decompress_pos = n,
+                Err(error) => {
+                    self.decompress_pos = 0;
+                    self.error = Some(error);
+                },
             }
         }
     }
@@ -216,10 +265,44 @@ unsafe impl BufMut for SocketRead {
 #[cfg(test)]
 mod test {
     use bytes::BufMut;
+    use falcon_core::error::FalconCoreError;
     use itertools::Itertools;
 
     use super::SocketRead;
 
+    #[test]
+    pub fn test_frame_too_long() {
+        let mut reader = SocketRead::new(-1);
+        reader.put_slice(&[0xff, 0xff, 0xff, 0x01]);
+        assert!(matches!(reader.next_packet(), Err(FalconCoreError::PacketTooLong)));
+    }
+
+    #[test]
+    pub fn test_state_limit() {
+        let mut reader = SocketRead::new(-1);
+        reader.set_max_packet_len(16);
+        reader.put_slice(&[0x10, 0x00]);
+        assert!(reader.next_packet().unwrap().is_none());
+
+        let mut reader = SocketRead::new(-1);
+        reader.set_max_packet_len(16);
+        reader.put_slice(&[0x11, 0x00]);
+        assert!(matches!(reader.next_packet(), Err(FalconCoreError::PacketTooLarge(17, 16))));
+    }
+
+    #[test]
+    pub fn test_uncompressed_size() {
+        let mut reader = SocketRead::new(256);
+        // declared size of 220 is below the threshold
+        reader.put_slice(&[0x8e, 0x00, 0xdc, 0x01]);
+        assert!(matches!(reader.next_packet(), Err(FalconCoreError::InvalidUncompressedSize(220))));
+
+        let mut reader = SocketRead::new(256);
+        // declared size of 16 MiB is above the limit
+        reader.put_slice(&[0x05, 0x80, 0x80, 0x80, 0x08, 0x00]);
+        assert!(matches!(reader.next_packet(), Err(FalconCoreError::InvalidUncompressedSize(_))));
+    }
+
     /// Bad test, this needs some asserts
     #[test]
     pub fn test_read() {
@@ -2,16 +2,18 @@ use anyhow::Result;
 use bytes::{Buf, Bytes};
 use falcon_core::error::FalconCoreError;
 use falcon_core::network::ConnectionState;
+use falcon_core::server::config::FalconConfig;
 use falcon_packet_core::{PacketRead, ReadError, VarI32};
 use mc_chat::{ChatColor, ChatComponent, ComponentStyle};
 use thiserror::Error;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
-use tracing::{debug_span, trace, trace_span};
+use tracing::{debug, debug_span, info, trace, trace_span, warn};
 
 use super::reader::SocketRead;
 use super::ConnectionReceiver;
 use crate::connection::ConnectionTask;
+use crate::metrics::{metrics, DisconnectReason};
 use crate::FalconConnection;
 
 impl FalconConnection {
@@ -19,53 +21,101 @@ impl FalconConnection {
     pub async fn start<R: ConnectionReceiver>(mut self, mut socket: TcpStream, mut receiver: R) {
         let (mut socket_readhalf, mut socket_writehalf) = socket.split();
         let mut socket_read = SocketRead::new(-1);
+        if let Some(dir) = FalconConfig::global().packet_capture_dir() {
+            match self.start_capture(dir) {
+                Ok(_) => debug!(dir, "Capturing packets"),
+                Err(error) => warn!(%error, "Could not start packet capture"),
+            }
+        }
+
+        let mut metrics_state = self.state.connection_state;
+        metrics().connection_state(None, Some(metrics_state));
 
         loop {
+            if metrics_state != self.state.connection_state {
+                metrics().connection_state(Some(metrics_state), Some(self.state.connection_state));
+                metrics_state = self.state.connection_state;
+            }
+            if self.state.connection_state == ConnectionState::Disconnected && !self.write_buffer.has_remaining() {
+                break;
+            }
+            socket_read.set_max_packet_len(self.state.connection_state.max_packet_len());
+
             tokio::select! {
                 _ = self.shutdown.wait_for_shutdown() => {
+                    self.disconnect_reason.get_or_insert(DisconnectReason::Shutdown);
                     break;
                 }
 
                 _ = self.timeout.tick() => {
                     let style = ComponentStyle::with_version(self.state().protocol_id.unsigned_abs());
-                    self.disconnect(ChatComponent::from_text(
+                    self.disconnect_for(DisconnectReason::Timeout, ChatComponent::from_text(
                         "Did not receive Keep alive packet!",
                         style
                     ));
                 }
 
+                _ = self.congestion_check.tick() => {
+                    if let Some(elapsed) = self.backpressure.update(self.pending_bytes()) {
+                        info!(
+                            pending_bytes = self.pending_bytes(),
+                            queued_tasks = self.backpressure.queued_tasks(),
+                            deferred = self.backpressure.deferred_len(),
+                            "Disconnecting slow client, congested for {}s", elapsed.as_secs()
+                        );
+                        let style = ComponentStyle::with_version(self.state().protocol_id.unsigned_abs());
+                        self.disconnect_for(DisconnectReason::SlowClient, ChatComponent::from_text("Connection too slow!", style));
+                        // the write buffer isn't draining, so don't wait for it
+                        break;
+                    } else if let Err(error) = self.flush_deferred() {
+                        self.disconnect_for(DisconnectReason::WriteError, ChatComponent::from_text(format!("Error on write: {}", error), ComponentStyle::with_version(self.state.protocol_id.unsigned_abs())));
+                    }
+                }
+
                 task = self.task_rx.recv() => {
                     let task = match task {
                         Some(task) => task,
                         None => continue,
                     };
+                    self.backpressure.task_received();
                     let span = debug_span!("connection_task", state = %self.state);
                     let _enter = span.enter();
                     if let Err(error) = match task {
                         ConnectionTask::Sync(task) => task.run(&mut self),
                         ConnectionTask::Async(task) => task.run(&mut self).await,
                     } {
-                        self.disconnect(ChatComponent::from_text(format!("Task errored: {}", error), ComponentStyle::with_version(self.state.protocol_id.unsigned_abs())));
+                        self.disconnect_for(DisconnectReason::TaskError, ChatComponent::from_text(format!("Task errored: {}", error), ComponentStyle::with_version(self.state.protocol_id.unsigned_abs())));
                     };
                 }
 
-                n = socket_readhalf.read_buf(&mut socket_read) => {
+                n = socket_readhalf.read_buf(&mut socket_read), if self.state.connection_state != ConnectionState::Disconnected => {
                     let span = debug_span!("incoming_data", state = %self.state);
                     let _enter = span.enter();
                     match n {
                         Ok(n) => {
+                            metrics().bytes_received(n);
                             if n == 0 {
                                 self.state.connection_state = ConnectionState::Disconnected;
                                 break;
                             }
-                            while let Some(packet) = socket_read.next_packet() {
-                                if let Err(error) = process_packet(&mut self, packet, &mut receiver) {
-                                    self.disconnect(ChatComponent::from_text(format!("Error on read: {}", error), ComponentStyle::with_version(self.state.protocol_id.unsigned_abs())));
+                            loop {
+                                match socket_read.next_packet() {
+                                    Ok(Some(packet)) => {
+                                        if let Err(error) = process_packet(&mut self, packet, &mut receiver) {
+                                            self.disconnect_for(DisconnectReason::ReadError, ChatComponent::from_text(format!("Error on read: {}", error), ComponentStyle::with_version(self.state.protocol_id.unsigned_abs())));
+                                        }
+                                    }
+                                    Ok(None) => break,
+                                    Err(error) => {
+                                        debug!(%error, "Invalid packet received");
+                                        self.disconnect_for(DisconnectReason::ReadError, ChatComponent::from_text(format!("Error on read: {}", error), ComponentStyle::with_version(self.state.protocol_id.unsigned_abs())));
+                                        break;
+                                    }
                                 }
                             }
                         }
                         Err(error) => {
-                            self.disconnect(ChatComponent::from_text(format!("Error on read: {}", error), ComponentStyle::with_version(self.state.protocol_id.unsigned_abs())));
+                            self.disconnect_for(DisconnectReason::ReadError, ChatComponent::from_text(format!("Error on read: {}", error), ComponentStyle::with_version(self.state.protocol_id.unsigned_abs())));
                         }
                     }
                 }
@@ -76,10 +126,17 @@ impl FalconConnection {
                         break;
                     } else if !self.write_buffer.has_remaining() && self.state.connection_state == ConnectionState::Disconnected {
                         break;
+                    } else if let Err(error) = self.flush_deferred() {
+                        self.disconnect_for(DisconnectReason::WriteError, ChatComponent::from_text(format!("Error on write: {}", error), ComponentStyle::with_version(self.state.protocol_id.unsigned_abs())));
                     }
                 }
             }
         }
+        if let Some(capture) = &mut self.capture {
+            capture.flush().ok();
+        }
+        metrics().connection_state(Some(metrics_state), None);
+        metrics().disconnected(self.disconnect_reason.unwrap_or(DisconnectReason::ClientClosed));
         if self.state().connection_state == ConnectionState::Disconnected {
             if let Some(uuid) = self.state().uuid {
                 self.server().player_leave(uuid);
@@ -89,6 +146,7 @@ impl FalconConnection {
 }
 
 fn process_packet<R: ConnectionReceiver>(connection: &mut FalconConnection, mut packet: Bytes, receiver: &mut R) -> Result<()> {
+    connection.record_inbound(&packet);
     let packet_id = VarI32::read(&mut packet)?.val();
     let span = trace_span!("packet", packet_id = %format!("{:#04X}", packet_id));
     let _enter = span.enter();
@@ -96,7 +154,7 @@ fn process_packet<R: ConnectionReceiver>(connection: &mut FalconConnection, mut
         let state = connection.state().connection_state;
         if state == ConnectionState::Login || state == ConnectionState::Status {
             let style = ComponentStyle::with_version(connection.state().protocol_id.unsigned_abs()).color_if_absent(ChatColor::Red);
-            connection.disconnect(ChatComponent::from_text("Unsupported version!", style));
+            connection.disconnect_for(DisconnectReason::UnsupportedVersion, ChatComponent::from_text("Unsupported version!", style));
         }
         trace!("Unknown packet received, skipping!");
     }
@@ -1,6 +1,8 @@
 use std::convert::Infallible;
 use std::error::Error;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use anyhow::Result;
 use falcon_packet_core::WriteError;
@@ -13,10 +15,14 @@ use crate::FalconConnection;
 #[derive(Debug)]
 pub struct ConnectionWrapper {
     link: UnboundedSender<ConnectionTask>,
+    queued_tasks: Arc<AtomicUsize>,
 }
 
 impl ConnectionWrapper {
-    pub fn new(link: UnboundedSender<ConnectionTask>) -> Self { ConnectionWrapper { link } }
+    pub fn new(link: UnboundedSender<ConnectionTask>, queued_tasks: Arc<AtomicUsize>) -> Self { ConnectionWrapper { link, queued_tasks } }
+
+    /// Amount of tasks that have not been picked up by the connection yet.
+    pub fn queued_tasks(&self) -> usize { self.queued_tasks.load(Ordering::Relaxed) }
 
     pub fn reset_keep_alive(&self) {
         self.execute(|connection| {
@@ -36,6 +42,18 @@ impl ConnectionWrapper {
         });
     }
 
+    /// See [`FalconConnection::send_packet_deferrable`].
+    pub fn send_packet_deferrable<T, F>(&self, packet: T, write_fn: F)
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(T, &mut SocketWrite, i32) -> Result<bool, WriteError> + Send + Sync + 'static,
+    {
+        self.execute(move |connection| -> Result<(), WriteError> {
+            connection.send_packet_deferrable(packet, write_fn)?;
+            Ok(())
+        });
+    }
+
     /// Do not pass a `Box` to this function.
     #[inline]
     pub fn send<T>(&self, task: T)
@@ -44,7 +62,10 @@ impl ConnectionWrapper {
     {
         // SAFE: if this channel returns an error, then the client will have
         // disconnected already.
-        self.link.send(ConnectionTask::Sync(Box::new(task))).ok();
+        self.queued_tasks.fetch_add(1, Ordering::Relaxed);
+        if self.link.send(ConnectionTask::Sync(Box::new(task))).is_err() {
+            self.queued_tasks.fetch_sub(1, Ordering::Relaxed);
+        }
     }
 
     /// Do not pass a `Box` to this function.
@@ -61,6 +82,7 @@ impl Clone for ConnectionWrapper {
     fn clone(&self) -> Self {
         ConnectionWrapper {
             link: self.link.clone(),
+            queued_tasks: self.queued_tasks.clone(),
         }
     }
 }
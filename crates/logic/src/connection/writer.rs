@@ -1,9 +1,11 @@
 use bytes::buf::UninitSlice;
-use bytes::{Buf, BufMut, BytesMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use falcon_packet_core::special::PacketPrepare;
 use falcon_packet_core::{PacketSize, VarI32};
 use flate2::{Compress, Compression, FlushCompress, Status};
 
+use crate::metrics::metrics;
+
 const COMPRESSION_BUFFER_LEN: usize = 4096;
 /// See [the specification](https://www.rfc-editor.org/rfc/rfc1950#section-2).
 /// This is header (= 2) + Adler checksum (= 4)
@@ -19,6 +21,8 @@ pub struct SocketWrite {
     next_is_compressed: bool,
     next_len_size: usize,
     ready_pos: usize,
+    capture: Option<BytesMut>,
+    captured: Vec<Bytes>,
 }
 
 impl SocketWrite {
@@ -32,18 +36,36 @@ impl SocketWrite {
             next_is_compressed: false,
             next_len_size: 0,
             ready_pos: 0,
+            capture: None,
+            captured: Vec::new(),
         }
     }
 
+    /// When enabled, a copy of every uncompressed frame is kept
+    /// until it is taken by [`take_captured`](Self::take_captured).
+    pub fn set_capture(&mut self, enabled: bool) {
+        self.capture = enabled.then(BytesMut::new);
+        self.captured.clear();
+    }
+
+    pub fn take_captured(&mut self) -> std::vec::Drain<'_, Bytes> { self.captured.drain(..) }
+
+    /// Amount of bytes that have been written but not yet sent to the socket.
+    pub fn pending_len(&self) -> usize { self.output_buffer.len() }
+
     pub fn finish(&mut self) {
         if self.ready_pos == self.output_buffer.len() {
             return;
         }
 
         self.flush();
+        if let Some(capture) = &mut self.capture {
+            self.captured.push(capture.split().freeze());
+        }
 
         if self.compression_threshold >= 0 {
             if self.next_is_compressed {
+                metrics().packet_compressed(self.compression.total_in(), self.compression.total_out());
                 let offset = VarI32::from(self.compression.total_in() as usize).size();
                 let overall_len = self.next_len_size - offset;
                 write_fixed_varint((self.output_buffer.len() - self.ready_pos - overall_len) as i32, overall_len, &mut self.output_buffer[self.ready_pos..]);
@@ -94,6 +116,9 @@ impl SocketWrite {
     }
 
     fn write_all(&mut self) {
+        if let Some(capture) = &mut self.capture {
+            capture.extend_from_slice(&self.compression_buffer[..self.compression_position]);
+        }
         if self.next_is_compressed {
             let mut n = 0;
             while !&self.compression_buffer[n..self.compression_position].is_empty() {
@@ -216,6 +241,7 @@ impl Buf for SocketWrite {
     fn chunk(&self) -> &[u8] { &self.output_buffer[..self.remaining()] }
 
     fn advance(&mut self, cnt: usize) {
+        metrics().bytes_sent(cnt);
         self.output_buffer.advance(cnt);
         self.ready_pos -= cnt;
     }
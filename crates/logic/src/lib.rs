@@ -1,4 +1,5 @@
 pub mod connection;
+pub mod metrics;
 pub mod player;
 pub mod server;
 pub mod world;
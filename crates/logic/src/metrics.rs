@@ -0,0 +1,268 @@
+//! Server wide counters, exported by the metrics endpoint.
+//!
+//! Everything in here is a plain atomic so it can be updated
+//! from connection tasks and the server thread without locking.
+
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+
+use falcon_core::network::ConnectionState;
+use falcon_core::server::config::FalconConfig;
+
+static METRICS: Metrics = Metrics::new();
+
+/// Upper bounds (in seconds) of the tick duration histogram buckets.
+pub const TICK_BUCKETS: [f64; 10] = [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0];
+
+pub const CONNECTION_STATES: [ConnectionState; 5] =
+    [ConnectionState::Handshake, ConnectionState::Status, ConnectionState::Login, ConnectionState::Play, ConnectionState::Disconnected];
+
+const PROTOCOLS: usize = FalconConfig::ALLOWED_VERSIONS.len();
+
+pub fn metrics() -> &'static Metrics { &METRICS }
+
+/// Why a connection was closed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// The client closed the connection.
+    ClientClosed,
+    /// The server shut down.
+    Shutdown,
+    /// No keep alive was received in time.
+    Timeout,
+    /// The client could not keep up with the outgoing packets.
+    SlowClient,
+    /// Invalid data was received.
+    ReadError,
+    /// Writing a packet or to the socket failed.
+    WriteError,
+    /// A connection task returned an error.
+    TaskError,
+    /// The client uses a protocol version the server doesn't support.
+    UnsupportedVersion,
+    /// Disconnected by a packet handler or the server.
+    Kicked,
+}
+
+impl DisconnectReason {
+    pub const ALL: [DisconnectReason; 9] = [
+        DisconnectReason::ClientClosed,
+        DisconnectReason::Shutdown,
+        DisconnectReason::Timeout,
+        DisconnectReason::SlowClient,
+        DisconnectReason::ReadError,
+        DisconnectReason::WriteError,
+        DisconnectReason::TaskError,
+        DisconnectReason::UnsupportedVersion,
+        DisconnectReason::Kicked,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DisconnectReason::ClientClosed => "client_closed",
+            DisconnectReason::Shutdown => "shutdown",
+            DisconnectReason::Timeout => "timeout",
+            DisconnectReason::SlowClient => "slow_client",
+            DisconnectReason::ReadError => "read_error",
+            DisconnectReason::WriteError => "write_error",
+            DisconnectReason::TaskError => "task_error",
+            DisconnectReason::UnsupportedVersion => "unsupported_version",
+            DisconnectReason::Kicked => "kicked",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Histogram<const N: usize> {
+    bounds: &'static [f64; N],
+    buckets: [AtomicU64; N],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl<const N: usize> Histogram<N> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const ZERO: AtomicU64 = AtomicU64::new(0);
+
+    pub const fn new(bounds: &'static [f64; N]) -> Self {
+        Histogram {
+            bounds,
+            buckets: [Self::ZERO; N],
+            count: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    pub fn observe(&self, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        if let Some(bucket) = self.bounds.iter().position(|bound| secs <= *bound) {
+            self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Cumulative bucket counts, paired with their upper bound.
+    pub fn buckets(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        self.bounds.iter().zip(self.buckets.iter()).scan(0, |total, (bound, count)| {
+            *total += count.load(Ordering::Relaxed);
+            Some((*bound, *total))
+        })
+    }
+
+    pub fn count(&self) -> u64 { self.count.load(Ordering::Relaxed) }
+
+    pub fn sum(&self) -> Duration { Duration::from_micros(self.sum_micros.load(Ordering::Relaxed)) }
+}
+
+#[derive(Debug)]
+pub struct Metrics {
+    players: [AtomicI64; PROTOCOLS],
+    connections: [AtomicI64; CONNECTION_STATES.len()],
+    disconnects: [AtomicU64; DisconnectReason::ALL.len()],
+    tick_duration: Histogram<{ TICK_BUCKETS.len() }>,
+    queued_server_tasks: AtomicI64,
+    bytes_received: AtomicU64,
+    bytes_sent: AtomicU64,
+    compression_in: AtomicU64,
+    compression_out: AtomicU64,
+    chunk_packets: AtomicU64,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const GAUGE: AtomicI64 = AtomicI64::new(0);
+#[allow(clippy::declare_interior_mutable_const)]
+const COUNTER: AtomicU64 = AtomicU64::new(0);
+
+impl Metrics {
+    const fn new() -> Self {
+        Metrics {
+            players: [GAUGE; PROTOCOLS],
+            connections: [GAUGE; CONNECTION_STATES.len()],
+            disconnects: [COUNTER; DisconnectReason::ALL.len()],
+            tick_duration: Histogram::new(&TICK_BUCKETS),
+            queued_server_tasks: AtomicI64::new(0),
+            bytes_received: AtomicU64::new(0),
+            bytes_sent: AtomicU64::new(0),
+            compression_in: AtomicU64::new(0),
+            compression_out: AtomicU64::new(0),
+            chunk_packets: AtomicU64::new(0),
+        }
+    }
+
+    pub fn player_joined(&self, protocol: i32) {
+        if let Some(gauge) = self.player_gauge(protocol) {
+            gauge.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn player_left(&self, protocol: i32) {
+        if let Some(gauge) = self.player_gauge(protocol) {
+            gauge.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    fn player_gauge(&self, protocol: i32) -> Option<&AtomicI64> {
+        FalconConfig::ALLOWED_VERSIONS
+            .iter()
+            .position(|version| *version as i32 == protocol)
+            .map(|index| &self.players[index])
+    }
+
+    /// Online players per protocol version.
+    pub fn players(&self) -> impl Iterator<Item = (u32, i64)> + '_ {
+        FalconConfig::ALLOWED_VERSIONS
+            .iter()
+            .zip(self.players.iter())
+            .map(|(version, gauge)| (*version, gauge.load(Ordering::Relaxed)))
+    }
+
+    /// Moves a connection from one state to another, `None` meaning
+    /// the connection is new or closed.
+    pub fn connection_state(&self, old: Option<ConnectionState>, new: Option<ConnectionState>) {
+        if let Some(old) = old {
+            self.connections[state_index(old)].fetch_sub(1, Ordering::Relaxed);
+        }
+        if let Some(new) = new {
+            self.connections[state_index(new)].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Open connections per state.
+    pub fn connections(&self) -> impl Iterator<Item = (ConnectionState, i64)> + '_ {
+        CONNECTION_STATES
+            .iter()
+            .zip(self.connections.iter())
+            .map(|(state, gauge)| (*state, gauge.load(Ordering::Relaxed)))
+    }
+
+    pub fn disconnected(&self, reason: DisconnectReason) { self.disconnects[reason as usize].fetch_add(1, Ordering::Relaxed); }
+
+    pub fn disconnects(&self) -> impl Iterator<Item = (DisconnectReason, u64)> + '_ {
+        DisconnectReason::ALL
+            .iter()
+            .zip(self.disconnects.iter())
+            .map(|(reason, counter)| (*reason, counter.load(Ordering::Relaxed)))
+    }
+
+    pub fn tick_duration(&self) -> &Histogram<{ TICK_BUCKETS.len() }> { &self.tick_duration }
+
+    pub fn server_task_queued(&self) { self.queued_server_tasks.fetch_add(1, Ordering::Relaxed); }
+
+    pub fn server_task_received(&self) { self.queued_server_tasks.fetch_sub(1, Ordering::Relaxed); }
+
+    pub fn queued_server_tasks(&self) -> i64 { self.queued_server_tasks.load(Ordering::Relaxed) }
+
+    pub fn bytes_received(&self, n: usize) { self.bytes_received.fetch_add(n as u64, Ordering::Relaxed); }
+
+    pub fn bytes_sent(&self, n: usize) { self.bytes_sent.fetch_add(n as u64, Ordering::Relaxed); }
+
+    pub fn total_bytes_received(&self) -> u64 { self.bytes_received.load(Ordering::Relaxed) }
+
+    pub fn total_bytes_sent(&self) -> u64 { self.bytes_sent.load(Ordering::Relaxed) }
+
+    /// Records a compressed packet of `uncompressed` bytes that
+    /// got compressed to `compressed` bytes.
+    pub fn packet_compressed(&self, uncompressed: u64, compressed: u64) {
+        self.compression_in.fetch_add(uncompressed, Ordering::Relaxed);
+        self.compression_out.fetch_add(compressed, Ordering::Relaxed);
+    }
+
+    /// Total uncompressed and compressed size of all compressed packets.
+    pub fn compression(&self) -> (u64, u64) { (self.compression_in.load(Ordering::Relaxed), self.compression_out.load(Ordering::Relaxed)) }
+
+    pub fn chunk_packet_sent(&self) { self.chunk_packets.fetch_add(1, Ordering::Relaxed); }
+
+    pub fn chunk_packets(&self) -> u64 { self.chunk_packets.load(Ordering::Relaxed) }
+}
+
+fn state_index(state: ConnectionState) -> usize {
+    match state {
+        ConnectionState::Handshake => 0,
+        ConnectionState::Status => 1,
+        ConnectionState::Login => 2,
+        ConnectionState::Play => 3,
+        ConnectionState::Disconnected => 4,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{Histogram, TICK_BUCKETS};
+
+    #[test]
+    pub fn test_histogram() {
+        let histogram = Histogram::new(&TICK_BUCKETS);
+        histogram.observe(Duration::from_micros(500));
+        histogram.observe(Duration::from_millis(3));
+        histogram.observe(Duration::from_millis(40));
+        histogram.observe(Duration::from_secs(2));
+
+        let buckets: Vec<_> = histogram.buckets().map(|(_, count)| count).collect();
+        assert_eq!(buckets, vec![1, 1, 2, 2, 2, 3, 3, 3, 3, 3]);
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(histogram.sum(), Duration::from_micros(2_043_500));
+    }
+}
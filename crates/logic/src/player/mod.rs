@@ -1,16 +1,28 @@
 use std::convert::Infallible;
 
+use falcon_core::data::Identifier;
 use falcon_core::player::data::{GameMode, LookAngles, PlayerAbilityFlags, Position};
 use falcon_core::server::config::FalconConfig;
 use falcon_core::server::data::Difficulty;
+use falcon_core::world::chunks::ChunkPos;
+use falcon_core::world::dimension::Dimension;
+use falcon_core::world::BlockPosition;
 use falcon_packet_core::WriteError;
-use falcon_send::specs::play::JoinGameSpec;
+use falcon_send::specs::play::{JoinGameSpec, RespawnSpec};
 use mc_chat::ChatComponent;
 use tokio::time::Instant;
 use uuid::Uuid;
 
 use crate::connection::ConnectionWrapper;
 
+const EYE_HEIGHT: f64 = 1.62;
+/// The reach vanilla servers accept when digging.
+const MAX_REACH: f64 = 6.0;
+const HOTBAR_SLOTS: usize = 9;
+/// The slots of the hotbar in the player inventory window.
+const FIRST_HOTBAR_SLOT: i16 = 36;
+const OFF_HAND_SLOT: i16 = 45;
+
 #[derive(Debug)]
 pub struct FalconPlayer {
     // identity
@@ -19,11 +31,17 @@ pub struct FalconPlayer {
     // in-game
     eid: i32,
     gamemode: GameMode,
-    dimension: i32,
+    world: String,
+    dimension: Dimension,
     abilities: PlayerAbilityFlags,
     position: Position,
     facing: LookAngles,
     view_distance: u8,
+    /// Item ids in the hotbar, the server only learns them from the
+    /// creative inventory.
+    hotbar: [Option<i32>; HOTBAR_SLOTS],
+    off_hand: Option<i32>,
+    held_slot: usize,
     // network
     time: Instant,
     protocol: i32,
@@ -37,11 +55,15 @@ impl FalconPlayer {
             uuid,
             eid,
             gamemode: GameMode::Creative,
-            dimension: 0,
+            world: String::from(FalconConfig::DEFAULT_WORLD),
+            dimension: Dimension::overworld(),
             abilities: PlayerAbilityFlags::new(false, true, true, true),
             position: pos,
             facing,
             view_distance: 5,
+            hotbar: [None; HOTBAR_SLOTS],
+            off_hand: None,
+            held_slot: 0,
             time: Instant::now(),
             protocol,
             connection,
@@ -56,7 +78,14 @@ impl FalconPlayer {
 
     pub fn gamemode(&self) -> GameMode { self.gamemode }
 
-    pub fn dimension(&self) -> i32 { self.dimension }
+    /// Name of the world this player is in.
+    pub fn world(&self) -> &str { &self.world }
+
+    pub fn set_world(&mut self, world: String) { self.world = world; }
+
+    pub fn dimension(&self) -> &Dimension { &self.dimension }
+
+    pub fn set_dimension(&mut self, dimension: Dimension) { self.dimension = dimension; }
 
     pub fn ability_flags(&self) -> PlayerAbilityFlags { self.abilities }
 
@@ -74,6 +103,48 @@ impl FalconPlayer {
         self.view_distance = std::cmp::max(0, std::cmp::min(distance, FalconConfig::global().players.max_view_distance));
     }
 
+    /// Selects the hotbar slot held in the main hand, from 0 to 8.
+    pub fn set_held_slot(&mut self, slot: i16) {
+        if (0..HOTBAR_SLOTS as i16).contains(&slot) {
+            self.held_slot = slot as usize;
+        }
+    }
+
+    /// Changes a slot of the player inventory window, only the items in
+    /// the hotbar and the off hand are kept.
+    pub fn set_inventory_slot(&mut self, slot: i16, item: Option<i32>) {
+        match slot {
+            OFF_HAND_SLOT => self.off_hand = item,
+            _ if (FIRST_HOTBAR_SLOT..FIRST_HOTBAR_SLOT + HOTBAR_SLOTS as i16).contains(&slot) => {
+                self.hotbar[(slot - FIRST_HOTBAR_SLOT) as usize] = item;
+            },
+            _ => {},
+        }
+    }
+
+    /// The item id in the main hand for hand 0 and in the off hand for hand 1.
+    pub fn held_item(&self, hand: i32) -> Option<i32> {
+        match hand {
+            0 => self.hotbar[self.held_slot],
+            1 => self.off_hand,
+            _ => None,
+        }
+    }
+
+    /// Whether the chunk is within this player's view distance.
+    pub fn has_chunk_loaded(&self, pos: ChunkPos) -> bool {
+        let (x, z) = self.position.chunk_coords();
+        x.abs_diff(pos.x) <= self.view_distance as u32 && z.abs_diff(pos.z) <= self.view_distance as u32
+    }
+
+    /// Whether a block is close enough to the eyes of this player to be edited.
+    pub fn can_reach(&self, pos: BlockPosition) -> bool {
+        let dx = pos.get_x() as f64 + 0.5 - self.position.x;
+        let dy = pos.get_y() as f64 + 0.5 - (self.position.y + EYE_HEIGHT);
+        let dz = pos.get_z() as f64 + 0.5 - self.position.z;
+        dx * dx + dy * dy + dz * dz <= MAX_REACH * MAX_REACH
+    }
+
     pub fn protocol_version(&self) -> i32 { self.protocol }
 
     pub fn connection(&self) -> &ConnectionWrapper { &self.connection }
@@ -97,11 +168,24 @@ impl FalconPlayer {
         });
     }
 
-    pub fn join_spec(&self, difficulty: Difficulty, max_players: u8, level_type: String, seed: i64, reduced_debug: bool, enable_respawn: bool) -> JoinGameSpec {
+    pub fn send_message(&self, message: ChatComponent) { self.connection.send_packet(message, falcon_send::write_system_message); }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn join_spec(
+        &self,
+        difficulty: Difficulty,
+        max_players: u8,
+        level_type: String,
+        seed: i64,
+        reduced_debug: bool,
+        enable_respawn: bool,
+        world_names: Vec<Identifier>,
+        dimensions: Vec<Dimension>,
+    ) -> JoinGameSpec {
         JoinGameSpec::new(
             self.eid,
             self.gamemode,
-            self.dimension,
+            self.dimension.id(),
             difficulty,
             max_players,
             level_type,
@@ -109,6 +193,19 @@ impl FalconPlayer {
             self.view_distance as i32,
             reduced_debug,
             enable_respawn,
+            self.dimension.name().clone(),
+            world_identifier(&self.world),
+            world_names,
+            dimensions,
         )
     }
+
+    /// Respawns the player in `dimension`, which must differ from the current
+    /// dimension for clients before 1.16 to unload the old world.
+    pub fn respawn_spec(&self, dimension: &Dimension, difficulty: Difficulty, level_type: String, seed: i64) -> RespawnSpec {
+        RespawnSpec::new(dimension.id(), dimension.name().clone(), world_identifier(&self.world), difficulty, self.gamemode, seed, level_type)
+    }
 }
+
+/// The identifier 1.16+ clients know the world called `name` by.
+pub fn world_identifier(name: &str) -> Identifier { Identifier::from_arbitrary("falcon", name) }
@@ -0,0 +1,157 @@
+use falcon_core::server::config::FalconConfig;
+use falcon_core::world::BlockPosition;
+use mc_chat::{ChatColor, ChatComponent, ComponentStyle};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::server::FalconServer;
+use crate::world::{parse_block, EditError};
+
+impl FalconServer {
+    pub fn player_chat(&mut self, uuid: Uuid, message: String) {
+        match message.strip_prefix('/') {
+            Some(command) => self.player_command(uuid, command),
+            None => {
+                if let Some(player) = self.players.get(&uuid) {
+                    info!("<{}> {}", player.username(), message);
+                }
+            },
+        }
+    }
+
+    fn player_command(&mut self, uuid: Uuid, command: &str) {
+        let version = match self.players.get(&uuid) {
+            Some(player) => player.protocol_version().unsigned_abs(),
+            None => return,
+        };
+        if command.starts_with('/') {
+            self.player_edit_command(uuid, command);
+            return;
+        }
+        let mut args = command.split_whitespace();
+        let reply = match (args.next(), args.next()) {
+            (Some("world"), None) => {
+                let current = self.players.get(&uuid).map(|player| player.world().to_string()).unwrap_or_default();
+                ChatComponent::from_text(
+                    format!("Worlds: {} (you are in \"{}\")", self.world_names().join(", "), current),
+                    ComponentStyle::with_version(version),
+                )
+            },
+            (Some("world"), Some(world)) => {
+                if self.change_world(uuid, world) {
+                    return;
+                }
+                let text = if self.worlds.contains_key(world) {
+                    format!("You are already in \"{}\"", world)
+                } else {
+                    format!("There is no world called \"{}\"", world)
+                };
+                ChatComponent::from_text(text, ComponentStyle::with_version(version).color_if_absent(ChatColor::Red))
+            },
+            _ => ChatComponent::from_text("Unknown command", ComponentStyle::with_version(version).color_if_absent(ChatColor::Red)),
+        };
+        if let Some(player) = self.players.get(&uuid) {
+            player.send_message(reply);
+        }
+    }
+
+    /// Region commands typed as `//set stone`, only builders may use
+    /// them and only in worlds that allow building.
+    fn player_edit_command(&mut self, uuid: Uuid, command: &str) {
+        let player = match self.players.get(&uuid) {
+            Some(player) => player,
+            None => return,
+        };
+        info!(username = player.username(), cmd = %command, "Player edit command");
+        let config = FalconConfig::global();
+        let result = if config.is_builder(player.username(), uuid) && config.allows_building(player.world()) {
+            let args: Vec<_> = command.split_whitespace().collect();
+            self.edit_command(Some(uuid), &args)
+        } else {
+            Err(EditError::NotAllowed)
+        };
+        if let Some(player) = self.players.get(&uuid) {
+            let style = ComponentStyle::with_version(player.protocol_version().unsigned_abs());
+            let reply = match result {
+                Ok(text) => ChatComponent::from_text(text, style),
+                Err(error) => ChatComponent::from_text(error.to_string(), style.color_if_absent(ChatColor::Red)),
+            };
+            player.send_message(reply);
+        }
+    }
+
+    /// Runs a region command with its leading slash left, like `/set stone`.
+    /// Players edit their world and default to their own position, the
+    /// console edits the default world and needs coordinates.
+    pub(crate) fn edit_command(&mut self, uuid: Option<Uuid>, args: &[&str]) -> Result<String, EditError> {
+        let (world_name, position) = match uuid.and_then(|uuid| self.players.get(&uuid)) {
+            Some(player) => (player.world().to_string(), Some(BlockPosition::from(*player.position()))),
+            None => (FalconConfig::DEFAULT_WORLD.to_string(), None),
+        };
+        let session = self.edit_sessions.entry(uuid).or_default();
+        if let ["/undo"] = args {
+            let (name, edit) = session.take_last_edit()?;
+            let world = self.worlds.get_mut(&name).ok_or(EditError::UnknownWorld(name))?;
+            let count = edit.len();
+            world.undo(edit);
+            return Ok(format!("Undid {} block changes", count));
+        }
+        let world = self
+            .worlds
+            .get_mut(&world_name)
+            .ok_or_else(|| EditError::UnknownWorld(world_name.clone()))?;
+        match args {
+            ["/pos1", coords @ ..] => {
+                let pos = target_position(coords, position, "//pos1 [<x> <y> <z>]")?;
+                session.set_pos1(pos);
+                Ok(format!("First corner set to {} {} {}", pos.get_x(), pos.get_y(), pos.get_z()))
+            },
+            ["/pos2", coords @ ..] => {
+                let pos = target_position(coords, position, "//pos2 [<x> <y> <z>]")?;
+                session.set_pos2(pos);
+                Ok(format!("Second corner set to {} {} {}", pos.get_x(), pos.get_y(), pos.get_z()))
+            },
+            ["/set", block] => {
+                let edit = world.set_region(session.selection()?, parse_block(block)?)?;
+                let count = edit.len();
+                session.remember(&world_name, edit);
+                Ok(format!("Changed {} blocks", count))
+            },
+            ["/replace", from, to] => {
+                let edit = world.replace_region(session.selection()?, parse_block(from)?, parse_block(to)?)?;
+                let count = edit.len();
+                session.remember(&world_name, edit);
+                Ok(format!("Replaced {} blocks", count))
+            },
+            ["/copy"] => {
+                let region = session.selection()?;
+                let clipboard = world.copy_region(region, position.unwrap_or_else(|| region.min()))?;
+                let count = clipboard.len();
+                session.set_clipboard(clipboard);
+                Ok(format!("Copied {} blocks", count))
+            },
+            ["/paste", coords @ ..] => {
+                let origin = target_position(coords, position, "//paste [<x> <y> <z>]")?;
+                let edit = world.paste(session.clipboard()?, origin);
+                let count = edit.len();
+                session.remember(&world_name, edit);
+                Ok(format!("Pasted, {} blocks changed", count))
+            },
+            ["/set", ..] => Err(EditError::Usage("//set <block>")),
+            ["/replace", ..] => Err(EditError::Usage("//replace <from> <to>")),
+            _ => Err(EditError::Usage("//pos1, //pos2, //set, //replace, //copy, //paste or //undo")),
+        }
+    }
+}
+
+/// The coordinates given to a command or else the position of the player.
+fn target_position(coords: &[&str], position: Option<BlockPosition>, usage: &'static str) -> Result<BlockPosition, EditError> {
+    match coords {
+        [] => position.ok_or(EditError::Usage(usage)),
+        [x, y, z] => match (x.parse(), y.parse(), z.parse()) {
+            (Ok(x), Ok(y), Ok(z)) => Ok(BlockPosition::new(x, y, z)),
+            _ => Err(EditError::Usage(usage)),
+        },
+        _ => Err(EditError::Usage(usage)),
+    }
+}
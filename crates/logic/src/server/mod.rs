@@ -4,24 +4,38 @@ use std::pin::Pin;
 
 use ahash::AHashMap;
 use anyhow::Result;
+use falcon_core::world::dimension::Dimension;
+use falcon_core::world::items::ItemRegistries;
 use falcon_core::ShutdownHandle;
+use itertools::Itertools;
 use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::time::Instant;
 use uuid::Uuid;
 pub use wrapper::ServerWrapper;
 
+use self::monitor::TickMonitor;
+use self::tick::PendingSave;
 use crate::player::FalconPlayer;
-use crate::world::FalconWorld;
+use crate::world::{EditSession, FalconWorld};
 
+mod commands;
+pub mod monitor;
 mod network;
 mod tick;
 mod wrapper;
 
 pub trait SyncServerTask: Send + Sync {
     fn run(self: Box<Self>, server: &mut FalconServer) -> Result<()>;
+
+    /// Name used when reporting slow tasks.
+    fn name(&self) -> &'static str { std::any::type_name::<Self>() }
 }
 
 pub trait SyncFutServerTask: Send + Sync {
     fn run(self: Box<Self>, server: &mut FalconServer) -> Pin<Box<dyn Future<Output = Result<()>> + Send + '_>>;
+
+    /// Name used when reporting slow tasks.
+    fn name(&self) -> &'static str { std::any::type_name::<Self>() }
 }
 
 pub enum ServerTask {
@@ -37,20 +51,42 @@ pub struct FalconServer {
     eid_count: i32,
     players: AHashMap<Uuid, FalconPlayer>,
     usernames: AHashMap<String, Uuid>,
-    world: FalconWorld,
+    worlds: AHashMap<String, FalconWorld>,
+    /// Region editing state per player, `None` is the console.
+    edit_sessions: AHashMap<Option<Uuid>, EditSession>,
+    /// Worlds that are being saved off the tick thread.
+    saves: AHashMap<String, PendingSave>,
+    /// Which block an item places, per protocol version.
+    items: ItemRegistries,
+    tick_monitor: TickMonitor,
 }
 
 impl FalconServer {
-    pub fn new(shutdown: ShutdownHandle, console_rx: UnboundedReceiver<String>, receiver: UnboundedReceiver<ServerTask>, world: FalconWorld) -> Self {
+    pub fn new(
+        shutdown: ShutdownHandle,
+        console_rx: UnboundedReceiver<String>,
+        receiver: UnboundedReceiver<ServerTask>,
+        mut worlds: AHashMap<String, FalconWorld>,
+        items: ItemRegistries,
+    ) -> Self {
+        // entities share their ids with players
+        let mut eid_count = 0;
+        for world in worlds.values_mut() {
+            world.assign_entity_ids(&mut eid_count);
+        }
         Self {
             shutdown,
             should_stop: false,
             console_rx,
             receiver,
-            eid_count: 0,
+            eid_count,
             players: AHashMap::new(),
             usernames: AHashMap::new(),
-            world,
+            worlds,
+            edit_sessions: AHashMap::new(),
+            saves: AHashMap::new(),
+            items,
+            tick_monitor: TickMonitor::new(Instant::now()),
         }
     }
 
@@ -66,7 +102,22 @@ impl FalconServer {
 
     pub fn username_mut(&mut self, username: &String) -> Option<&mut FalconPlayer> { self.usernames.get(username).and_then(|x| self.players.get_mut(x)) }
 
-    pub fn world(&mut self) -> &mut FalconWorld { &mut self.world }
+    pub fn world(&mut self, name: &str) -> Option<&mut FalconWorld> { self.worlds.get_mut(name) }
+
+    /// The distinct dimensions of all loaded worlds.
+    pub fn dimensions(&self) -> Vec<Dimension> {
+        self.worlds
+            .values()
+            .map(|world| world.dimension())
+            .unique_by(|dimension| dimension.id())
+            .cloned()
+            .collect()
+    }
+
+    /// Names of all loaded worlds, sorted.
+    pub fn world_names(&self) -> Vec<&str> { self.worlds.keys().map(String::as_str).sorted().collect() }
+
+    pub fn tick_monitor(&self) -> &TickMonitor { &self.tick_monitor }
 }
 
 impl<F, E> SyncServerTask for F
@@ -0,0 +1,160 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use itertools::Itertools;
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::metrics::metrics;
+
+pub const TICK_DURATION: Duration = Duration::from_millis(50);
+pub const TICKS_PER_SECOND: f64 = 20.0;
+/// The windows (in seconds) the TPS and MSPT are averaged over.
+pub const WINDOWS: [usize; 3] = [60, 5 * 60, 15 * 60];
+
+const MAX_SAMPLES: usize = WINDOWS[WINDOWS.len() - 1];
+
+/// How far behind the server has to be before warning.
+const LAG_THRESHOLD: Duration = Duration::from_millis(100);
+/// Minimum time between two lag warnings.
+const LAG_WARNING_INTERVAL: Duration = Duration::from_secs(15);
+/// Amount of server tasks that get reported for a slow tick.
+const SLOWEST_TASKS: usize = 5;
+
+/// Ticks and busy time within one second.
+#[derive(Debug, Default, Clone, Copy)]
+struct Sample {
+    ticks: u32,
+    busy: Duration,
+}
+
+/// Measures every tick of the server, keeping rolling
+/// TPS and MSPT figures.
+#[derive(Debug)]
+pub struct TickMonitor {
+    samples: VecDeque<Sample>,
+    current: Sample,
+    current_start: Instant,
+    last_tick: Option<Instant>,
+    last_warning: Option<Instant>,
+    tasks: Vec<(&'static str, Duration)>,
+}
+
+impl TickMonitor {
+    pub fn new(now: Instant) -> Self {
+        TickMonitor {
+            samples: VecDeque::with_capacity(MAX_SAMPLES),
+            current: Sample::default(),
+            current_start: now,
+            last_tick: None,
+            last_warning: None,
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Records how long a server task of the current tick took.
+    pub fn task_finished(&mut self, name: &'static str, duration: Duration) { self.tasks.push((name, duration)); }
+
+    /// Records a tick that started at `start` and took `duration`.
+    pub fn tick_finished(&mut self, start: Instant, duration: Duration) {
+        metrics().tick_duration().observe(duration);
+
+        if let Some(last_tick) = self.last_tick {
+            let behind = start.saturating_duration_since(last_tick).saturating_sub(TICK_DURATION);
+            if behind >= LAG_THRESHOLD
+                && self
+                    .last_warning
+                    .is_none_or(|warning| start.duration_since(warning) >= LAG_WARNING_INTERVAL)
+            {
+                warn!("Can't keep up! Running {}ms behind, skipping {} tick(s)", behind.as_millis(), behind.as_millis() / TICK_DURATION.as_millis());
+                self.last_warning = Some(start);
+            }
+        }
+        self.last_tick = Some(start);
+
+        if duration > TICK_DURATION && !self.tasks.is_empty() {
+            self.tasks.sort_unstable_by_key(|(_, duration)| std::cmp::Reverse(*duration));
+            let slowest = self
+                .tasks
+                .iter()
+                .take(SLOWEST_TASKS)
+                .map(|(name, duration)| format!("{} ({:.2}ms)", name, duration.as_secs_f64() * 1000.0))
+                .join(", ");
+            warn!(tasks = self.tasks.len(), "Slow tick took {}ms, slowest tasks: {}", duration.as_millis(), slowest);
+        }
+        self.tasks.clear();
+
+        while start.saturating_duration_since(self.current_start) >= Duration::from_secs(1) {
+            if self.samples.len() == MAX_SAMPLES {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(std::mem::take(&mut self.current));
+            self.current_start += Duration::from_secs(1);
+        }
+        self.current.ticks += 1;
+        self.current.busy += duration;
+    }
+
+    /// Average ticks per second over the last `window` seconds.
+    pub fn tps(&self, window: usize) -> f64 {
+        let (ticks, seconds, _) = self.window(window);
+        if seconds == 0 {
+            return TICKS_PER_SECOND;
+        }
+        ticks as f64 / seconds as f64
+    }
+
+    /// Average milliseconds per tick over the last `window` seconds.
+    pub fn mspt(&self, window: usize) -> f64 {
+        let (ticks, _, busy) = self.window(window);
+        if ticks == 0 {
+            return 0.0;
+        }
+        busy.as_secs_f64() * 1000.0 / ticks as f64
+    }
+
+    fn window(&self, window: usize) -> (u64, usize, Duration) {
+        self.samples
+            .iter()
+            .rev()
+            .take(window)
+            .fold((0, 0, Duration::ZERO), |(ticks, seconds, busy), sample| (ticks + sample.ticks as u64, seconds + 1, busy + sample.busy))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::{TickMonitor, TICK_DURATION};
+
+    #[test]
+    pub fn test_windows() {
+        let start = Instant::now();
+        let mut monitor = TickMonitor::new(start);
+        assert_eq!(monitor.tps(60), 20.0);
+
+        // 90 seconds at full speed, followed by 30 seconds at half speed
+        let mut now = start;
+        for tick in 0..(90 * 20 + 30 * 10) {
+            let busy = if tick < 90 * 20 {
+                Duration::from_millis(10)
+            } else {
+                Duration::from_millis(100)
+            };
+            monitor.tick_finished(now, busy);
+            now += if tick < 90 * 20 {
+                TICK_DURATION
+            } else {
+                2 * TICK_DURATION
+            };
+        }
+        monitor.tick_finished(now, Duration::ZERO);
+
+        assert_eq!(monitor.tps(60), 15.0);
+        assert_eq!(monitor.tps(15 * 60), (90 * 20 + 30 * 10) as f64 / 120.0);
+        assert!((monitor.mspt(30) - 100.0).abs() < 1e-9);
+    }
+}
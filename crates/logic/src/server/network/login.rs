@@ -8,7 +8,8 @@ use tracing::{debug, error, info};
 use uuid::Uuid;
 
 use crate::connection::ConnectionWrapper;
-use crate::player::FalconPlayer;
+use crate::metrics::metrics;
+use crate::player::{world_identifier, FalconPlayer};
 use crate::server::FalconServer;
 
 impl FalconServer {
@@ -34,15 +35,30 @@ impl FalconServer {
         }
         info!(name = %username, "Player joined the game!");
         let (spawn_pos, spawn_look) = (FalconConfig::global().players.spawn_position, FalconConfig::global().players.spawn_look);
-        let player = FalconPlayer::new(username.clone(), uuid, self.eid_count, spawn_pos, spawn_look, protocol, connection);
+        let mut player = FalconPlayer::new(username.clone(), uuid, self.eid_count, spawn_pos, spawn_look, protocol, connection);
+        if let Some(world) = self.worlds.get(player.world()) {
+            player.set_dimension(world.dimension().clone());
+        }
 
         self.eid_count += 1;
 
-        self.players.insert(uuid, player);
+        metrics().player_joined(protocol);
+        if let Some(old) = self.players.insert(uuid, player) {
+            metrics().player_left(old.protocol_version());
+        }
         self.usernames.insert(username, uuid);
         if let Some(player) = self.players.get(&uuid) {
-            let join_game_spec =
-                player.join_spec(Difficulty::Peaceful, FalconConfig::global().server.max_players as u8, String::from("customized"), 0, false, false);
+            let world_names = self.world_names().into_iter().map(world_identifier).collect();
+            let join_game_spec = player.join_spec(
+                Difficulty::Peaceful,
+                FalconConfig::global().server.max_players as u8,
+                String::from("customized"),
+                0,
+                false,
+                false,
+                world_names,
+                self.dimensions(),
+            );
             player.connection().send_packet(join_game_spec, falcon_send::write_join_game);
 
             let server_difficulty = ServerDifficultySpec::new(Difficulty::Peaceful, false);
@@ -55,7 +71,9 @@ impl FalconServer {
                 .connection()
                 .send_packet(player_abilities, falcon_send::write_player_abilities);
 
-            self.world.send_chunks_for_player(player);
+            if let Some(world) = self.worlds.get_mut(player.world()) {
+                world.send_chunks_for_player(player);
+            }
 
             let position_look = PositionAndLookSpec::new(player.position(), player.look_angles(), 0, 1);
             player.connection().send_packet(position_look, falcon_send::write_position_look);
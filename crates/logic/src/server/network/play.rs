@@ -1,13 +1,25 @@
-use falcon_core::player::data::Position;
-use tracing::info;
+use falcon_core::player::data::{GameMode, Position};
+use falcon_core::server::config::FalconConfig;
+use falcon_core::server::data::Difficulty;
+use falcon_core::world::block_util::is_replaceable;
+use falcon_core::world::blocks::Blocks;
+use falcon_core::world::dimension::Dimension;
+use falcon_core::world::entity::Direction;
+use falcon_core::world::BlockPosition;
+use falcon_packet_core::WriteError;
+use falcon_send::specs::play::{PlayerAbilitiesSpec, PositionAndLookSpec};
+use tracing::{debug, info};
 use uuid::Uuid;
 
+use crate::metrics::metrics;
 use crate::server::FalconServer;
 
 impl FalconServer {
     pub fn player_leave(&mut self, uuid: Uuid) {
         if let Some(player) = self.players.remove(&uuid) {
             self.usernames.remove(player.username());
+            self.edit_sessions.remove(&Some(uuid));
+            metrics().player_left(player.protocol_version());
             info!(%uuid, username = player.username(), "Player disconnected!");
         }
     }
@@ -43,7 +55,10 @@ impl FalconServer {
             None => (0, 0, 0, 0),
         };
         if update_position {
-            self.world.update_player_pos(self.players.get(&uuid).unwrap(), old_x, old_z, x, z);
+            let player = self.players.get(&uuid).unwrap();
+            if let Some(world) = self.worlds.get_mut(player.world()) {
+                world.update_player_pos(player, old_x, old_z, x, z);
+            }
         }
         if update_viewpos {
             self.players
@@ -56,8 +71,141 @@ impl FalconServer {
 
     pub fn player_update_view_distance(&mut self, uuid: Uuid, view_distance: u8) {
         if let Some(player) = self.players.get_mut(&uuid) {
-            self.world.update_view_distance(player, view_distance);
+            if let Some(world) = self.worlds.get_mut(player.world()) {
+                world.update_view_distance(player, view_distance);
+            }
             player.set_view_distance(view_distance);
         }
     }
+
+    /// Moves a player to the spawn of the world called `world`, returns
+    /// whether the player was moved.
+    pub fn change_world(&mut self, uuid: Uuid, world: &str) -> bool {
+        let player = match self.players.get_mut(&uuid) {
+            Some(player) if player.world() != world && self.worlds.contains_key(world) => player,
+            _ => return false,
+        };
+        player.set_world(world.to_string());
+        let old_dimension = player.dimension().id();
+        player.set_dimension(self.worlds[world].dimension().clone());
+        *player.position_mut() = FalconConfig::global().world_spawn(world);
+
+        let respawn = player.respawn_spec(player.dimension(), Difficulty::Peaceful, String::from("customized"), 0);
+        // clients before 1.16 only unload their world when the dimension changes
+        let detour = (player.protocol_version() < 735 && player.dimension().id() == old_dimension).then(|| {
+            let other = if old_dimension == -1 {
+                Dimension::overworld()
+            } else {
+                Dimension::nether()
+            };
+            player.respawn_spec(&other, Difficulty::Peaceful, String::from("customized"), 0)
+        });
+        player.connection().execute(move |connection| -> Result<(), WriteError> {
+            // chunks of the old world that have not been sent yet
+            connection.discard_deferred();
+            if let Some(detour) = detour {
+                connection.send_packet(detour, falcon_send::write_respawn)?;
+            }
+            connection.send_packet(respawn, falcon_send::write_respawn)?;
+            Ok(())
+        });
+
+        let player = self.players.get(&uuid).unwrap();
+        let player_abilities = PlayerAbilitiesSpec::new(player.ability_flags(), 0.05, 0.1);
+        player
+            .connection()
+            .send_packet(player_abilities, falcon_send::write_player_abilities);
+        player
+            .connection()
+            .send_packet(player.position().chunk_coords(), falcon_send::write_update_viewpos);
+        if let Some(world) = self.worlds.get_mut(world) {
+            world.send_chunks_for_player(player);
+        }
+        let position_look = PositionAndLookSpec::new(player.position(), player.look_angles(), 0, 1);
+        player.connection().send_packet(position_look, falcon_send::write_position_look);
+        info!(%uuid, username = player.username(), world, "Player changed world");
+        true
+    }
+
+    /// Creative players break a block as soon as they start digging.
+    pub fn player_dig(&mut self, uuid: Uuid, status: i32, position: BlockPosition) {
+        // 0 means started digging, the other statuses don't change blocks in creative
+        if status == 0 {
+            self.edit_block(uuid, position, Blocks::Air);
+        }
+    }
+
+    pub fn player_change_held_item(&mut self, uuid: Uuid, slot: i16) {
+        if let Some(player) = self.players.get_mut(&uuid) {
+            player.set_held_slot(slot);
+        }
+    }
+
+    /// Creative players tell the server which items they put in their
+    /// inventory, this is how the server knows what they place.
+    pub fn player_set_creative_slot(&mut self, uuid: Uuid, slot: i16, item: Option<i32>) {
+        if let Some(player) = self.players.get_mut(&uuid).filter(|player| player.gamemode() == GameMode::Creative) {
+            player.set_inventory_slot(slot, item);
+        }
+    }
+
+    /// Places the default state of the block the held item places. It
+    /// replaces the clicked block if that is replaceable, like grass, and
+    /// goes next to it otherwise. Placements of items without a block are
+    /// reverted, the clicked block as well in case the client changed it.
+    pub fn player_place_block(&mut self, uuid: Uuid, position: BlockPosition, face: i32, hand: i32) {
+        let (player, world) = match self.players.get(&uuid) {
+            Some(player) => (player, self.worlds.get_mut(player.world())),
+            None => return,
+        };
+        let (world, (x, y, z)) = match (world, Direction::from_id(face)) {
+            (Some(world), Some(face)) => (world, face.offset()),
+            _ => return,
+        };
+        let next = BlockPosition::new(position.get_x() + x, position.get_y() + y, position.get_z() + z);
+        let target = if is_replaceable(&world.block_at(position)) {
+            position
+        } else {
+            next
+        };
+        let block = player
+            .held_item(hand)
+            .and_then(|item| self.items.block(player.protocol_version(), item))
+            .filter(|_| is_replaceable(&world.block_at(target)));
+        match block {
+            Some(block) => {
+                if target != position {
+                    world.revert_block(player, position);
+                }
+                self.edit_block(uuid, target, block);
+            },
+            None => {
+                debug!(%uuid, ?position, hand, "Reverted placement of an unknown block");
+                world.revert_block(player, position);
+                world.revert_block(player, next);
+            },
+        }
+    }
+
+    /// Changes a block for a player that may build, the change is sent to
+    /// players at the end of the tick. Rejected edits are reverted.
+    fn edit_block(&mut self, uuid: Uuid, position: BlockPosition, block: Blocks) {
+        let player = match self.players.get(&uuid) {
+            Some(player) => player,
+            None => return,
+        };
+        let world = match self.worlds.get_mut(player.world()) {
+            Some(world) => world,
+            None => return,
+        };
+        let config = FalconConfig::global();
+        let allowed = player.gamemode() == GameMode::Creative
+            && config.allows_building(player.world())
+            && config.is_builder(player.username(), uuid)
+            && player.can_reach(position);
+        if !allowed || !world.set_block(position, block) {
+            debug!(%uuid, ?position, "Rejected block change");
+            world.revert_block(player, position);
+        }
+    }
 }
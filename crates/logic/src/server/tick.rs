@@ -1,11 +1,33 @@
+use std::path::Path;
 use std::time::Duration;
 
+use falcon_core::error::FalconCoreError;
+use falcon_core::server::config::FalconConfig;
+use falcon_core::world::block_util::blocks_movement;
+use falcon_core::world::chunks::ChunkPos;
+use falcon_core::world::BlockPosition;
+use falcon_packet_core::WriteError;
+use falcon_send::specs::play::PositionAndLookSpec;
+use itertools::Itertools;
 use tokio::runtime::Builder;
-use tokio::time::MissedTickBehavior;
-use tracing::{debug, debug_span, error, info};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{debug, debug_span, error, info, warn};
 
+use super::monitor::WINDOWS;
 use super::ServerTask;
-use crate::FalconServer;
+use crate::metrics::metrics;
+use crate::{FalconServer, FalconWorld};
+
+/// A world being encoded and written on the blocking thread pool.
+#[derive(Debug)]
+pub(super) struct PendingSave {
+    path: String,
+    /// Marked as changed again if the save fails.
+    chunks: Vec<ChunkPos>,
+    start: Instant,
+    handle: JoinHandle<Result<(), FalconCoreError>>,
+}
 
 impl FalconServer {
     #[tracing::instrument(name = "server", skip(self))]
@@ -18,20 +40,32 @@ impl FalconServer {
             tick_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
             let mut keep_alive_interval = tokio::time::interval(Duration::from_secs(12));
             keep_alive_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            let autosave = FalconConfig::global().autosave_interval();
+            let autosave_period = autosave.unwrap_or(Duration::from_secs(300));
+            let mut autosave_interval = tokio::time::interval_at(Instant::now() + autosave_period, autosave_period);
+            autosave_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
             while !self.should_stop {
                 tokio::select! {
                     _ = tick_interval.tick() => {
+                        let start = Instant::now();
                         self.tick().await;
+                        self.tick_monitor.tick_finished(start, start.elapsed());
                     }
                     _ = keep_alive_interval.tick() => {
                         self.keep_alive();
                     }
+                    _ = autosave_interval.tick(), if autosave.is_some() => {
+                        self.save_worlds();
+                    }
                     _ = self.shutdown_handle().wait_for_shutdown() => {
                         break;
                     }
                 }
             }
+            self.finish_saves(true).await;
+            self.save_worlds();
+            self.finish_saves(true).await;
             debug!("Stopping server logic!");
         });
     }
@@ -39,16 +73,25 @@ impl FalconServer {
     /// Game loop method
     #[tracing::instrument(skip(self), fields(player_count = self.online_count()))]
     async fn tick(&mut self) {
+        self.finish_saves(false).await;
         while let Ok(task) = self.receiver.try_recv() {
+            metrics().server_task_received();
             let span = debug_span!("server_task");
             let _enter = span.enter();
-            if let Err(error) = match task {
-                ServerTask::Sync(task) => task.run(self),
-                ServerTask::Async(task) => task.run(self).await,
-            } {
+            let start = Instant::now();
+            let (name, result) = match task {
+                ServerTask::Sync(task) => (task.name(), task.run(self)),
+                ServerTask::Async(task) => (task.name(), task.run(self).await),
+            };
+            self.tick_monitor.task_finished(name, start.elapsed());
+            if let Err(error) = result {
                 error!(%error);
             }
         }
+        for (name, world) in self.worlds.iter_mut() {
+            world.send_block_changes(self.players.values().filter(|player| player.world() == name));
+            world.unload_generated(self.players.values().filter(|player| player.world() == name));
+        }
         while let Ok(command) = self.console_rx.try_recv() {
             info!(cmd = %command.trim(), "Console command execution");
             // TODO: better commands
@@ -57,8 +100,128 @@ impl FalconServer {
                 self.should_stop = true;
                 self.shutdown_handle().send_shutdown();
                 return;
+            } else if command.trim() == "save" {
+                if self.worlds.keys().all(|name| FalconConfig::global().world_save_path(name).is_none()) {
+                    warn!("No save location configured, set `save` in the config!");
+                }
+                self.save_worlds();
+            } else if command.trim() == "tps" {
+                let monitor = &self.tick_monitor;
+                let tps = WINDOWS.iter().map(|window| format!("{:.2}", monitor.tps(*window))).join(", ");
+                let mspt = WINDOWS.iter().map(|window| format!("{:.2}", monitor.mspt(*window))).join(", ");
+                info!("TPS from last 1m, 5m, 15m: {}", tps);
+                info!("MSPT from last 1m, 5m, 15m: {}", mspt);
+            } else if let Some(command) = command.trim().strip_prefix('/') {
+                let args: Vec<_> = command.split_whitespace().collect();
+                match self.edit_command(None, &args) {
+                    Ok(reply) => info!("{}", reply),
+                    Err(error) => warn!("{}", error),
+                }
+            }
+        }
+    }
+
+    /// Starts saving every world that changed to its configured save
+    /// location. The changes are copied on the tick thread, encoding and
+    /// writing them happens on the blocking thread pool. Worlds that are
+    /// still being saved are skipped.
+    pub fn save_worlds(&mut self) {
+        for (name, world) in self.worlds.iter_mut() {
+            let path = match FalconConfig::global().world_save_path(name) {
+                Some(path) => path,
+                None => continue,
+            };
+            if self.saves.contains_key(name) {
+                debug!(world = %name, "World is still being saved");
+                continue;
+            }
+            match world.take_save(Path::new(path)) {
+                Ok(Some(save)) => {
+                    let chunks = save.chunk_positions();
+                    let handle = tokio::task::spawn_blocking(move || save.write());
+                    self.saves.insert(name.clone(), PendingSave {
+                        path: path.to_owned(),
+                        chunks,
+                        start: Instant::now(),
+                        handle,
+                    });
+                },
+                Ok(None) => debug!(world = %name, "World unchanged, nothing to save"),
+                Err(error) => error!(%error, "Could not save world \"{}\" to \"{}\"", name, path),
+            }
+        }
+    }
+
+    /// Reports the saves that finished, or waits for all of them if `wait`
+    /// is set. Chunks of failed saves are marked as changed again.
+    async fn finish_saves(&mut self, wait: bool) {
+        let finished: Vec<String> = self
+            .saves
+            .iter()
+            .filter(|(_, save)| wait || save.handle.is_finished())
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in finished {
+            let save = match self.saves.remove(&name) {
+                Some(save) => save,
+                None => continue,
+            };
+            let error = match save.handle.await {
+                Ok(Ok(())) => {
+                    info!("Saved world \"{}\" to \"{}\" in {}ms", name, save.path, save.start.elapsed().as_millis());
+                    continue;
+                },
+                Ok(Err(error)) => error.to_string(),
+                Err(error) => error.to_string(),
+            };
+            error!(%error, "Could not save world \"{}\" to \"{}\"", name, save.path);
+            if let Some(world) = self.worlds.get_mut(&name) {
+                world.mark_unsaved(&save.chunks);
+            }
+        }
+    }
+
+    /// Replaces the world called `name`, players in it get the chunks of the
+    /// new world and are moved to spawn if they would be stuck in a block.
+    pub fn reload_world(&mut self, name: String, mut world: FalconWorld) {
+        let old = match self.worlds.get(&name) {
+            Some(old) => old,
+            None => {
+                warn!("There is no world called \"{}\" to reload", name);
+                return;
+            },
+        };
+        if old.is_dirty() {
+            warn!("Discarding unsaved changes of world \"{}\"", name);
+        }
+        for session in self.edit_sessions.values_mut() {
+            session.forget_world(&name);
+        }
+        world.assign_entity_ids(&mut self.eid_count);
+        let spawn = FalconConfig::global().world_spawn(&name);
+        for player in self.players.values_mut().filter(|player| player.world() == name) {
+            player.connection().execute(|connection| -> Result<(), WriteError> {
+                // chunks of the old world that have not been sent yet
+                connection.discard_deferred();
+                Ok(())
+            });
+            old.unload_chunks_for_player(player);
+
+            let feet = BlockPosition::from(*player.position());
+            let head = BlockPosition::new(feet.get_x(), feet.get_y() + 1, feet.get_z());
+            if blocks_movement(&world.block_at(feet)) || blocks_movement(&world.block_at(head)) {
+                *player.position_mut() = spawn;
+                player
+                    .connection()
+                    .send_packet(player.position().chunk_coords(), falcon_send::write_update_viewpos);
+                let position_look = PositionAndLookSpec::new(player.position(), player.look_angles(), 0, 1);
+                player.connection().send_packet(position_look, falcon_send::write_position_look);
+                info!(username = player.username(), "Moved player out of the reloaded world");
             }
+            world.send_chunks_for_player(player);
         }
+        self.worlds.insert(name.clone(), world);
+        info!("Reloaded world \"{}\"", name);
     }
 
     #[tracing::instrument(skip(self), fields(player_count = self.players.len()))]
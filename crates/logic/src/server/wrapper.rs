@@ -3,12 +3,14 @@ use std::error::Error;
 
 use anyhow::Result;
 use falcon_core::player::data::Position;
+use falcon_core::world::BlockPosition;
 use tokio::sync::mpsc::UnboundedSender;
 use uuid::Uuid;
 
 use super::{ServerTask, SyncServerTask};
 use crate::connection::ConnectionWrapper;
-use crate::FalconServer;
+use crate::metrics::metrics;
+use crate::{FalconServer, FalconWorld};
 
 #[derive(Debug)]
 pub struct ServerWrapper {
@@ -26,7 +28,9 @@ impl ServerWrapper {
     {
         // SAFE: if this channel returns an error, the server will have shut down
         // already.
-        self.link.send(ServerTask::Sync(Box::new(task))).ok();
+        if self.link.send(ServerTask::Sync(Box::new(task))).is_ok() {
+            metrics().server_task_queued();
+        }
     }
 
     /// Do not pass a `Box` to this function.
@@ -68,6 +72,48 @@ impl ServerWrapper {
         });
     }
 
+    pub fn reload_world(&self, name: String, world: FalconWorld) {
+        self.execute(move |server| {
+            server.reload_world(name, world);
+            Ok::<(), Infallible>(())
+        });
+    }
+
+    pub fn player_dig(&self, uuid: Uuid, status: i32, position: BlockPosition) {
+        self.execute(move |server| {
+            server.player_dig(uuid, status, position);
+            Ok::<(), Infallible>(())
+        });
+    }
+
+    pub fn player_place_block(&self, uuid: Uuid, position: BlockPosition, face: i32, hand: i32) {
+        self.execute(move |server| {
+            server.player_place_block(uuid, position, face, hand);
+            Ok::<(), Infallible>(())
+        });
+    }
+
+    pub fn player_change_held_item(&self, uuid: Uuid, slot: i16) {
+        self.execute(move |server| {
+            server.player_change_held_item(uuid, slot);
+            Ok::<(), Infallible>(())
+        });
+    }
+
+    pub fn player_set_creative_slot(&self, uuid: Uuid, slot: i16, item: Option<i32>) {
+        self.execute(move |server| {
+            server.player_set_creative_slot(uuid, slot, item);
+            Ok::<(), Infallible>(())
+        });
+    }
+
+    pub fn player_chat(&self, uuid: Uuid, message: String) {
+        self.execute(move |server| {
+            server.player_chat(uuid, message);
+            Ok::<(), Infallible>(())
+        });
+    }
+
     pub fn player_leave(&self, uuid: Uuid) {
         self.execute(move |server| {
             server.player_leave(uuid);
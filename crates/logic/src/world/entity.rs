@@ -0,0 +1,46 @@
+use falcon_core::world::chunks::ChunkPos;
+use falcon_core::world::entity::{Entity, EntityKind};
+use falcon_send::specs::play::{EntityMetadataSpec, SpawnEntitySpec};
+use uuid::Uuid;
+
+use crate::player::FalconPlayer;
+
+/// An entity that never moves, it is spawned for players that
+/// load its chunk and destroyed when they unload it.
+#[derive(Debug)]
+pub struct StaticEntity {
+    entity_id: i32,
+    uuid: Uuid,
+    entity: Entity,
+}
+
+impl StaticEntity {
+    /// Entities without a stored uuid get one derived from their id.
+    pub fn new(entity_id: i32, entity: Entity) -> Self {
+        StaticEntity {
+            entity_id,
+            uuid: entity.uuid().unwrap_or_else(|| Uuid::from_u128(entity_id as u128)),
+            entity,
+        }
+    }
+
+    pub fn entity_id(&self) -> i32 { self.entity_id }
+
+    pub fn entity(&self) -> &Entity { &self.entity }
+
+    pub fn chunk_pos(&self) -> ChunkPos { super::chunk_pos(self.entity.block_position()) }
+
+    pub fn spawn(&self, player: &FalconPlayer) {
+        let spawn = SpawnEntitySpec::new(self.entity_id, self.uuid, self.entity.clone());
+        let connection = player.connection();
+        match self.entity.kind() {
+            EntityKind::ArmorStand { .. } => connection.send_packet_deferrable(spawn, falcon_send::write_spawn_living_entity),
+            EntityKind::ItemFrame { .. } => connection.send_packet_deferrable(spawn, falcon_send::write_spawn_object),
+            EntityKind::Painting { .. } => connection.send_packet_deferrable(spawn, falcon_send::write_spawn_painting),
+        }
+        if !matches!(self.entity.kind(), EntityKind::Painting { .. }) {
+            let metadata = EntityMetadataSpec::new(self.entity_id, self.entity.clone());
+            connection.send_packet_deferrable(metadata, falcon_send::write_entity_metadata);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::fmt::Debug;
+
+use falcon_core::world::blocks::{Blocks, ParseBlockError};
+use falcon_core::world::chunks::{Chunk, ChunkPos, ChunkSection, SECTIONS_NUM, SECTION_HEIGHT, SECTION_LENGTH, SECTION_WIDTH};
+use falcon_core::world::dimension::Dimension;
+use falcon_core::world::BlockPosition;
+use thiserror::Error;
+
+/// Creates the chunks of a world that weren't loaded from a file.
+pub trait ChunkGenerator: Debug + Send + Sync {
+    /// The chunk is as high as `dimension`.
+    fn generate(&self, pos: ChunkPos, dimension: &Dimension) -> Chunk;
+}
+
+#[derive(Debug, Error)]
+pub enum ParseLayersError {
+    #[error("Invalid block \"{0}\"")]
+    Block(String, #[source] ParseBlockError),
+    #[error("Invalid layer count \"{0}\"")]
+    Count(String),
+    #[error("The layers are higher than the world")]
+    TooHigh,
+}
+
+/// A superflat world, every chunk has the same layers starting at y 0.
+#[derive(Debug)]
+pub struct FlatGenerator {
+    sections: Vec<ChunkSection>,
+}
+
+impl FlatGenerator {
+    /// The layers from the bottom up.
+    pub fn new(layers: &[Blocks]) -> Result<Self, ParseLayersError> {
+        if layers.len() > (SECTIONS_NUM * SECTION_HEIGHT) as usize {
+            return Err(ParseLayersError::TooHigh);
+        }
+        let sections = layers
+            .chunks(SECTION_HEIGHT as usize)
+            .map(|layers| {
+                let mut section = ChunkSection::empty();
+                for (y, block) in layers.iter().enumerate() {
+                    for x in 0..SECTION_WIDTH {
+                        for z in 0..SECTION_LENGTH {
+                            section.set_block_at(x, y as u16, z, *block);
+                        }
+                    }
+                }
+                section
+            })
+            .collect();
+        Ok(FlatGenerator { sections })
+    }
+
+    /// Parses layers like `minecraft:bedrock,2*dirt,grass_block`,
+    /// from the bottom up.
+    pub fn from_layers(layers: &str) -> Result<Self, ParseLayersError> {
+        let mut blocks = Vec::new();
+        for layer in split_layers(layers) {
+            let (count, block) = match layer.split_once('*') {
+                Some((count, block)) => (count.trim().parse().map_err(|_| ParseLayersError::Count(count.to_owned()))?, block.trim()),
+                None => (1usize, layer.trim()),
+            };
+            if count > (SECTIONS_NUM * SECTION_HEIGHT) as usize {
+                return Err(ParseLayersError::TooHigh);
+            }
+            let block = Blocks::parse_state(block).map_err(|error| ParseLayersError::Block(block.to_owned(), error))?;
+            blocks.extend(std::iter::repeat_n(block, count));
+        }
+        FlatGenerator::new(&blocks)
+    }
+}
+
+impl ChunkGenerator for FlatGenerator {
+    fn generate(&self, pos: ChunkPos, dimension: &Dimension) -> Chunk {
+        let mut chunk = Chunk::empty(pos, dimension);
+        for (section_y, section) in (0..).zip(&self.sections) {
+            chunk.set_section(section_y, section.clone());
+        }
+        chunk.mark_dirty(false);
+        chunk
+    }
+}
+
+/// An empty world with a square platform players can stand on.
+#[derive(Debug)]
+pub struct VoidGenerator {
+    centre: BlockPosition,
+    radius: i32,
+    block: Blocks,
+}
+
+impl VoidGenerator {
+    /// The platform is `2 * radius + 1` blocks wide, centred at `centre`.
+    pub fn new(centre: BlockPosition, radius: u8, block: Blocks) -> Self {
+        VoidGenerator {
+            centre,
+            radius: radius as i32,
+            block,
+        }
+    }
+}
+
+impl ChunkGenerator for VoidGenerator {
+    fn generate(&self, pos: ChunkPos, dimension: &Dimension) -> Chunk {
+        let mut chunk = Chunk::empty(pos, dimension);
+        let y = self.centre.get_y();
+        let (min_x, min_z) = (pos.x * SECTION_WIDTH as i32, pos.z * SECTION_LENGTH as i32);
+        for x in (self.centre.get_x() - self.radius).max(min_x)..=(self.centre.get_x() + self.radius).min(min_x + SECTION_WIDTH as i32 - 1) {
+            for z in (self.centre.get_z() - self.radius).max(min_z)..=(self.centre.get_z() + self.radius).min(min_z + SECTION_LENGTH as i32 - 1) {
+                chunk.set_block_at((x - min_x) as u16, y, (z - min_z) as u16, self.block);
+            }
+        }
+        chunk.mark_dirty(false);
+        chunk
+    }
+}
+
+/// Splits at commas that aren't part of block properties.
+fn split_layers(layers: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    layers
+        .split(move |c| {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {},
+            }
+            c == ',' && depth == 0
+        })
+        .filter(|layer| !layer.trim().is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use falcon_core::world::blocks::Blocks;
+    use falcon_core::world::chunks::ChunkPos;
+    use falcon_core::world::dimension::Dimension;
+    use falcon_core::world::BlockPosition;
+
+    use super::{ChunkGenerator, FlatGenerator, VoidGenerator};
+
+    #[test]
+    pub fn test_flat_generator() {
+        let generator = FlatGenerator::from_layers("minecraft:bedrock,2*dirt, 15*stone,oak_slab[type=top,waterlogged=false]").unwrap();
+        let chunk = generator.generate(ChunkPos::new(3, -7), &Dimension::overworld());
+        assert!(!chunk.is_dirty());
+        assert_eq!(chunk.get_bit_mask(), 0b11);
+        let sections = chunk.get_chunk_sections();
+        let lower = sections[0].as_ref().unwrap();
+        assert_eq!(*lower.block_at(4, 0, 9), Blocks::Bedrock);
+        assert_eq!(*lower.block_at(0, 2, 15), Blocks::Dirt);
+        assert_eq!(*lower.block_at(0, 15, 15), Blocks::Stone);
+        assert_eq!(sections[1].as_ref().unwrap().get_block_count(), 16 * 16 * 3);
+        let chunk = generator.generate(ChunkPos::new(0, 0), &Dimension::overworld().with_height(-64, 384));
+        assert_eq!(chunk.min_section(), -4);
+        assert_eq!(chunk.block_at(0, 0, 0), Blocks::Bedrock);
+        assert_eq!(chunk.block_at(0, -64, 0), Blocks::Air);
+
+        assert!(FlatGenerator::from_layers("x*dirt").is_err());
+        assert!(FlatGenerator::from_layers("cheese").is_err());
+        assert!(FlatGenerator::from_layers("200*dirt,100*stone").is_err());
+    }
+
+    #[test]
+    pub fn test_void_generator() {
+        let generator = VoidGenerator::new(BlockPosition::new(15, 64, 0), 1, Blocks::Stone);
+        let chunk = generator.generate(ChunkPos::new(0, 0), &Dimension::overworld());
+        assert_eq!(chunk.get_chunk_sections()[4].as_ref().unwrap().get_block_count(), 4);
+        assert_eq!(
+            generator
+                .generate(ChunkPos::new(1, -1), &Dimension::overworld())
+                .get_chunk_sections()[4]
+                .as_ref()
+                .unwrap()
+                .get_block_count(),
+            1
+        );
+        assert_eq!(generator.generate(ChunkPos::new(2, 0), &Dimension::overworld()).get_bit_mask(), 0);
+    }
+}
@@ -1,15 +1,43 @@
-use ahash::AHashMap;
+use std::ops::Range;
+use std::path::Path;
+
+use ahash::{AHashMap, AHashSet};
 use falcon_core::error::FalconCoreError;
 use falcon_core::network::util::read_var_i32_from_iter;
 use falcon_core::schematic::SchematicData;
+use falcon_core::world::biome::Biome;
 use falcon_core::world::blocks::Blocks;
-use falcon_core::world::chunks::{Chunk, ChunkPos, SECTION_LENGTH, SECTION_WIDTH};
-use falcon_send::specs::play::ChunkDataSpec;
+use falcon_core::world::chunks::{Chunk, ChunkPos, ChunkSection, BIOME_CELL_SIZE, SECTIONS_NUM, SECTION_HEIGHT, SECTION_LENGTH, SECTION_WIDTH};
+use falcon_core::world::dimension::Dimension;
+use falcon_core::world::entity::Entity;
+use falcon_core::world::light::compute_light;
+use falcon_core::world::BlockPosition;
+use falcon_packet_core::WriteError;
+use falcon_send::specs::play::{BlockChangeSpec, ChunkDataSpec, MultiBlockChangeSpec, UpdateLightSpec};
 use itertools::Itertools;
-use tracing::debug;
+use tracing::{debug, warn};
 
+pub use self::entity::StaticEntity;
+pub use self::generator::{ChunkGenerator, FlatGenerator, ParseLayersError, VoidGenerator};
+pub use self::region::{parse_block, Clipboard, Edit, EditError, EditSession, Region};
+use self::save::SaveFormat;
+pub use self::save::WorldSave;
+use crate::connection::writer::SocketWrite;
+use crate::metrics::metrics;
 use crate::player::FalconPlayer;
 
+mod entity;
+mod generator;
+mod region;
+mod save;
+
+/// Changed blocks in a section from which on the whole chunk is resent
+/// instead, like vanilla servers do.
+pub const RESEND_THRESHOLD: usize = 64;
+
+/// Changed positions in a section, x and z relative to the chunk.
+type SectionChanges = AHashSet<(u16, i32, u16)>;
+
 #[derive(Debug)]
 pub struct FalconWorld {
     min_x: i32,
@@ -17,6 +45,22 @@ pub struct FalconWorld {
     max_x: i32,
     max_z: i32,
     chunks: AHashMap<ChunkPos, Chunk>,
+    dimension: Dimension,
+    default_biome: Biome,
+    entities: AHashMap<ChunkPos, Vec<StaticEntity>>,
+    /// Entities that didn't get an entity id yet.
+    pending_entities: Vec<Entity>,
+    generator: Option<Box<dyn ChunkGenerator>>,
+    /// Chunks made by the generator, they are only saved once they change
+    /// and dropped once no player sees them.
+    generated: AHashMap<ChunkPos, Chunk>,
+    /// Blocks changed since they were last sent, per chunk and section. The
+    /// per section Multi Block Change of 1.16.2 and later isn't written as
+    /// the supported versions end at 1.16.1, every batch is one section.
+    block_changes: AHashMap<(ChunkPos, i32), SectionChanges>,
+    /// Where the schematic the world was loaded from starts and its size,
+    /// the world is saved as a schematic with the same bounds.
+    schematic_bounds: Option<(BlockPosition, [u16; 3])>,
 }
 
 impl FalconWorld {
@@ -27,6 +71,206 @@ impl FalconWorld {
             max_x,
             max_z,
             chunks: AHashMap::with_capacity(capacity),
+            dimension: Dimension::overworld(),
+            default_biome: Biome::default(),
+            entities: AHashMap::new(),
+            pending_entities: Vec::new(),
+            generator: None,
+            generated: AHashMap::new(),
+            block_changes: AHashMap::new(),
+            schematic_bounds: None,
+        }
+    }
+
+    /// Creates a world from already loaded chunks, the bounds
+    /// are taken from the chunk positions.
+    pub fn from_chunks(chunks: Vec<Chunk>) -> Self {
+        let mut world = FalconWorld::new(chunks.len(), 0, 0, 0, 0);
+        if let Some(first) = chunks.first() {
+            let pos = *first.get_position();
+            (world.min_x, world.min_z, world.max_x, world.max_z) = (pos.x, pos.z, pos.x, pos.z);
+        }
+        for chunk in chunks {
+            let pos = *chunk.get_position();
+            world.min_x = world.min_x.min(pos.x);
+            world.min_z = world.min_z.min(pos.z);
+            world.max_x = world.max_x.max(pos.x);
+            world.max_z = world.max_z.max(pos.z);
+            world.chunks.insert(pos, chunk);
+        }
+        debug!(count = world.chunks.len(), "Loaded chunks.");
+        world
+    }
+
+    /// Creates a world from a schematic, placing its first block at `origin`.
+    /// Blocks that end up outside the world height are dropped, unknown
+    /// biomes become `default_biome`.
+    #[tracing::instrument(name = "world_loading", skip(schematic))]
+    pub fn from_schematic(schematic: SchematicData, origin: [i32; 3], default_biome: Biome) -> Result<Self, FalconCoreError> {
+        let [origin_x, origin_y, origin_z] = origin;
+        let chunk_x = |x: usize| (origin_x + x as i32).div_euclid(SECTION_WIDTH as i32);
+        let chunk_z = |z: usize| (origin_z + z as i32).div_euclid(SECTION_LENGTH as i32);
+        let (min_x, min_z) = (chunk_x(0), chunk_z(0));
+        let (max_x, max_z) = (chunk_x((schematic.width as usize).max(1) - 1), chunk_z((schematic.length as usize).max(1) - 1));
+        debug!(min_x, min_z, max_x, max_z, "World bounds");
+
+        // schematic indices are small, so a table maps them to dense palette
+        // indices. u16::MAX marks positions the schematic doesn't cover.
+        let mut palette: Vec<(i32, Blocks)> = schematic.palette.iter().map(|(index, block)| (*index, *block)).collect();
+        palette.sort_unstable_by_key(|(index, _)| *index);
+        let table_len = match (palette.first(), palette.last()) {
+            (Some((min, _)), Some((max, _))) if *min >= 0 && *max < u16::MAX as i32 => *max as usize + 1,
+            (None, None) => 0,
+            _ => return Err(FalconCoreError::InvalidData(String::from("Invalid schematic data, palette index out of range!!"))),
+        };
+        let mut dense = vec![u16::MAX; table_len];
+        for (value, (index, _)) in palette.iter().enumerate() {
+            dense[*index as usize] = value as u16;
+        }
+        let palette: Vec<Blocks> = palette.into_iter().map(|(_, block)| block).collect();
+        let mut schematic_blocks = schematic
+            .block_data
+            .iter()
+            .map(|b| b as u8)
+            .batching(|iter| read_var_i32_from_iter(iter));
+
+        let mut outside = 0usize;
+        let capacity = ((max_x - min_x + 1) * (max_z - min_z + 1)) as usize;
+        let mut world = FalconWorld::new(capacity, min_x, min_z, max_x, max_z);
+        world.schematic_bounds = Some((BlockPosition::new(origin_x, origin_y, origin_z), [schematic.width, schematic.height, schematic.length]));
+        let world_height = world.height_range();
+        // the schematic is read one section high layer at a time so whole sections can
+        // be built at once
+        let (width, length) = (schematic.width as usize, schematic.length as usize);
+        let mut layer = vec![u16::MAX; width * length * SECTION_HEIGHT as usize];
+        let mut layer_y = None;
+        for y in 0..schematic.height as usize {
+            let world_y = origin_y + y as i32;
+            let inside = world_height.contains(&world_y);
+            let section_y = world_y.div_euclid(SECTION_HEIGHT as i32);
+            if inside && layer_y != Some(section_y) {
+                if let Some(layer_y) = layer_y {
+                    world.load_section_layer(layer_y, &layer, &palette, [origin_x, origin_z], [width, length]);
+                }
+                layer.fill(u16::MAX);
+                layer_y = Some(section_y);
+            }
+            let row_start = world_y.rem_euclid(SECTION_HEIGHT as i32) as usize * width * length;
+            for i in 0..width * length {
+                let schematic_block = schematic_blocks
+                    .next()
+                    .ok_or_else(|| FalconCoreError::InvalidData(String::from("Invalid world data, fewer blocks than size given!!")))?;
+                let value = match usize::try_from(schematic_block).ok().and_then(|index| dense.get(index)) {
+                    Some(value) if *value != u16::MAX => *value,
+                    _ => return Err(FalconCoreError::InvalidData(String::from("Invalid schematic data, could not find corresponding palette entry!!"))),
+                };
+                if inside {
+                    layer[row_start + i] = value;
+                } else if palette[value as usize] != Blocks::Air {
+                    outside += 1;
+                }
+            }
+        }
+        if let Some(layer_y) = layer_y {
+            world.load_section_layer(layer_y, &layer, &palette, [origin_x, origin_z], [width, length]);
+        }
+        if outside > 0 {
+            warn!(count = outside, "Dropped blocks outside of the world height");
+        }
+        if let Some(biomes) = &schematic.biomes {
+            let palette: AHashMap<i32, Biome> = biomes
+                .palette
+                .iter()
+                .map(|(index, name)| {
+                    let biome = Biome::from_name(name).unwrap_or_else(|| {
+                        warn!(biome = %name, "Unknown biome, using the default biome");
+                        default_biome
+                    });
+                    (*index, biome)
+                })
+                .collect();
+            let mut schematic_biomes = biomes.data.iter().map(|b| b as u8).batching(read_var_i32_from_iter);
+            let height = if biomes.per_block {
+                schematic.height as usize
+            } else {
+                1
+            };
+            for y in 0..height {
+                for z in 0..schematic.length as usize {
+                    for x in 0..schematic.width as usize {
+                        let schematic_biome = schematic_biomes
+                            .next()
+                            .ok_or_else(|| FalconCoreError::InvalidData(String::from("Invalid world data, fewer biomes than size given!!")))?;
+                        let biome = palette.get(&schematic_biome).copied().unwrap_or(default_biome);
+                        // column biomes fill the whole height
+                        let cells = if biomes.per_block {
+                            let cell_y = (origin_y + y as i32).div_euclid(BIOME_CELL_SIZE as i32);
+                            cell_y..cell_y + 1
+                        } else {
+                            world_height.start / BIOME_CELL_SIZE as i32..world_height.end / BIOME_CELL_SIZE as i32
+                        };
+                        let (world_x, world_z) = (origin_x + x as i32, origin_z + z as i32);
+                        let chunk = world.get_chunk_mut(ChunkPos::new(chunk_x(x), chunk_z(z)));
+                        for cell_y in cells {
+                            chunk.set_biome(
+                                world_x.rem_euclid(SECTION_WIDTH as i32) as u16 / BIOME_CELL_SIZE,
+                                cell_y,
+                                world_z.rem_euclid(SECTION_LENGTH as i32) as u16 / BIOME_CELL_SIZE,
+                                biome,
+                                default_biome,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        for block_entity in &schematic.block_entities {
+            let pos = block_entity.position();
+            let (x, y, z) = (pos.get_x(), pos.get_y(), pos.get_z());
+            let world_y = origin_y + y;
+            if !(0..schematic.width as i32).contains(&x) || !(0..schematic.length as i32).contains(&z) || !world_height.contains(&world_y) {
+                continue;
+            }
+            let mut block_entity = block_entity.clone();
+            block_entity.set_position(BlockPosition::new(origin_x + x, world_y, origin_z + z));
+            world
+                .get_chunk_mut(ChunkPos::new(chunk_x(x as usize), chunk_z(z as usize)))
+                .set_block_entity(block_entity);
+        }
+        for entity in &schematic.entities {
+            let mut entity = entity.clone();
+            entity.translate(origin_x, origin_y, origin_z);
+            world.add_entity(entity);
+        }
+        debug!(count = world.chunks.len(), entities = world.pending_entities.len(), "Loaded chunks.");
+        Ok(world)
+    }
+
+    /// Builds the sections at `section_y` of every chunk from a section high
+    /// layer of schematic blocks, the schematic starts at `origin` (x and z).
+    fn load_section_layer(&mut self, section_y: i32, layer: &[u16], palette: &[Blocks], origin: [i32; 2], [width, length]: [usize; 2]) {
+        let mut indices = vec![u16::MAX; (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH) as usize];
+        for chunk_z in self.min_z..=self.max_z {
+            for chunk_x in self.min_x..=self.max_x {
+                for y in 0..SECTION_HEIGHT {
+                    for z in 0..SECTION_LENGTH {
+                        let schematic_z = chunk_z * SECTION_LENGTH as i32 + z as i32 - origin[1];
+                        for x in 0..SECTION_WIDTH {
+                            let schematic_x = chunk_x * SECTION_WIDTH as i32 + x as i32 - origin[0];
+                            indices[ChunkSection::calculate_index(x, y, z)] = match (usize::try_from(schematic_x), usize::try_from(schematic_z)) {
+                                (Ok(schematic_x), Ok(schematic_z)) if schematic_x < width && schematic_z < length => {
+                                    layer[(y as usize * length + schematic_z) * width + schematic_x]
+                                },
+                                _ => u16::MAX,
+                            };
+                        }
+                    }
+                }
+                let section = ChunkSection::from_palette(palette, &indices);
+                if section.get_block_count() > 0 {
+                    self.get_chunk_mut(ChunkPos::new(chunk_x, chunk_z)).set_section(section_y, section);
+                }
+            }
         }
     }
 
@@ -37,19 +281,354 @@ impl FalconWorld {
         self.chunks.get(&pos)
     }
 
-    pub fn get_chunk_mut(&mut self, pos: ChunkPos) -> &mut Chunk { self.chunks.entry(pos).or_insert_with(|| Chunk::empty(pos)) }
+    /// Positions without a loaded chunk get a generated one if the world
+    /// has a generator.
+    pub fn get_or_generate_chunk(&mut self, pos: ChunkPos) -> Option<&Chunk> {
+        if self.get_chunk(pos).is_some() {
+            return self.get_chunk(pos);
+        }
+        if !self.generated.contains_key(&pos) {
+            let mut chunk = self.generator.as_ref()?.generate(pos, &self.dimension);
+            // the light of neighbours isn't updated, a generator makes the same
+            // chunks on both sides of a border
+            chunk.set_light(compute_light(&chunk, self.dimension.has_skylight(), |x, z| self.loaded_chunk(ChunkPos::new(pos.x + x, pos.z + z))));
+            self.generated.insert(pos, chunk);
+        }
+        self.generated.get(&pos)
+    }
+
+    /// Drops the generated chunks none of the players sees, they are
+    /// generated again when needed.
+    pub fn unload_generated<'a>(&mut self, players: impl Iterator<Item = &'a FalconPlayer>) {
+        if self.generated.is_empty() {
+            return;
+        }
+        let players: Vec<_> = players.collect();
+        self.generated
+            .retain(|pos, _| players.iter().any(|player| player.has_chunk_loaded(*pos)));
+    }
+
+    /// A loaded or generated chunk.
+    fn loaded_chunk(&self, pos: ChunkPos) -> Option<&Chunk> { self.chunks.get(&pos).or_else(|| self.generated.get(&pos)) }
+
+    /// Creates the chunk if it doesn't exist yet, growing the world bounds.
+    pub fn get_chunk_mut(&mut self, pos: ChunkPos) -> &mut Chunk {
+        self.min_x = self.min_x.min(pos.x);
+        self.min_z = self.min_z.min(pos.z);
+        self.max_x = self.max_x.max(pos.x);
+        self.max_z = self.max_z.max(pos.z);
+        let (generated, generator, dimension) = (&mut self.generated, &self.generator, &self.dimension);
+        self.chunks.entry(pos).or_insert_with(|| {
+            generated
+                .remove(&pos)
+                .or_else(|| generator.as_ref().map(|generator| generator.generate(pos, dimension)))
+                .unwrap_or_else(|| Chunk::empty(pos, dimension))
+        })
+    }
+
+    /// The block at `pos`, air outside the world height and in
+    /// positions without a chunk.
+    pub fn block_at(&mut self, pos: BlockPosition) -> Blocks {
+        let chunk_pos = chunk_pos(pos);
+        let (x, z) = (pos.get_x().rem_euclid(SECTION_WIDTH as i32) as u16, pos.get_z().rem_euclid(SECTION_LENGTH as i32) as u16);
+        self.get_or_generate_chunk(chunk_pos)
+            .map_or(Blocks::Air, |chunk| chunk.block_at(x, pos.get_y(), z))
+    }
+
+    /// Changes a single block, players get the change with the next
+    /// [`send_block_changes`](Self::send_block_changes). Returns `false`
+    /// for positions outside the world height.
+    pub fn set_block(&mut self, pos: BlockPosition, block: Blocks) -> bool {
+        let y = pos.get_y();
+        if !self.height_range().contains(&y) {
+            return false;
+        }
+        let chunk_pos = chunk_pos(pos);
+        let (x, z) = (pos.get_x().rem_euclid(SECTION_WIDTH as i32) as u16, pos.get_z().rem_euclid(SECTION_LENGTH as i32) as u16);
+        self.get_chunk_mut(chunk_pos).set_block_at(x, y, z, block);
+        self.block_changes
+            .entry((chunk_pos, y.div_euclid(SECTION_HEIGHT as i32)))
+            .or_default()
+            .insert((x, y, z));
+        true
+    }
+
+    /// Sends the changed blocks to the players that have their chunk loaded
+    /// and updates the light of the changed chunks. Single blocks are sent
+    /// as a Block Change and more as a Multi Block Change per section,
+    /// chunks with [`RESEND_THRESHOLD`] changes in a section are resent.
+    pub fn send_block_changes<'a>(&mut self, players: impl Iterator<Item = &'a FalconPlayer>) {
+        if self.block_changes.is_empty() {
+            return;
+        }
+        let (resend, batches) = self.take_block_changes();
+        self.update_light();
+        for player in players {
+            for &pos in resend.iter().filter(|pos| player.has_chunk_loaded(**pos)) {
+                self.send_chunk_data(player, pos.x, pos.z);
+            }
+            for (pos, blocks) in batches.iter().filter(|(pos, _)| player.has_chunk_loaded(*pos)) {
+                if let [(x, y, z, block)] = blocks[..] {
+                    let position = BlockPosition::new(pos.x * SECTION_WIDTH as i32 + x as i32, y as i32, pos.z * SECTION_LENGTH as i32 + z as i32);
+                    let block_change = BlockChangeSpec::new(position, block, player.protocol_version());
+                    player
+                        .connection()
+                        .send_packet_deferrable(block_change, falcon_send::write_block_change);
+                } else {
+                    let multi_block_change = MultiBlockChangeSpec::new(*pos, blocks.clone(), player.protocol_version());
+                    player
+                        .connection()
+                        .send_packet_deferrable(multi_block_change, falcon_send::write_multi_block_change);
+                }
+            }
+        }
+    }
+
+    /// Clears the changed blocks, returns the chunks to resend and the new
+    /// blocks of every other changed section.
+    #[allow(clippy::type_complexity)]
+    fn take_block_changes(&mut self) -> (AHashSet<ChunkPos>, Vec<(ChunkPos, Vec<(u16, u16, u16, Blocks)>)>) {
+        // clients up to 1.16 don't know the sections outside y 0 to 256
+        self.block_changes
+            .retain(|(_, section_y), _| (0..SECTIONS_NUM as i32).contains(section_y));
+        let resend: AHashSet<ChunkPos> = self
+            .block_changes
+            .iter()
+            .filter(|(_, blocks)| blocks.len() >= RESEND_THRESHOLD)
+            .map(|((pos, _), _)| *pos)
+            .collect();
+        let mut batches = Vec::new();
+        for ((pos, _), blocks) in self.block_changes.drain().filter(|((pos, _), _)| !resend.contains(pos)) {
+            let chunk = self.chunks.get(&pos);
+            let blocks = blocks
+                .into_iter()
+                .map(|(x, y, z)| (x, y as u16, z, chunk.map_or(Blocks::Air, |chunk| chunk.block_at(x, y, z))))
+                .collect();
+            batches.push((pos, blocks));
+        }
+        (resend, batches)
+    }
+
+    /// Sends the actual block at `pos` to a player whose client changed it.
+    /// Blocks with a block entity resend their chunk so the client gets
+    /// the block entity back too.
+    pub fn revert_block(&mut self, player: &FalconPlayer, pos: BlockPosition) {
+        let chunk_pos = chunk_pos(pos);
+        let has_block_entity = self
+            .get_or_generate_chunk(chunk_pos)
+            .is_some_and(|chunk| chunk.block_entities().iter().any(|block_entity| block_entity.position() == pos));
+        if has_block_entity {
+            self.update_light();
+            self.send_chunk_data(player, chunk_pos.x, chunk_pos.z);
+        } else {
+            let block_change = BlockChangeSpec::new(pos, self.block_at(pos), player.protocol_version());
+            player
+                .connection()
+                .send_packet_deferrable(block_change, falcon_send::write_block_change);
+        }
+    }
+
+    /// Fills every position without a loaded chunk.
+    pub fn set_generator(&mut self, generator: Box<dyn ChunkGenerator>) {
+        self.generator = Some(generator);
+        self.generated.clear();
+    }
+
+    /// The dimension this world is served as.
+    pub fn dimension(&self) -> &Dimension { &self.dimension }
+
+    /// Chunks get the height of the new dimension, blocks outside of it are
+    /// dropped. Changing the dimension does not update the light of the chunks.
+    pub fn set_dimension(&mut self, dimension: Dimension) {
+        for chunk in self.chunks.values_mut() {
+            chunk.set_height(dimension.min_section(), dimension.section_count());
+        }
+        self.generated.clear();
+        self.dimension = dimension;
+    }
+
+    /// The y of the blocks in the world, from the bottom to the top of the
+    /// dimension.
+    pub fn height_range(&self) -> Range<i32> { self.dimension.min_y()..self.dimension.min_y() + self.dimension.height() }
+
+    /// The biome sent for chunks without biome data.
+    pub fn default_biome(&self) -> Biome { self.default_biome }
+
+    pub fn set_default_biome(&mut self, biome: Biome) { self.default_biome = biome; }
+
+    /// Adds an entity that is sent to players once it got an
+    /// id from [`assign_entity_ids`](Self::assign_entity_ids).
+    pub fn add_entity(&mut self, entity: Entity) { self.pending_entities.push(entity); }
+
+    /// Gives every added entity an id, counting up from `next_id`.
+    pub fn assign_entity_ids(&mut self, next_id: &mut i32) {
+        for entity in self.pending_entities.drain(..) {
+            let entity = StaticEntity::new(*next_id, entity);
+            *next_id += 1;
+            self.entities.entry(entity.chunk_pos()).or_default().push(entity);
+        }
+    }
+
+    /// Computes the light of chunks that changed since their light was last
+    /// computed and of the chunks next to them, as light spreads over
+    /// chunk borders.
+    pub fn update_light(&mut self) {
+        let has_skylight = self.dimension.has_skylight();
+        let outdated: AHashSet<ChunkPos> = self
+            .chunks
+            .values()
+            .filter(|chunk| chunk.light().is_none())
+            .flat_map(|chunk| {
+                let pos = *chunk.get_position();
+                (-1..=1).flat_map(move |x| (-1..=1).map(move |z| ChunkPos::new(pos.x + x, pos.z + z)))
+            })
+            .filter(|pos| self.loaded_chunk(*pos).is_some())
+            .collect();
+        if outdated.is_empty() {
+            return;
+        }
+        let light: Vec<_> = outdated
+            .iter()
+            .filter_map(|&pos| {
+                let chunk = self.loaded_chunk(pos)?;
+                let light = compute_light(chunk, has_skylight, |x, z| self.loaded_chunk(ChunkPos::new(pos.x + x, pos.z + z)));
+                Some((pos, light))
+            })
+            .collect();
+        for (pos, light) in light {
+            if let Some(chunk) = self.chunks.get_mut(&pos).or_else(|| self.generated.get_mut(&pos)) {
+                chunk.set_light(light);
+            }
+        }
+        debug!(count = outdated.len(), "Updated chunk light.");
+    }
+
+    /// Whether any chunk changed since the world was loaded or saved.
+    pub fn is_dirty(&self) -> bool { self.chunks.values().any(|chunk| chunk.is_dirty()) }
+
+    /// Marks every chunk as saved.
+    pub fn mark_saved(&mut self) { self.chunks.values_mut().for_each(|chunk| chunk.mark_dirty(false)); }
+
+    /// Marks chunks as changed again, used when saving them failed.
+    pub fn mark_unsaved(&mut self, chunks: &[ChunkPos]) {
+        for pos in chunks {
+            if let Some(chunk) = self.chunks.get_mut(pos) {
+                chunk.mark_dirty(true);
+            }
+        }
+    }
+
+    /// Copies what has to be saved if any chunk changed and marks the world
+    /// as saved, [`WorldSave::write`] then encodes and writes it.
+    /// A `.schem` path gets a Sponge schematic with the bounds of the
+    /// schematic the world was loaded from, blocks outside of them aren't
+    /// saved. Other worlds are saved as a schematic covering all chunks.
+    /// Anything but `.schem` is written as an Anvil world folder, only its
+    /// dirty chunks are copied.
+    pub fn take_save(&mut self, path: &Path) -> Result<Option<WorldSave>, FalconCoreError> {
+        if !self.is_dirty() {
+            return Ok(None);
+        }
+        let save = if path.extension().is_some_and(|extension| extension == "schem") {
+            let (origin, size) = match self.schematic_bounds {
+                Some(bounds) => bounds,
+                None => {
+                    let too_large = |_| FalconCoreError::InvalidData(String::from("The world is too large to be saved as a schematic"));
+                    let width = u16::try_from((self.max_x as i64 - self.min_x as i64 + 1) * SECTION_WIDTH as i64).map_err(too_large)?;
+                    let length = u16::try_from((self.max_z as i64 - self.min_z as i64 + 1) * SECTION_LENGTH as i64).map_err(too_large)?;
+                    let height = u16::try_from(self.dimension.height()).map_err(too_large)?;
+                    let origin = BlockPosition::new(self.min_x * SECTION_WIDTH as i32, self.dimension.min_y(), self.min_z * SECTION_LENGTH as i32);
+                    (origin, [width, height, length])
+                },
+            };
+            let (min, max) = (chunk_pos(origin), chunk_pos(BlockPosition::new(origin.get_x() + size[0] as i32 - 1, 0, origin.get_z() + size[2] as i32 - 1)));
+            let chunks = self
+                .chunks
+                .iter()
+                .filter(|(pos, _)| (min.x..=max.x).contains(&pos.x) && (min.z..=max.z).contains(&pos.z))
+                .map(|(pos, chunk)| (*pos, chunk.clone()))
+                .collect();
+            let entities = self
+                .entities
+                .values()
+                .flatten()
+                .map(StaticEntity::entity)
+                .chain(&self.pending_entities)
+                .cloned()
+                .collect();
+            let format = SaveFormat::Schematic {
+                origin,
+                size,
+                entities,
+                default_biome: self.default_biome,
+                height_range: self.height_range(),
+            };
+            WorldSave::new(path.to_path_buf(), chunks, format)
+        } else {
+            let chunks = self
+                .chunks
+                .iter()
+                .filter(|(_, chunk)| chunk.is_dirty())
+                .map(|(pos, chunk)| (*pos, chunk.clone()))
+                .collect();
+            WorldSave::new(path.to_path_buf(), chunks, SaveFormat::Anvil)
+        };
+        self.mark_saved();
+        Ok(Some(save))
+    }
+
+    /// Sends the light of a chunk followed by its blocks, positions
+    /// without a chunk are sent as empty chunks.
+    fn send_chunk(&mut self, player: &FalconPlayer, x: i32, z: i32) {
+        self.send_chunk_data(player, x, z);
+        for entity in self.entities.get(&(x, z).into()).into_iter().flatten() {
+            entity.spawn(player);
+        }
+    }
+
+    /// Sends the light and blocks of a chunk without its entities, callers
+    /// [`update_light`](Self::update_light) once before sending chunks.
+    fn send_chunk_data(&mut self, player: &FalconPlayer, x: i32, z: i32) {
+        let has_skylight = self.dimension.has_skylight();
+        let default_biome = self.default_biome;
+        let (light, data) = match self.get_or_generate_chunk((x, z).into()) {
+            Some(chunk) => (UpdateLightSpec::new(chunk, has_skylight), ChunkDataSpec::new(chunk, player.protocol_version(), default_biome)),
+            None => (UpdateLightSpec::empty(x, z, has_skylight), ChunkDataSpec::empty(x, z, player.protocol_version(), default_biome)),
+        };
+        player.connection().send_packet_deferrable(light, falcon_send::write_update_light);
+        player.connection().send_packet_deferrable(data, write_chunk_data);
+    }
+
+    /// Destroys the entities of a chunk before unloading it.
+    fn unload_chunk(&self, player: &FalconPlayer, x: i32, z: i32) {
+        if let Some(entities) = self.entities.get(&(x, z).into()) {
+            let ids = entities.iter().map(StaticEntity::entity_id).collect();
+            player.connection().send_packet_deferrable(ids, falcon_send::write_destroy_entities);
+        }
+        player.connection().send_packet_deferrable((x, z), falcon_send::write_unload_chunk);
+    }
 
     pub fn send_chunks_for_player(&mut self, player: &FalconPlayer) {
         let (chunk_x, chunk_z) = player.position().chunk_coords();
         let view_distance = player.view_distance();
+        self.update_light();
 
         for x in chunk_x - view_distance as i32..=chunk_x + view_distance as i32 {
             for z in chunk_z - view_distance as i32..=chunk_z + view_distance as i32 {
-                let spec = match self.get_chunk((x, z).into()) {
-                    Some(chunk) => ChunkDataSpec::new(chunk, player.protocol_version()),
-                    None => ChunkDataSpec::empty(x, z),
-                };
-                player.connection().send_packet(spec, falcon_send::write_chunk_data);
+                self.send_chunk(player, x, z);
+            }
+        }
+    }
+
+    /// Unloads every chunk the player can see, the counterpart of
+    /// [`send_chunks_for_player`](Self::send_chunks_for_player).
+    pub fn unload_chunks_for_player(&self, player: &FalconPlayer) {
+        let (chunk_x, chunk_z) = player.position().chunk_coords();
+        let view_distance = player.view_distance();
+
+        for x in chunk_x - view_distance as i32..=chunk_x + view_distance as i32 {
+            for z in chunk_z - view_distance as i32..=chunk_z + view_distance as i32 {
+                self.unload_chunk(player, x, z);
             }
         }
     }
@@ -61,19 +640,16 @@ impl FalconWorld {
         for x in old_chunk_x - view_distance as i32..=old_chunk_x + view_distance as i32 {
             for z in old_chunk_z - view_distance as i32..=old_chunk_z + view_distance as i32 {
                 if chunk_x.abs_diff(x) > view_distance as u32 || chunk_z.abs_diff(z) > view_distance as u32 {
-                    player.connection().send_packet((x, z), falcon_send::write_unload_chunk);
+                    self.unload_chunk(player, x, z);
                 }
             }
         }
         // load new chunks
+        self.update_light();
         for x in chunk_x - view_distance as i32..=chunk_x + view_distance as i32 {
             for z in chunk_z - view_distance as i32..=chunk_z + view_distance as i32 {
                 if old_chunk_x.abs_diff(x) > view_distance as u32 || old_chunk_z.abs_diff(z) > view_distance as u32 {
-                    let spec = match self.get_chunk((x, z).into()) {
-                        Some(chunk) => ChunkDataSpec::new(chunk, player.protocol_version()),
-                        None => ChunkDataSpec::empty(x, z),
-                    };
-                    player.connection().send_packet(spec, falcon_send::write_chunk_data);
+                    self.send_chunk(player, x, z);
                 }
             }
         }
@@ -85,14 +661,11 @@ impl FalconWorld {
 
         match old_view_distance.cmp(&view_distance) {
             std::cmp::Ordering::Less => {
+                self.update_light();
                 for x in -(view_distance as i8)..=view_distance as i8 {
                     for z in -(view_distance as i8)..=view_distance as i8 {
                         if x.unsigned_abs() > old_view_distance || z.unsigned_abs() > old_view_distance {
-                            let spec = match self.get_chunk((chunk_x + x as i32, chunk_z + z as i32).into()) {
-                                Some(chunk) => ChunkDataSpec::new(chunk, player.protocol_version()),
-                                None => ChunkDataSpec::empty(chunk_x + x as i32, chunk_z + z as i32),
-                            };
-                            player.connection().send_packet(spec, falcon_send::write_chunk_data);
+                            self.send_chunk(player, chunk_x + x as i32, chunk_z + z as i32);
                         }
                     }
                 }
@@ -101,9 +674,7 @@ impl FalconWorld {
                 for x in -(old_view_distance as i8)..=old_view_distance as i8 {
                     for z in -(old_view_distance as i8)..=old_view_distance as i8 {
                         if x.unsigned_abs() > view_distance || z.unsigned_abs() > view_distance {
-                            player
-                                .connection()
-                                .send_packet((chunk_x + x as i32, chunk_z + z as i32), falcon_send::write_unload_chunk);
+                            self.unload_chunk(player, chunk_x + x as i32, chunk_z + z as i32);
                         }
                     }
                 }
@@ -113,55 +684,195 @@ impl FalconWorld {
     }
 }
 
+/// The chunk a block is in.
+pub fn chunk_pos(pos: BlockPosition) -> ChunkPos { ChunkPos::new(pos.get_x().div_euclid(SECTION_WIDTH as i32), pos.get_z().div_euclid(SECTION_LENGTH as i32)) }
+
+/// Writes a chunk packet, counting it in the metrics if it was written.
+fn write_chunk_data(spec: ChunkDataSpec, buffer: &mut SocketWrite, protocol: i32) -> Result<bool, WriteError> {
+    let written = falcon_send::write_chunk_data(spec, buffer, protocol)?;
+    if written {
+        metrics().chunk_packet_sent();
+    }
+    Ok(written)
+}
+
 impl<'a> TryFrom<SchematicData<'a>> for FalconWorld {
     type Error = FalconCoreError;
 
-    #[tracing::instrument(name = "world_loading", skip_all)]
     fn try_from(schematic: SchematicData<'a>) -> Result<Self, Self::Error> {
-        let rest_x = schematic.width % 16;
-        let rest_z = schematic.length % 16;
-        let count_x = ((schematic.width - rest_x) / 16) as usize + usize::from(rest_x > 0);
-        let count_z = ((schematic.length - rest_z) / 16) as usize + usize::from(rest_z > 0);
-        debug!(x = count_x, z = count_z, "World size");
-
-        let air_value = schematic
-            .palette
-            .iter()
-            .find(|(_, value)| *value == &Blocks::Air)
-            .map(|(index, _)| *index);
-        let mut schematic_blocks = schematic
-            .block_data
+        let offset = schematic.offset;
+        FalconWorld::from_schematic(schematic, offset, Biome::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+    use std::io::Read;
+    use std::path::Path;
+
+    use ahash::AHashMap;
+    use falcon_core::schematic::{SchematicBiomes, SchematicData, SchematicVersionedRaw};
+    use falcon_core::world::biome::Biome;
+    use falcon_core::world::blocks::Blocks;
+    use falcon_core::world::chunks::ChunkPos;
+    use falcon_core::world::dimension::Dimension;
+    use falcon_core::world::entity::Entity;
+    use falcon_core::world::BlockPosition;
+    use fastnbt::borrow::ByteArray;
+    use fastnbt::Value;
+    use flate2::read::GzDecoder;
+
+    use super::{FalconWorld, FlatGenerator};
+
+    #[test]
+    pub fn test_schematic_origin() {
+        let palette = AHashMap::from_iter([(0, Blocks::Air), (1, Blocks::Stone), (2, Blocks::Granite)]);
+        let data = [1, 2, 1, 2];
+        let schematic = SchematicData::new(2, 2, 1, [0, 0, 0], palette, ByteArray::new(&data));
+        let world = FalconWorld::from_schematic(schematic, [-1, 255, 15], Biome::default()).unwrap();
+
+        assert_eq!((world.min_x, world.min_z, world.max_x, world.max_z), (-1, 0, 0, 0));
+        let west = world.get_chunk(ChunkPos::new(-1, 0)).unwrap();
+        assert_eq!(*west.get_chunk_sections()[15].as_ref().unwrap().block_at(15, 15, 15), Blocks::Stone);
+        let east = world.get_chunk(ChunkPos::new(0, 0)).unwrap();
+        assert_eq!(*east.get_chunk_sections()[15].as_ref().unwrap().block_at(0, 15, 15), Blocks::Granite);
+        assert_eq!(east.get_chunk_sections()[15].as_ref().unwrap().get_block_count(), 1);
+    }
+    #[test]
+    pub fn test_schematic_biomes() {
+        let palette = AHashMap::from_iter([(0, Blocks::Air)]);
+        let (blocks, biomes) = ([0; 5], [0, 0, 0, 0, 1]);
+        let schematic = SchematicData::new(5, 1, 1, [0, 0, 0], palette, ByteArray::new(&blocks)).with_biomes(SchematicBiomes {
+            palette: AHashMap::from_iter([(0, Cow::Borrowed("minecraft:desert")), (1, Cow::Borrowed("minecraft:cheese"))]),
+            data: ByteArray::new(&biomes),
+            per_block: false,
+        });
+        let world = FalconWorld::from_schematic(schematic, [0, 0, 0], Biome::Ocean).unwrap();
+
+        let chunk = world.get_chunk(ChunkPos::new(0, 0)).unwrap();
+        assert_eq!(chunk.biome_at(0, 0, 0), Some(Biome::Desert));
+        assert_eq!(chunk.biome_at(0, 63, 0), Some(Biome::Desert));
+        assert_eq!(chunk.biome_at(1, 10, 0), Some(Biome::Ocean));
+        assert_eq!(chunk.biome_at(3, 10, 3), Some(Biome::Ocean));
+    }
+
+    #[test]
+    pub fn test_schematic_entities() {
+        let palette = AHashMap::from_iter([(0, Blocks::Air)]);
+        let blocks = [0];
+        let stand = Value::Compound(
+            [
+                (String::from("Id"), Value::String(String::from("minecraft:armor_stand"))),
+                (String::from("Pos"), Value::List(vec![Value::Double(0.5), Value::Double(1.0), Value::Double(0.5)])),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let schematic = SchematicData::new(1, 1, 1, [0, 0, 0], palette, ByteArray::new(&blocks)).with_entities(vec![Entity::from_schematic(stand).unwrap()]);
+        let mut world = FalconWorld::from_schematic(schematic, [-20, 64, 3], Biome::default()).unwrap();
+        assert!(world.entities.is_empty());
+
+        let mut next_id = 7;
+        world.assign_entity_ids(&mut next_id);
+        assert_eq!(next_id, 8);
+        let entity = &world.entities[&ChunkPos::new(-2, 0)][0];
+        assert_eq!(entity.entity_id(), 7);
+        assert_eq!(entity.entity().position().y, 65.0);
+    }
+
+    #[test]
+    pub fn test_save_schematic() {
+        let palette = AHashMap::from_iter([(0, Blocks::Air), (1, Blocks::Stone)]);
+        let (blocks, biomes) = ([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], [0; 6]);
+        let stand = Value::Compound(
+            [
+                (String::from("Id"), Value::String(String::from("minecraft:armor_stand"))),
+                (String::from("Pos"), Value::List(vec![Value::Double(0.5), Value::Double(1.0), Value::Double(0.5)])),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let schematic = SchematicData::new(3, 2, 2, [0, 0, 0], palette, ByteArray::new(&blocks))
+            .with_biomes(SchematicBiomes {
+                palette: AHashMap::from_iter([(0, Cow::Borrowed("minecraft:desert"))]),
+                data: ByteArray::new(&biomes),
+                per_block: false,
+            })
+            .with_entities(vec![Entity::from_schematic(stand).unwrap()]);
+        let mut world = FalconWorld::from_schematic(schematic, [-20, 64, 3], Biome::default()).unwrap();
+        world.assign_entity_ids(&mut 0);
+
+        let mut data = Vec::new();
+        let save = world.take_save(Path::new("world.schem")).unwrap().unwrap();
+        assert!(!world.is_dirty());
+        GzDecoder::new(&save.write_schematic().unwrap()[..]).read_to_end(&mut data).unwrap();
+        let raw: SchematicVersionedRaw = fastnbt::from_bytes(&data).unwrap();
+        let saved = SchematicData::try_from(raw).unwrap();
+        assert_eq!((saved.width, saved.height, saved.length), (3, 2, 2));
+        assert_eq!(saved.offset, [-20, 64, 3]);
+        let blocks: Vec<_> = saved.block_data.iter().map(|b| saved.palette[&(b as i32)]).collect();
+        assert_eq!((blocks[0], blocks[1], blocks[11]), (Blocks::Stone, Blocks::Air, Blocks::Stone));
+        let saved_biomes = saved.biomes.unwrap();
+        assert!(saved_biomes
+            .data
             .iter()
-            .map(|b| b as u8)
-            .batching(|iter| read_var_i32_from_iter(iter));
+            .all(|b| saved_biomes.palette[&(b as i32)] == "minecraft:desert"));
+        assert_eq!(saved.entities.len(), 1);
+        assert_eq!(saved.entities[0].position().y, 1.0);
+    }
 
-        let mut world = FalconWorld::new(count_x * count_z, 0, 0, count_x as i32, count_z as i32);
-        for y in 0..schematic.height as usize {
-            for z in 0..schematic.length as usize {
-                for x in 0..schematic.width as usize {
-                    let schematic_block = schematic_blocks
-                        .next()
-                        .ok_or_else(|| FalconCoreError::InvalidData(String::from("Invalid world data, fewer blocks than size given!!")))?;
-                    match air_value {
-                        Some(value) if value == schematic_block => {},
-                        _ => {
-                            let chunk_pos = ChunkPos::new((x / SECTION_WIDTH as usize) as i32, (z / SECTION_LENGTH as usize) as i32);
-                            let palette_entry = *schematic.palette.get(&schematic_block).ok_or_else(|| {
-                                FalconCoreError::InvalidData(String::from("Invalid schematic data, could not find corresponding palette entry!!"))
-                            })?;
-                            let chunk = world.get_chunk_mut(chunk_pos);
-                            chunk.set_block_at(
-                                (x as i32 - (chunk_pos.x * SECTION_WIDTH as i32)) as u16,
-                                y as u16,
-                                (z as i32 - (chunk_pos.z * SECTION_LENGTH as i32)) as u16,
-                                palette_entry,
-                            );
-                        },
-                    }
-                }
+    #[test]
+    pub fn test_set_block() {
+        let mut world = FalconWorld::new(0, 0, 0, 0, 0);
+        world.set_generator(Box::new(FlatGenerator::from_layers("bedrock,dirt").unwrap()));
+        let pos = BlockPosition::new(-17, 1, 40);
+        assert_eq!(world.block_at(pos), Blocks::Dirt);
+        assert!(!world.is_dirty());
+
+        assert!(world.set_block(pos, Blocks::Stone));
+        assert_eq!(world.block_at(pos), Blocks::Stone);
+        assert_eq!(world.block_at(BlockPosition::new(-18, 1, 40)), Blocks::Dirt);
+        assert!(world.is_dirty());
+        assert!(world.get_chunk(ChunkPos::new(-2, 2)).unwrap().light().is_none());
+        assert!(!world.set_block(BlockPosition::new(0, 256, 0), Blocks::Stone));
+        assert_eq!(world.block_at(BlockPosition::new(0, -1, 0)), Blocks::Air);
+    }
+
+    #[test]
+    pub fn test_block_changes() {
+        let mut world = FalconWorld::new(0, 0, 0, 0, 0);
+        world.set_block(BlockPosition::new(1, 2, 3), Blocks::Stone);
+        world.set_block(BlockPosition::new(1, 2, 3), Blocks::Granite);
+        world.set_block(BlockPosition::new(1, 20, 3), Blocks::Stone);
+        for x in 0..8 {
+            for z in 0..8 {
+                world.set_block(BlockPosition::new(16 + x, 0, z), Blocks::Stone);
             }
         }
-        debug!(count = world.chunks.len(), "Loaded chunks.");
-        Ok(world)
+        let (resend, mut batches) = world.take_block_changes();
+        assert!(world.block_changes.is_empty());
+        assert_eq!(resend.into_iter().collect::<Vec<_>>(), [ChunkPos::new(1, 0)]);
+        batches.sort_by_key(|(_, blocks)| blocks[0].1);
+        assert_eq!(batches, [(ChunkPos::new(0, 0), vec![(1, 2, 3, Blocks::Granite)]), (ChunkPos::new(0, 0), vec![(1, 20, 3, Blocks::Stone)])]);
+    }
+
+    #[test]
+    pub fn test_world_height() {
+        let mut world = FalconWorld::new(0, 0, 0, 0, 0);
+        world.set_dimension(Dimension::overworld().with_height(-64, 384));
+        let below = BlockPosition::new(3, -40, -5);
+        assert!(world.set_block(below, Blocks::Stone));
+        assert!(world.set_block(BlockPosition::new(3, 300, -5), Blocks::Stone));
+        assert!(!world.set_block(BlockPosition::new(3, 320, -5), Blocks::Stone));
+        assert_eq!(world.block_at(below), Blocks::Stone);
+        assert_eq!(world.get_chunk(ChunkPos::new(0, -1)).unwrap().get_bit_mask(), 0);
+        // clients only get changes from y 0 to 256
+        let (resend, batches) = world.take_block_changes();
+        assert!(resend.is_empty() && batches.is_empty());
+
+        world.set_dimension(Dimension::overworld());
+        assert_eq!(world.block_at(below), Blocks::Air);
+        assert_eq!(world.get_chunk(ChunkPos::new(0, -1)).unwrap().section_count(), 16);
     }
 }
@@ -0,0 +1,255 @@
+use std::collections::VecDeque;
+
+use falcon_core::world::blocks::{Blocks, ParseBlockError};
+use falcon_core::world::BlockPosition;
+use itertools::iproduct;
+use thiserror::Error;
+
+use super::FalconWorld;
+
+/// The most blocks a single region operation may change.
+pub const MAX_VOLUME: u64 = 1 << 20;
+/// The number of edits that can be undone per session.
+pub const HISTORY_SIZE: usize = 16;
+
+#[derive(Debug, Error)]
+pub enum EditError {
+    #[error("Invalid block \"{0}\"")]
+    Block(String, #[source] ParseBlockError),
+    #[error("The region has {0} blocks, at most {MAX_VOLUME} can be changed at once")]
+    TooLarge(u64),
+    #[error("Select both corners with //pos1 and //pos2 first")]
+    NoSelection,
+    #[error("The clipboard is empty, use //copy first")]
+    EmptyClipboard,
+    #[error("There is nothing to undo")]
+    NothingToUndo,
+    #[error("There is no world called \"{0}\"")]
+    UnknownWorld(String),
+    #[error("You may not edit this world")]
+    NotAllowed,
+    #[error("Usage: {0}")]
+    Usage(&'static str),
+}
+
+/// A cuboid between two corners, both corners are part of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    min: BlockPosition,
+    max: BlockPosition,
+}
+
+impl Region {
+    pub fn new(a: BlockPosition, b: BlockPosition) -> Self {
+        Region {
+            min: BlockPosition::new(a.get_x().min(b.get_x()), a.get_y().min(b.get_y()), a.get_z().min(b.get_z())),
+            max: BlockPosition::new(a.get_x().max(b.get_x()), a.get_y().max(b.get_y()), a.get_z().max(b.get_z())),
+        }
+    }
+
+    pub fn min(&self) -> BlockPosition { self.min }
+
+    pub fn max(&self) -> BlockPosition { self.max }
+
+    pub fn volume(&self) -> u64 {
+        let length = |min: i32, max: i32| (max as i64 - min as i64 + 1) as u64;
+        length(self.min.get_x(), self.max.get_x()) * length(self.min.get_y(), self.max.get_y()) * length(self.min.get_z(), self.max.get_z())
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = BlockPosition> {
+        let (min, max) = (self.min, self.max);
+        iproduct!(min.get_y()..=max.get_y(), min.get_z()..=max.get_z(), min.get_x()..=max.get_x()).map(|(y, z, x)| BlockPosition::new(x, y, z))
+    }
+
+    fn check_volume(&self) -> Result<(), EditError> {
+        match self.volume() {
+            volume if volume > MAX_VOLUME => Err(EditError::TooLarge(volume)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The blocks an edit replaced, used to undo it.
+#[derive(Debug, Default)]
+pub struct Edit {
+    previous: Vec<(BlockPosition, Blocks)>,
+}
+
+impl Edit {
+    /// The number of blocks that changed.
+    pub fn len(&self) -> usize { self.previous.len() }
+
+    pub fn is_empty(&self) -> bool { self.previous.is_empty() }
+}
+
+/// Copied blocks, relative to the position they were copied from.
+#[derive(Clone, Debug)]
+pub struct Clipboard {
+    blocks: Vec<([i32; 3], Blocks)>,
+}
+
+impl Clipboard {
+    pub fn len(&self) -> usize { self.blocks.len() }
+
+    pub fn is_empty(&self) -> bool { self.blocks.is_empty() }
+}
+
+/// The selection, clipboard and undo history of a player or the console.
+#[derive(Debug, Default)]
+pub struct EditSession {
+    pos1: Option<BlockPosition>,
+    pos2: Option<BlockPosition>,
+    clipboard: Option<Clipboard>,
+    /// Edits with the name of their world, the latest last.
+    history: VecDeque<(String, Edit)>,
+}
+
+impl EditSession {
+    pub fn set_pos1(&mut self, pos: BlockPosition) { self.pos1 = Some(pos); }
+
+    pub fn set_pos2(&mut self, pos: BlockPosition) { self.pos2 = Some(pos); }
+
+    pub fn selection(&self) -> Result<Region, EditError> {
+        match (self.pos1, self.pos2) {
+            (Some(pos1), Some(pos2)) => Ok(Region::new(pos1, pos2)),
+            _ => Err(EditError::NoSelection),
+        }
+    }
+
+    pub fn clipboard(&self) -> Result<&Clipboard, EditError> { self.clipboard.as_ref().ok_or(EditError::EmptyClipboard) }
+
+    pub fn set_clipboard(&mut self, clipboard: Clipboard) { self.clipboard = Some(clipboard); }
+
+    /// Remembers an edit, forgetting the oldest one once
+    /// there are [`HISTORY_SIZE`] edits.
+    pub fn remember(&mut self, world: &str, edit: Edit) {
+        if edit.is_empty() {
+            return;
+        }
+        if self.history.len() == HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.history.push_back((world.to_string(), edit));
+    }
+
+    /// The latest edit and the name of its world.
+    pub fn take_last_edit(&mut self) -> Result<(String, Edit), EditError> { self.history.pop_back().ok_or(EditError::NothingToUndo) }
+
+    /// Forgets the edits of a world, they can't be undone once
+    /// it got replaced.
+    pub fn forget_world(&mut self, world: &str) { self.history.retain(|(name, _)| name != world); }
+}
+
+/// Parses a block like `stone` or `minecraft:oak_slab[type=top]`.
+pub fn parse_block(block: &str) -> Result<Blocks, EditError> { Blocks::parse_state(block).map_err(|error| EditError::Block(block.to_string(), error)) }
+
+impl FalconWorld {
+    /// Fills the region with `block`.
+    pub fn set_region(&mut self, region: Region, block: Blocks) -> Result<Edit, EditError> {
+        region.check_volume()?;
+        Ok(self.apply_changes(region.positions().map(|pos| (pos, block))))
+    }
+
+    /// Replaces every `from` block in the region with `to`.
+    pub fn replace_region(&mut self, region: Region, from: Blocks, to: Blocks) -> Result<Edit, EditError> {
+        region.check_volume()?;
+        let positions: Vec<_> = region.positions().filter(|pos| self.block_at(*pos) == from).collect();
+        Ok(self.apply_changes(positions.into_iter().map(|pos| (pos, to))))
+    }
+
+    /// Copies the region, the blocks are stored relative to `origin`.
+    pub fn copy_region(&mut self, region: Region, origin: BlockPosition) -> Result<Clipboard, EditError> {
+        region.check_volume()?;
+        let blocks = region
+            .positions()
+            .map(|pos| {
+                let offset = [pos.get_x() - origin.get_x(), pos.get_y() - origin.get_y(), pos.get_z() - origin.get_z()];
+                (offset, self.block_at(pos))
+            })
+            .collect();
+        Ok(Clipboard { blocks })
+    }
+
+    /// Places the copied blocks relative to `origin`.
+    pub fn paste(&mut self, clipboard: &Clipboard, origin: BlockPosition) -> Edit {
+        let changes = clipboard
+            .blocks
+            .iter()
+            .map(|([x, y, z], block)| (BlockPosition::new(origin.get_x() + x, origin.get_y() + y, origin.get_z() + z), *block));
+        self.apply_changes(changes)
+    }
+
+    /// Puts back the blocks an edit replaced.
+    pub fn undo(&mut self, edit: Edit) {
+        for (pos, block) in edit.previous.into_iter().rev() {
+            self.set_block(pos, block);
+        }
+    }
+
+    /// Changes the blocks that differ, players get them in batches with the
+    /// next [`send_block_changes`](Self::send_block_changes).
+    fn apply_changes(&mut self, changes: impl Iterator<Item = (BlockPosition, Blocks)>) -> Edit {
+        let mut edit = Edit::default();
+        for (pos, block) in changes {
+            let previous = self.block_at(pos);
+            if previous != block && self.set_block(pos, block) {
+                edit.previous.push((pos, previous));
+            }
+        }
+        edit
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use falcon_core::world::blocks::Blocks;
+    use falcon_core::world::BlockPosition;
+
+    use super::{parse_block, EditSession, Region, HISTORY_SIZE};
+    use crate::world::FalconWorld;
+
+    #[test]
+    pub fn test_region_edits() {
+        let mut world = FalconWorld::new(0, 0, 0, 0, 0);
+        let region = Region::new(BlockPosition::new(17, 2, -1), BlockPosition::new(15, 0, 0));
+        assert_eq!(region.volume(), 18);
+        assert_eq!(world.set_region(region, Blocks::Stone).unwrap().len(), 18);
+        assert!(world
+            .set_region(Region::new(BlockPosition::new(0, 0, 0), BlockPosition::new(1024, 0, 1024)), Blocks::Stone)
+            .is_err());
+
+        world.set_block(BlockPosition::new(16, 1, 0), Blocks::Dirt);
+        let edit = world.replace_region(region, Blocks::Stone, Blocks::Granite).unwrap();
+        assert_eq!(edit.len(), 17);
+        assert_eq!(world.block_at(BlockPosition::new(16, 1, 0)), Blocks::Dirt);
+        world.undo(edit);
+        assert_eq!(world.block_at(BlockPosition::new(15, 2, -1)), Blocks::Stone);
+
+        let clipboard = world.copy_region(region, BlockPosition::new(15, 0, -1)).unwrap();
+        let edit = world.paste(&clipboard, BlockPosition::new(0, 254, 0));
+        assert_eq!(edit.len(), 12);
+        assert_eq!(world.block_at(BlockPosition::new(1, 255, 1)), Blocks::Dirt);
+        world.undo(edit);
+        assert_eq!(world.block_at(BlockPosition::new(1, 255, 1)), Blocks::Air);
+    }
+
+    #[test]
+    pub fn test_edit_session() {
+        let mut world = FalconWorld::new(0, 0, 0, 0, 0);
+        let mut session = EditSession::default();
+        assert!(session.selection().is_err());
+        session.set_pos1(BlockPosition::new(0, 0, 0));
+        session.set_pos2(BlockPosition::new(0, 0, 0));
+        let block = parse_block("minecraft:stone").unwrap();
+        for _ in 0..=HISTORY_SIZE {
+            let edit = world.set_region(session.selection().unwrap(), block).unwrap();
+            session.remember("world", edit);
+            world.set_block(BlockPosition::new(0, 0, 0), Blocks::Air);
+        }
+        assert_eq!(session.history.len(), HISTORY_SIZE);
+        assert_eq!(session.take_last_edit().unwrap().0, "world");
+        session.forget_world("world");
+        assert!(session.take_last_edit().is_err());
+        assert!(parse_block("cheese").is_err());
+    }
+}
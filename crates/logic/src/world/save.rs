@@ -0,0 +1,134 @@
+use std::ops::Range;
+use std::path::PathBuf;
+
+use ahash::AHashMap;
+use falcon_core::error::FalconCoreError;
+use falcon_core::schematic::write_sponge_v2;
+use falcon_core::world::biome::Biome;
+use falcon_core::world::blocks::Blocks;
+use falcon_core::world::chunks::{Chunk, ChunkPos, BIOME_CELL_SIZE, SECTION_LENGTH, SECTION_WIDTH};
+use falcon_core::world::entity::Entity;
+use falcon_core::world::{anvil, write_atomically, BlockPosition};
+use tracing::debug;
+
+use super::chunk_pos;
+
+/// A copy of the parts of a world that get saved, taken by
+/// [`FalconWorld::take_save`](super::FalconWorld::take_save) so the world
+/// can be encoded and written off the tick thread.
+#[derive(Debug)]
+pub struct WorldSave {
+    path: PathBuf,
+    chunks: AHashMap<ChunkPos, Chunk>,
+    format: SaveFormat,
+}
+
+#[derive(Debug)]
+pub(super) enum SaveFormat {
+    /// Only the dirty chunks are copied.
+    Anvil,
+    /// A Sponge schematic of `size` blocks starting at `origin`.
+    Schematic {
+        origin: BlockPosition,
+        size: [u16; 3],
+        entities: Vec<Entity>,
+        default_biome: Biome,
+        height_range: Range<i32>,
+    },
+}
+
+impl WorldSave {
+    pub(super) fn new(path: PathBuf, chunks: AHashMap<ChunkPos, Chunk>, format: SaveFormat) -> Self {
+        WorldSave {
+            path,
+            chunks,
+            format,
+        }
+    }
+
+    /// The chunks this save contains, they have to be marked
+    /// dirty again if writing it fails.
+    pub fn chunk_positions(&self) -> Vec<ChunkPos> { self.chunks.keys().copied().collect() }
+
+    /// Encodes and writes the world.
+    #[tracing::instrument(name = "world_saving", skip(self), fields(path = %self.path.display()))]
+    pub fn write(self) -> Result<(), FalconCoreError> {
+        match &self.format {
+            SaveFormat::Anvil => {
+                let regions = anvil::save_world(&self.path, self.chunks.values())?;
+                debug!(regions, "Saved regions.");
+            },
+            SaveFormat::Schematic { .. } => write_atomically(&self.path, &self.write_schematic()?)?,
+        }
+        Ok(())
+    }
+
+    /// Writes the blocks, biomes, block entities and entities as a Sponge
+    /// schematic.
+    pub(super) fn write_schematic(&self) -> Result<Vec<u8>, FalconCoreError> {
+        let (origin, size, entities, default_biome, height_range) = match &self.format {
+            SaveFormat::Schematic {
+                origin,
+                size,
+                entities,
+                default_biome,
+                height_range,
+            } => (*origin, *size, entities, *default_biome, height_range),
+            SaveFormat::Anvil => return Err(FalconCoreError::InvalidData(String::from("The world isn't saved as a schematic"))),
+        };
+        let [width, height, length] = size;
+        let (x_range, z_range) = (origin.get_x()..origin.get_x() + width as i32, origin.get_z()..origin.get_z() + length as i32);
+        let y_range = origin.get_y()..origin.get_y() + height as i32;
+        let chunk_at = |x: i32, z: i32| self.chunks.get(&chunk_pos(BlockPosition::new(x, 0, z)));
+        let local = |x: i32, z: i32| (x.rem_euclid(SECTION_WIDTH as i32) as u16, z.rem_euclid(SECTION_LENGTH as i32) as u16);
+
+        // schematics only have biomes per column, the bottom cell in the world is used
+        let biomes = self.chunks.values().any(|chunk| chunk.biomes().is_some()).then(|| {
+            let cell_y = origin
+                .get_y()
+                .clamp(height_range.start, height_range.end - 1)
+                .div_euclid(BIOME_CELL_SIZE as i32);
+            z_range
+                .clone()
+                .flat_map(|z| x_range.clone().map(move |x| (x, z)))
+                .map(|(x, z)| {
+                    let (local_x, local_z) = local(x, z);
+                    chunk_at(x, z)
+                        .and_then(|chunk| chunk.biome_at(local_x / BIOME_CELL_SIZE, cell_y, local_z / BIOME_CELL_SIZE))
+                        .unwrap_or(default_biome)
+                })
+                .collect::<Vec<_>>()
+        });
+        let block_entities: Vec<_> = self
+            .chunks
+            .values()
+            .flat_map(Chunk::block_entities)
+            .filter(|block_entity| {
+                let pos = block_entity.position();
+                x_range.contains(&pos.get_x()) && y_range.contains(&pos.get_y()) && z_range.contains(&pos.get_z())
+            })
+            .map(|block_entity| {
+                let pos = block_entity.position();
+                let mut block_entity = block_entity.clone();
+                block_entity.set_position(BlockPosition::new(pos.get_x() - origin.get_x(), pos.get_y() - origin.get_y(), pos.get_z() - origin.get_z()));
+                block_entity
+            })
+            .collect();
+        let entities: Vec<_> = entities
+            .iter()
+            .map(|entity| {
+                let mut entity = entity.clone();
+                entity.translate(-origin.get_x(), -origin.get_y(), -origin.get_z());
+                entity
+            })
+            .collect();
+
+        let offset = [origin.get_x(), origin.get_y(), origin.get_z()];
+        let block_at = |x: u16, y: u16, z: u16| {
+            let (x, z) = (origin.get_x() + x as i32, origin.get_z() + z as i32);
+            let (local_x, local_z) = local(x, z);
+            chunk_at(x, z).map_or(Blocks::Air, |chunk| chunk.block_at(local_x, origin.get_y() + y as i32, local_z))
+        };
+        write_sponge_v2(size, offset, block_at, biomes.as_deref(), &block_entities, &entities)
+    }
+}
@@ -12,6 +12,7 @@ use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{reload, Layer};
 
 mod error;
+mod metrics;
 mod network;
 mod server;
 
@@ -0,0 +1,148 @@
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::Context;
+use falcon_core::ShutdownHandle;
+use falcon_logic::metrics::{metrics, Metrics};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info};
+
+const MAX_REQUEST_LEN: usize = 4096;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct MetricsListener {
+    shutdown_handle: ShutdownHandle,
+    addr: SocketAddr,
+}
+
+impl MetricsListener {
+    pub async fn start_metrics_listening(shutdown_handle: ShutdownHandle, addr: SocketAddr) {
+        let metrics_listener = MetricsListener {
+            shutdown_handle,
+            addr,
+        };
+
+        metrics_listener.start_listening().await;
+    }
+
+    #[tracing::instrument(name = "metrics", skip(self))]
+    async fn start_listening(mut self) {
+        let listener = match TcpListener::bind(self.addr)
+            .await
+            .with_context(|| format!("Could not bind metrics endpoint to {}!", self.addr))
+        {
+            Ok(listener) => listener,
+            Err(ref error) => {
+                // metrics are optional, keep the server running
+                print_error!(error);
+                return;
+            },
+        };
+        info!("Serving metrics on http://{}/metrics", self.addr);
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown_handle.wait_for_shutdown() => {
+                    break;
+                }
+                connection = listener.accept() => {
+                    match connection {
+                        Ok((socket, addr)) => {
+                            tokio::spawn(async move {
+                                match tokio::time::timeout(REQUEST_TIMEOUT, handle_request(socket)).await {
+                                    Ok(Err(error)) => debug!(address = %addr, %error, "Metrics request failed"),
+                                    Err(_) => debug!(address = %addr, "Metrics request timed out"),
+                                    Ok(Ok(_)) => {},
+                                }
+                            });
+                        },
+                        Err(error) => debug!(%error, "Could not accept metrics connection"),
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn handle_request(mut socket: TcpStream) -> std::io::Result<()> {
+    let mut request = Vec::with_capacity(512);
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        if request.len() >= MAX_REQUEST_LEN || socket.read_buf(&mut request).await? == 0 {
+            return Ok(());
+        }
+    }
+
+    let request_line = request.split(|byte| *byte == b'\r').next().unwrap_or_default();
+    let mut parts = request_line.split(|byte| *byte == b' ');
+    let response = match (parts.next(), parts.next()) {
+        (Some(b"GET"), Some(b"/metrics")) => {
+            let body = render(metrics());
+            format!("HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body)
+        },
+        _ => String::from("HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"),
+    };
+    socket.write_all(response.as_bytes()).await?;
+    socket.shutdown().await
+}
+
+/// Renders all metrics in the Prometheus text exposition format.
+fn render(metrics: &Metrics) -> String {
+    let mut out = String::with_capacity(4096);
+
+    header(&mut out, "falcon_players_online", "gauge", "Players online per protocol version.");
+    for (protocol, count) in metrics.players() {
+        writeln!(out, "falcon_players_online{{protocol=\"{}\"}} {}", protocol, count).unwrap();
+    }
+
+    header(&mut out, "falcon_connections", "gauge", "Open connections per connection state.");
+    for (state, count) in metrics.connections() {
+        writeln!(out, "falcon_connections{{state=\"{}\"}} {}", format!("{:?}", state).to_lowercase(), count).unwrap();
+    }
+
+    header(&mut out, "falcon_tick_duration_seconds", "histogram", "Duration of server ticks.");
+    let ticks = metrics.tick_duration();
+    for (bound, count) in ticks.buckets() {
+        writeln!(out, "falcon_tick_duration_seconds_bucket{{le=\"{}\"}} {}", bound, count).unwrap();
+    }
+    writeln!(out, "falcon_tick_duration_seconds_bucket{{le=\"+Inf\"}} {}", ticks.count()).unwrap();
+    writeln!(out, "falcon_tick_duration_seconds_sum {}", ticks.sum().as_secs_f64()).unwrap();
+    writeln!(out, "falcon_tick_duration_seconds_count {}", ticks.count()).unwrap();
+
+    header(&mut out, "falcon_queued_server_tasks", "gauge", "Server tasks waiting to be run by the server thread.");
+    writeln!(out, "falcon_queued_server_tasks {}", metrics.queued_server_tasks()).unwrap();
+
+    header(&mut out, "falcon_received_bytes_total", "counter", "Bytes received from clients.");
+    writeln!(out, "falcon_received_bytes_total {}", metrics.total_bytes_received()).unwrap();
+    header(&mut out, "falcon_sent_bytes_total", "counter", "Bytes sent to clients.");
+    writeln!(out, "falcon_sent_bytes_total {}", metrics.total_bytes_sent()).unwrap();
+
+    let (uncompressed, compressed) = metrics.compression();
+    header(&mut out, "falcon_compression_input_bytes_total", "counter", "Uncompressed size of compressed packets.");
+    writeln!(out, "falcon_compression_input_bytes_total {}", uncompressed).unwrap();
+    header(&mut out, "falcon_compression_output_bytes_total", "counter", "Compressed size of compressed packets.");
+    writeln!(out, "falcon_compression_output_bytes_total {}", compressed).unwrap();
+    header(&mut out, "falcon_compression_ratio", "gauge", "Compressed size divided by uncompressed size of all compressed packets.");
+    let ratio = if uncompressed == 0 {
+        1.0
+    } else {
+        compressed as f64 / uncompressed as f64
+    };
+    writeln!(out, "falcon_compression_ratio {}", ratio).unwrap();
+
+    header(&mut out, "falcon_chunk_packets_sent_total", "counter", "Chunk data packets sent to clients.");
+    writeln!(out, "falcon_chunk_packets_sent_total {}", metrics.chunk_packets()).unwrap();
+
+    header(&mut out, "falcon_disconnects_total", "counter", "Closed connections per disconnect reason.");
+    for (reason, count) in metrics.disconnects() {
+        writeln!(out, "falcon_disconnects_total{{reason=\"{}\"}} {}", reason.label(), count).unwrap();
+    }
+
+    out
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    writeln!(out, "# HELP {} {}", name, help).unwrap();
+    writeln!(out, "# TYPE {} {}", name, kind).unwrap();
+}
@@ -1,24 +1,30 @@
 use std::thread;
 
 use anyhow::{Context, Result};
+use falcon_core::server::config::FalconConfig;
 use falcon_core::ShutdownHandle;
+use falcon_logic::server::ServerWrapper;
 use tokio::sync::broadcast;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use tracing::{info, trace};
 
+use crate::server::load_configured_world;
+
 pub struct ConsoleListener {
     shutdown_handle: broadcast::Sender<()>,
     console_sender: UnboundedSender<String>,
+    server: ServerWrapper,
 }
 
 impl ConsoleListener {
-    pub fn start_console(shutdown_handle: ShutdownHandle) -> Result<UnboundedReceiver<String>> {
+    pub fn start_console(shutdown_handle: ShutdownHandle, server: ServerWrapper) -> Result<UnboundedReceiver<String>> {
         info!("Starting console thread!");
         let (console_tx, console_rx) = unbounded_channel();
 
         let console = ConsoleListener {
             shutdown_handle: shutdown_handle.into_signal_sender(),
             console_sender: console_tx,
+            server,
         };
         thread::Builder::new()
             .name(String::from("Console listener"))
@@ -37,6 +43,8 @@ impl ConsoleListener {
                 print_error!(e);
                 self.shutdown_handle.send(()).ok();
                 break;
+            } else if let Some(name) = reload_target(&buffer) {
+                self.reload_world(name);
             } else {
                 trace!(input = %buffer, "Sending console input!");
                 if self.console_sender.send(buffer).is_err() {
@@ -45,4 +53,31 @@ impl ConsoleListener {
             }
         }
     }
+
+    /// Loads the world on its own thread, the server swaps
+    /// it in once it is loaded.
+    fn reload_world(&self, name: &str) {
+        info!("Reloading world \"{}\"...", name);
+        let (name, server) = (name.to_string(), self.server.clone());
+        let loader = thread::Builder::new()
+            .name(String::from("World loader"))
+            .spawn(move || match load_configured_world(&name).with_context(|| format!("Could not reload world \"{}\"", name)) {
+                Ok(world) => server.reload_world(name, world),
+                Err(ref e) => print_error!(e),
+            })
+            .with_context(|| "Couldn't start world loader!");
+        if let Err(ref e) = loader {
+            print_error!(e);
+        }
+    }
+}
+
+/// The world to reload for `reload world [name]`.
+fn reload_target(input: &str) -> Option<&str> {
+    let mut args = input.split_whitespace();
+    match (args.next(), args.next(), args.next(), args.next()) {
+        (Some("reload"), Some("world"), None, None) => Some(FalconConfig::DEFAULT_WORLD),
+        (Some("reload"), Some("world"), Some(name), None) => Some(name),
+        _ => None,
+    }
 }
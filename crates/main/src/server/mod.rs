@@ -1,16 +1,28 @@
 use std::io::Read;
+use std::path::Path;
 use std::thread;
 
-use anyhow::{Context, Result};
+use ahash::AHashMap;
+use anyhow::{bail, Context, Result};
+use falcon_core::data::Identifier;
+use falcon_core::player::data::Position;
+use falcon_core::schematic::legacy::{LegacySchematic, LegacySchematicRaw};
 use falcon_core::schematic::{SchematicData, SchematicVersionedRaw};
 use falcon_core::server::config::FalconConfig;
+use falcon_core::world::biome::Biome;
+use falcon_core::world::blocks::Blocks;
+use falcon_core::world::dimension::Dimension;
+use falcon_core::world::items::ItemRegistries;
+use falcon_core::world::{anvil, BlockPosition};
 use falcon_core::ShutdownHandle;
 use falcon_logic::server::ServerWrapper;
+use falcon_logic::world::{ChunkGenerator, FlatGenerator, VoidGenerator};
 use falcon_logic::{FalconServer, FalconWorld};
 use flate2::read::GzDecoder;
 use tokio::sync::mpsc::unbounded_channel;
 use tracing::info;
 
+use crate::metrics::MetricsListener;
 use crate::network::NetworkListener;
 use crate::server::console::ConsoleListener;
 
@@ -19,7 +31,105 @@ pub mod console;
 pub(crate) fn start_server(shutdown_handle: ShutdownHandle) -> Result<()> {
     info!("Starting server thread...");
 
-    let world = match FalconConfig::global().world_file() {
+    let config = FalconConfig::global();
+    let mut worlds = AHashMap::new();
+    worlds.insert(String::from(FalconConfig::DEFAULT_WORLD), load_configured_world(FalconConfig::DEFAULT_WORLD)?);
+    for settings in config.worlds() {
+        if settings.name.is_empty() || settings.name.contains(':') || Identifier::try_from(settings.name.as_str()).is_err() {
+            bail!("Invalid world name \"{}\", only use a-z, 0-9, '_', '-', '.' and '/'", settings.name);
+        }
+        if worlds.contains_key(&settings.name) {
+            bail!("The world name \"{}\" is used more than once", settings.name);
+        }
+        let world = load_configured_world(&settings.name).with_context(|| format!("Could not load world \"{}\"", settings.name))?;
+        worlds.insert(settings.name.clone(), world);
+    }
+    info!(count = worlds.len(), "Loaded worlds");
+
+    let items = match config.item_registries_dir() {
+        Some(dir) => ItemRegistries::load_dir(dir).with_context(|| format!("Could not load the item registries in \"{}\"", dir))?,
+        None => ItemRegistries::default(),
+    };
+
+    let (server_tx, server_rx) = unbounded_channel();
+    let console_rx = ConsoleListener::start_console(shutdown_handle.clone(), ServerWrapper::new(server_tx.clone()))?;
+    let mut server = FalconServer::new(shutdown_handle, console_rx, server_rx, worlds, items);
+
+    tokio::spawn(NetworkListener::start_network_listening(server.shutdown_handle().clone(), ServerWrapper::new(server_tx)));
+    if let Some(addr) = FalconConfig::global().metrics_addr() {
+        tokio::spawn(MetricsListener::start_metrics_listening(server.shutdown_handle().clone(), addr));
+    }
+
+    thread::Builder::new()
+        .name(String::from("Main Server Thread"))
+        .spawn(move || server.start())
+        .with_context(|| "Couldn't start server logic!")?;
+
+    Ok(())
+}
+
+/// Loads the world called `name` with its settings from the config, this
+/// is also used to reload a world while the server runs.
+pub(crate) fn load_configured_world(name: &str) -> Result<FalconWorld> {
+    let config = FalconConfig::global();
+    let fallback = config
+        .fallback_block()
+        .with_context(|| format!("Invalid fallback block \"{}\", stopping launch", config.server.fallback_block))?;
+    let default_biome = config
+        .default_biome()
+        .with_context(|| format!("Invalid default biome \"{}\", stopping launch", config.server.default_biome))?;
+    let (file, origin, dimension_name, generator_setting) = if name == FalconConfig::DEFAULT_WORLD {
+        (config.world_file(), config.world_origin(), config.world_dimension(), config.world_generator())
+    } else {
+        let settings = config
+            .world_settings(name)
+            .with_context(|| format!("There is no world called \"{}\"", name))?;
+        (settings.world.as_deref(), settings.world_origin, settings.dimension.as_deref(), settings.generator.as_deref())
+    };
+    let mut world = load_world(file, origin, config.world_save_path(name), fallback, default_biome)?;
+    world.set_dimension(dimension(dimension_name)?);
+    if let Some(generator) = generator(generator_setting, config.world_spawn(name))? {
+        world.set_generator(generator);
+    }
+    world.update_light();
+    Ok(world)
+}
+
+/// Finds the configured dimension, defaults to the overworld.
+fn dimension(name: Option<&str>) -> Result<Dimension> {
+    match name {
+        Some(name) => Dimension::from_name(name).with_context(|| format!("Unknown dimension \"{}\", use overworld, the_nether or the_end", name)),
+        None => Ok(Dimension::overworld()),
+    }
+}
+
+/// Creates the configured generator, the platform of void worlds is
+/// placed below `spawn`.
+fn generator(setting: Option<&str>, spawn: Position) -> Result<Option<Box<dyn ChunkGenerator>>> {
+    Ok(match setting {
+        Some("void") => {
+            let spawn = BlockPosition::from(spawn);
+            let centre = BlockPosition::new(spawn.get_x(), spawn.get_y() - 1, spawn.get_z());
+            Some(Box::new(VoidGenerator::new(centre, 2, Blocks::Stone)))
+        },
+        Some(layers) => {
+            Some(Box::new(FlatGenerator::from_layers(layers).with_context(|| format!("Invalid generator \"{}\", use void or superflat layers", layers))?))
+        },
+        None => None,
+    })
+}
+
+/// Loads a schematic or world folder, `origin` overrides the offset of
+/// schematics.
+fn load_world(file: Option<&str>, origin: Option<[i32; 3]>, save_path: Option<&str>, fallback: Blocks, default_biome: Biome) -> Result<FalconWorld> {
+    let mut world = match file {
+        Some(dir_name) if Path::new(dir_name).is_dir() => {
+            let chunks = anvil::load_world(dir_name, fallback, default_biome)
+                .with_context(|| format!("Could not load anvil world \"{}\", stopping launch", dir_name))?;
+            info!("Loaded world \"{}\"", dir_name);
+
+            FalconWorld::from_chunks(chunks)
+        },
         Some(file_name) => {
             let world_file = std::fs::read(file_name).with_context(|| format!("Could not load \"{}\", stopping launch", file_name))?;
             let mut gz = GzDecoder::new(&world_file[..]);
@@ -27,29 +137,37 @@ pub(crate) fn start_server(shutdown_handle: ShutdownHandle) -> Result<()> {
             gz.read_to_end(&mut decompressed_world)
                 .with_context(|| format!("Could not decompress \"{}\", is this a valid schematic?", file_name))?;
 
-            let schematic: SchematicVersionedRaw =
-                fastnbt::from_bytes(&decompressed_world).with_context(|| format!("Could not parse schematic file \"{}\", is this valid nbt?", file_name))?;
+            if file_name.ends_with(".schematic") {
+                let schematic: LegacySchematicRaw = fastnbt::from_bytes(&decompressed_world)
+                    .with_context(|| format!("Could not parse legacy schematic file \"{}\", is this valid nbt?", file_name))?;
 
-            let data = SchematicData::try_from(schematic)
-                .with_context(|| format!("Invalid schematic, this server cannot use schematic \"{}\" currently!", file_name))?;
+                let legacy = LegacySchematic::from_raw(schematic, fallback)
+                    .with_context(|| format!("Invalid legacy schematic, this server cannot use schematic \"{}\" currently!", file_name))?;
 
-            info!("Loaded world");
+                info!("Loaded world \"{}\"", file_name);
 
-            FalconWorld::try_from(data)?
-        },
-        None => FalconWorld::new(0, 0, 0, 0, 0),
-    };
+                let data = legacy.data();
+                let origin = origin.unwrap_or(data.offset);
+                FalconWorld::from_schematic(data, origin, default_biome)?
+            } else {
+                let schematic: SchematicVersionedRaw = fastnbt::from_bytes(&decompressed_world)
+                    .with_context(|| format!("Could not parse schematic file \"{}\", is this valid nbt?", file_name))?;
 
-    let console_rx = ConsoleListener::start_console(shutdown_handle.clone())?;
-    let (server_tx, server_rx) = unbounded_channel();
-    let mut server = FalconServer::new(shutdown_handle, console_rx, server_rx, world);
+                let data = SchematicData::from_raw(schematic, fallback)
+                    .with_context(|| format!("Invalid schematic, this server cannot use schematic \"{}\" currently!", file_name))?;
 
-    tokio::spawn(NetworkListener::start_network_listening(server.shutdown_handle().clone(), ServerWrapper::new(server_tx)));
+                info!("Loaded world \"{}\"", file_name);
 
-    thread::Builder::new()
-        .name(String::from("Main Server Thread"))
-        .spawn(move || server.start())
-        .with_context(|| "Couldn't start server logic!")?;
+                let origin = origin.unwrap_or(data.offset);
+                FalconWorld::from_schematic(data, origin, default_biome)?
+            }
+        },
+        None => FalconWorld::new(0, 0, 0, 0, 0),
+    };
+    world.set_default_biome(default_biome);
 
-    Ok(())
+    if save_path == file {
+        world.mark_saved();
+    }
+    Ok(world)
 }
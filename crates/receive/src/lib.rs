@@ -5,6 +5,7 @@ mod macros;
 packet_modules! {
     extern pub mod v1_8_9;
     extern pub mod v1_12_2;
+    extern pub mod v1_13;
     extern pub mod v1_9;
 }
 
@@ -12,4 +13,6 @@ packet_modules! {
 pub enum ReceiveError {
     #[error("The player could not be found")]
     PlayerNotFound,
+    #[error("Invalid packet data")]
+    Read(#[from] falcon_packet_core::ReadError),
 }
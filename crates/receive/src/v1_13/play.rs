@@ -0,0 +1,165 @@
+#[falcon_receive_derive::falcon_receive]
+mod inner {
+    use falcon_core::world::BlockPosition;
+    use falcon_logic::connection::handler::PacketHandler;
+    use falcon_logic::FalconConnection;
+    use falcon_packet_core::{PacketRead, ReadError, VarI32};
+
+    use crate::ReceiveError;
+
+    #[derive(PacketRead)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x18;
+        477, 480, 485, 490, 498, 573, 575, 578 = 0x1A;
+        735, 736 = 0x1B;
+    })]
+    pub struct PlayerDiggingPacket {
+        #[falcon(var32)]
+        status: i32,
+        location: i64,
+        _face: u8,
+    }
+
+    #[derive(PacketRead)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x29;
+    })]
+    pub struct BlockPlacementPacket {
+        location: i64,
+        #[falcon(var32)]
+        face: i32,
+        #[falcon(var32)]
+        hand: i32,
+        _cursor_x: f32,
+        _cursor_y: f32,
+        _cursor_z: f32,
+    }
+
+    #[derive(PacketRead)]
+    #[falcon_packet(versions = {
+        477, 480, 485, 490, 498, 573, 575, 578 = 0x2C;
+        735, 736 = 0x2D;
+    })]
+    pub struct BlockPlacementPacketV14 {
+        #[falcon(var32)]
+        hand: i32,
+        location: i64,
+        #[falcon(var32)]
+        face: i32,
+        _cursor_x: f32,
+        _cursor_y: f32,
+        _cursor_z: f32,
+        _inside_block: bool,
+    }
+
+    #[derive(PacketRead)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x21;
+        477, 480, 485, 490, 498, 573, 575, 578 = 0x23;
+        735, 736 = 0x24;
+    })]
+    pub struct HeldItemChangePacket {
+        slot: i16,
+    }
+
+    #[derive(PacketRead)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x24;
+        477, 480, 485, 490, 498, 573, 575, 578 = 0x26;
+        735, 736 = 0x27;
+    })]
+    pub struct CreativeInventoryActionPacket {
+        slot: i16,
+        /// The item stack, its nbt isn't needed.
+        #[falcon(bytes)]
+        item: Vec<u8>,
+    }
+
+    /// The item id of a stack, `None` for an empty slot. Up until 1.13.1
+    /// the id is a short that is -1 for empty slots, since 1.13.2 a flag
+    /// tells whether the slot is empty.
+    fn item_id(mut stack: &[u8], protocol: i32) -> Result<Option<i32>, ReadError> {
+        if protocol < 404 {
+            let id = i16::read(&mut stack)?;
+            Ok((id >= 0).then_some(id as i32))
+        } else if bool::read(&mut stack)? {
+            Ok(Some(VarI32::read(&mut stack)?.val()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Positions are packed as x, y, z up to 1.13 and as x, z, y since 1.14.
+    fn block_position(location: i64, protocol: i32) -> BlockPosition {
+        let x = (location >> 38) as i32;
+        if protocol < 477 {
+            BlockPosition::new(x, ((location << 26) >> 52) as i32, ((location << 38) >> 38) as i32)
+        } else {
+            BlockPosition::new(x, ((location << 52) >> 52) as i32, ((location << 26) >> 38) as i32)
+        }
+    }
+
+    impl PacketHandler for PlayerDiggingPacket {
+        type Error = ReceiveError;
+
+        fn handle_packet(self, connection: &mut FalconConnection) -> Result<(), Self::Error> {
+            let uuid = connection.state().uuid.ok_or(ReceiveError::PlayerNotFound)?;
+            let position = block_position(self.location, connection.state().protocol_id);
+            connection.server().player_dig(uuid, self.status, position);
+            Ok(())
+        }
+
+        fn get_name(&self) -> &'static str { "Player Digging (1.13)" }
+    }
+
+    impl PacketHandler for BlockPlacementPacket {
+        type Error = ReceiveError;
+
+        fn handle_packet(self, connection: &mut FalconConnection) -> Result<(), Self::Error> {
+            let uuid = connection.state().uuid.ok_or(ReceiveError::PlayerNotFound)?;
+            let position = block_position(self.location, connection.state().protocol_id);
+            connection.server().player_place_block(uuid, position, self.face, self.hand);
+            Ok(())
+        }
+
+        fn get_name(&self) -> &'static str { "Player Block Placement (1.13)" }
+    }
+
+    impl PacketHandler for BlockPlacementPacketV14 {
+        type Error = ReceiveError;
+
+        fn handle_packet(self, connection: &mut FalconConnection) -> Result<(), Self::Error> {
+            let uuid = connection.state().uuid.ok_or(ReceiveError::PlayerNotFound)?;
+            let position = block_position(self.location, connection.state().protocol_id);
+            connection.server().player_place_block(uuid, position, self.face, self.hand);
+            Ok(())
+        }
+
+        fn get_name(&self) -> &'static str { "Player Block Placement (1.14)" }
+    }
+
+    impl PacketHandler for HeldItemChangePacket {
+        type Error = ReceiveError;
+
+        fn handle_packet(self, connection: &mut FalconConnection) -> Result<(), Self::Error> {
+            let uuid = connection.state().uuid.ok_or(ReceiveError::PlayerNotFound)?;
+            connection.server().player_change_held_item(uuid, self.slot);
+            Ok(())
+        }
+
+        fn get_name(&self) -> &'static str { "Held Item Change (1.13)" }
+    }
+
+    impl PacketHandler for CreativeInventoryActionPacket {
+        type Error = ReceiveError;
+
+        fn handle_packet(self, connection: &mut FalconConnection) -> Result<(), Self::Error> {
+            let uuid = connection.state().uuid.ok_or(ReceiveError::PlayerNotFound)?;
+            let item = item_id(&self.item, connection.state().protocol_id)?;
+            connection.server().player_set_creative_slot(uuid, self.slot, item);
+            Ok(())
+        }
+
+        fn get_name(&self) -> &'static str { "Creative Inventory Action (1.13)" }
+    }
+}
@@ -1,7 +1,8 @@
 #[falcon_receive_derive::falcon_receive]
 mod inner {
     use falcon_core::player::data::Position;
-    use falcon_logic::{FalconConnection, connection::handler::PacketHandler};
+    use falcon_logic::connection::handler::PacketHandler;
+    use falcon_logic::FalconConnection;
     use falcon_packet_core::PacketRead;
 
     use crate::ReceiveError;
@@ -58,18 +59,29 @@ mod inner {
         on_ground: bool,
     }
 
+    #[derive(PacketRead)]
+    #[falcon_packet(versions = {
+        47 = 0x01;
+        107, 108, 109, 110, 210, 315, 316, 338, 340, 393, 401, 404 = 0x02;
+        335, 477, 480, 485, 490, 498, 573, 575, 578, 735, 736 = 0x03;
+    })]
+    pub struct ChatMessagePacket {
+        #[falcon(string = 256)]
+        message: String,
+    }
+
     impl PacketHandler for PlayerPositionPacket {
         type Error = ReceiveError;
 
         fn handle_packet(self, connection: &mut FalconConnection) -> Result<(), Self::Error> {
             let uuid = connection.state().uuid.ok_or(ReceiveError::PlayerNotFound)?;
-            connection.server().player_update_pos_look(uuid, Some(Position::new(self.x, self.y, self.z)), None, self.on_ground);
+            connection
+                .server()
+                .player_update_pos_look(uuid, Some(Position::new(self.x, self.y, self.z)), None, self.on_ground);
             Ok(())
         }
 
-        fn get_name(&self) -> &'static str {
-            "Player Position (1.8.9)"
-        }
+        fn get_name(&self) -> &'static str { "Player Position (1.8.9)" }
     }
 
     impl PacketHandler for PlayerLookPacket {
@@ -77,13 +89,13 @@ mod inner {
 
         fn handle_packet(self, connection: &mut FalconConnection) -> Result<(), Self::Error> {
             let uuid = connection.state().uuid.ok_or(ReceiveError::PlayerNotFound)?;
-            connection.server().player_update_pos_look(uuid, None, Some((self.yaw, self.pitch)), self.on_ground);
+            connection
+                .server()
+                .player_update_pos_look(uuid, None, Some((self.yaw, self.pitch)), self.on_ground);
             Ok(())
         }
 
-        fn get_name(&self) -> &'static str {
-            "Player Look (1.8.9)"
-        }
+        fn get_name(&self) -> &'static str { "Player Look (1.8.9)" }
     }
 
     impl PacketHandler for PositionLookPacket {
@@ -91,12 +103,24 @@ mod inner {
 
         fn handle_packet(self, connection: &mut FalconConnection) -> Result<(), Self::Error> {
             let uuid = connection.state().uuid.ok_or(ReceiveError::PlayerNotFound)?;
-            connection.server().player_update_pos_look(uuid, Some(Position::new(self.x, self.y, self.z)), Some((self.yaw, self.pitch)), self.on_ground);
+            connection
+                .server()
+                .player_update_pos_look(uuid, Some(Position::new(self.x, self.y, self.z)), Some((self.yaw, self.pitch)), self.on_ground);
             Ok(())
         }
 
-        fn get_name(&self) -> &'static str {
-            "Position And Look (1.8.9)"
+        fn get_name(&self) -> &'static str { "Position And Look (1.8.9)" }
+    }
+
+    impl PacketHandler for ChatMessagePacket {
+        type Error = ReceiveError;
+
+        fn handle_packet(self, connection: &mut FalconConnection) -> Result<(), Self::Error> {
+            let uuid = connection.state().uuid.ok_or(ReceiveError::PlayerNotFound)?;
+            connection.server().player_chat(uuid, self.message);
+            Ok(())
         }
+
+        fn get_name(&self) -> &'static str { "Chat Message (1.8.9)" }
     }
 }
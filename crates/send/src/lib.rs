@@ -52,6 +52,16 @@ packet_write_fn! {
         mod v1_8_9::play::difficulty;
         mod v1_14::play::difficulty;
     }
+    RespawnSpec => write_respawn {
+        mod v1_13::play::respawn;
+        mod v1_14::play::respawn;
+        mod v1_15::play::respawn;
+        mod v1_16::play::respawn;
+    }
+    ChatComponent => write_system_message {
+        mod v1_8_9::play::system_message;
+        mod v1_16::play::system_message;
+    }
     PlayerAbilitiesSpec => write_player_abilities {
         mod v1_8_9::play::player_abilities;
     }
@@ -67,10 +77,45 @@ packet_write_fn! {
         mod v1_15::play::chunk_data;
         mod v1_16::play::chunk_data;
     }
+    UpdateLightSpec => write_update_light {
+        mod v1_14::play::update_light;
+        mod v1_16::play::update_light;
+    }
     (i32, i32) => write_unload_chunk {
         mod v1_9::play::unload_chunk;
     }
     (i32, i32) => write_update_viewpos {
         mod v1_14::play::update_viewpos;
     }
+    SpawnEntitySpec => write_spawn_object {
+        mod v1_13::play::spawn_object;
+        mod v1_14::play::spawn_object;
+        mod v1_15::play::spawn_object;
+        mod v1_16::play::spawn_object;
+    }
+    SpawnEntitySpec => write_spawn_living_entity {
+        mod v1_13::play::spawn_living_entity;
+        mod v1_14::play::spawn_living_entity;
+        mod v1_15::play::spawn_living_entity;
+    }
+    SpawnEntitySpec => write_spawn_painting {
+        mod v1_13::play::spawn_painting;
+        mod v1_14::play::spawn_painting;
+    }
+    EntityMetadataSpec => write_entity_metadata {
+        mod v1_13::play::entity_metadata;
+        mod v1_14::play::entity_metadata;
+        mod v1_15::play::entity_metadata;
+    }
+    Vec<i32> => write_destroy_entities {
+        mod v1_13::play::destroy_entities;
+    }
+    BlockChangeSpec => write_block_change {
+        mod v1_13::play::block_change;
+        mod v1_14::play::block_change;
+    }
+    MultiBlockChangeSpec => write_multi_block_change {
+        mod v1_13::play::multi_block_change;
+        mod v1_14::play::multi_block_change;
+    }
 }
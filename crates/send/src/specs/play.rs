@@ -1,8 +1,18 @@
+use falcon_core::data::Identifier;
 use falcon_core::player::data::{GameMode, LookAngles, PlayerAbilityFlags, Position};
 use falcon_core::server::data::Difficulty;
+use falcon_core::world::biome::Biome;
+use falcon_core::world::bit_storage::BitStorage;
+use falcon_core::world::block_entity::BlockEntity;
 use falcon_core::world::blocks::Blocks;
-use falcon_core::world::chunks::{Chunk, ChunkSection};
+use falcon_core::world::chunks::{Chunk, ChunkPos, ChunkSection, BIOME_CELLS, BIOME_CELLS_LENGTH, BIOME_CELLS_WIDTH, SECTIONS_NUM};
+use falcon_core::world::dimension::Dimension;
+use falcon_core::world::entity::Entity;
+use falcon_core::world::light::{SectionLight, LIGHT_ARRAY_LEN};
 use falcon_core::world::palette::Palette;
+use falcon_core::world::BlockPosition;
+use fastnbt::Value;
+use uuid::Uuid;
 
 use crate::define_spec;
 
@@ -17,7 +27,23 @@ define_spec! {
         hashed_seed: i64,
         view_distance: i32,
         reduced_debug: bool,
-        enable_respawn_screen: bool;
+        enable_respawn_screen: bool,
+        dimension_name: Identifier,
+        world_name: Identifier,
+        world_names: Vec<Identifier>,
+        dimensions: Vec<Dimension>;
+    }
+}
+
+define_spec! {
+    RespawnSpec {
+        dimension: i32,
+        dimension_name: Identifier,
+        world_name: Identifier,
+        difficulty: Difficulty,
+        game_mode: GameMode,
+        hashed_seed: i64,
+        level_type: String;
     }
 }
 
@@ -49,38 +75,166 @@ define_spec! {
 }
 
 define_spec! {
-    ChunkDataSpec => chunk: &Chunk, protocol_version: i32 {
+    ChunkDataSpec => chunk: &Chunk, protocol_version: i32, default_biome: Biome {
         ;let chunk_x: i32 = chunk_pos.x,
         let chunk_z: i32 = chunk_pos.z,
         let bitmask: i32 = bit_mask,
-        let sections: Vec<ChunkSectionDataSpec> = chunk_sections;
+        let sections: Vec<ChunkSectionDataSpec> = chunk_sections,
+        let biomes: [i32; BIOME_CELLS] = biome_ids,
+        let block_entities: Vec<Value> = chunk.block_entities().iter().map(|block_entity| block_entity_nbt(block_entity, protocol_version)).collect();
         {
             let chunk_pos = chunk.get_position()
+            // clients up to 1.16 only know the sections from y 0 to 256
             let bit_mask = chunk.get_bit_mask()
             let mut chunk_sections = Vec::with_capacity(bit_mask.count_ones() as usize)
-            for (i, section) in chunk.get_chunk_sections().iter().enumerate().filter_map(|(i, v)| v.as_ref().map(|v| (i, v))) {
-                chunk_sections.push(ChunkSectionDataSpec::new(section, i, protocol_version));
+            for section_y in 0..SECTIONS_NUM as i32 {
+                if let Some(section) = chunk.section(section_y) {
+                    chunk_sections.push(ChunkSectionDataSpec::new(section, chunk.section_light(section_y), section_y as usize, protocol_version));
+                }
+            }
+            let mut biome_ids = [default_biome.protocol_id(protocol_version); BIOME_CELLS]
+            if chunk.biomes().is_some() {
+                for (i, id) in biome_ids.iter_mut().enumerate() {
+                    let i = i as u16;
+                    let (x, z, y) = (i % BIOME_CELLS_WIDTH, i / BIOME_CELLS_WIDTH % BIOME_CELLS_LENGTH, i / (BIOME_CELLS_WIDTH * BIOME_CELLS_LENGTH));
+                    if let Some(biome) = chunk.biome_at(x, y as i32, z) {
+                        *id = biome.protocol_id(protocol_version);
+                    }
+                }
             }
         }
     }
 }
 
 impl ChunkDataSpec {
-    pub fn empty(x: i32, z: i32) -> Self {
+    pub fn empty(x: i32, z: i32, protocol_version: i32, default_biome: Biome) -> Self {
         ChunkDataSpec {
             chunk_x: x,
             chunk_z: z,
             bitmask: 0,
             sections: vec![],
+            biomes: [default_biome.protocol_id(protocol_version); BIOME_CELLS],
+            block_entities: vec![],
         }
     }
 }
 
+/// The nbt clients get of a block entity, without the contents of
+/// containers. Clients before 1.16 expect skull owner ids as strings.
+fn block_entity_nbt(block_entity: &BlockEntity, protocol_version: i32) -> Value {
+    let mut nbt = match block_entity.to_nbt() {
+        Value::Compound(nbt) => nbt,
+        _ => unreachable!(),
+    };
+    nbt.remove("Items");
+    if protocol_version < 735 {
+        if let Some(Value::Compound(owner)) = nbt.get_mut("SkullOwner") {
+            if let Some(Value::IntArray(id)) = owner.get("Id") {
+                if id.len() == 4 {
+                    let id = id.iter().fold(0u128, |id, part| id << 32 | *part as u32 as u128);
+                    owner.insert(String::from("Id"), Value::String(uuid::Uuid::from_u128(id).hyphenated().to_string()));
+                }
+            }
+        }
+    }
+    Value::Compound(nbt)
+}
+
 define_spec! {
-    ChunkSectionDataSpec => section: &ChunkSection {
+    ChunkSectionDataSpec => section: &ChunkSection, light: Option<&SectionLight> {
         section_index: usize,
         protocol_version: i32;
         let palette: Palette<Blocks> = section.get_palette().clone(),
-        let blocks: Vec<u16> = section.get_block_data().clone(),
+        let blocks: BitStorage = section.get_block_data().clone(),
+        let counts: Vec<u16> = section.get_palette_counts().to_vec(),
+        let block_light: Vec<u8> = light.map_or_else(full_light, |light| light.block_light().to_vec()),
+        let sky_light: Option<Vec<u8>> = light.map_or_else(|| Some(full_light()), |light| light.sky_light().map(<[u8]>::to_vec)),
+    }
+}
+
+/// The light of a chunk for clients that get it in a separate packet,
+/// the first entry is the section below the world and the last entry
+/// the section above it.
+pub struct UpdateLightSpec {
+    pub(crate) chunk_x: i32,
+    pub(crate) chunk_z: i32,
+    pub(crate) sky_light: Option<Vec<Vec<u8>>>,
+    pub(crate) block_light: Vec<Vec<u8>>,
+}
+
+impl UpdateLightSpec {
+    /// Chunks whose light wasn't computed are sent fully lit.
+    pub fn new(chunk: &Chunk, has_skylight: bool) -> Self {
+        let pos = chunk.get_position();
+        if chunk.light().is_none() {
+            let mut spec = UpdateLightSpec::empty(pos.x, pos.z, has_skylight);
+            spec.block_light.iter_mut().for_each(|section| section.fill(0xFF));
+            return spec;
+        }
+        // sections the chunk doesn't have are dark below y 0 and lit by the sky above
+        // it
+        let sections: Vec<_> = (-1..=SECTIONS_NUM as i32)
+            .map(|section_y| (section_y, chunk.section_light(section_y)))
+            .collect();
+        let sky_light = |(section_y, light): &(i32, Option<&SectionLight>)| match light {
+            Some(light) => light.sky_light().map(<[u8]>::to_vec),
+            None if *section_y < 0 => Some(vec![0; LIGHT_ARRAY_LEN]),
+            None => Some(full_light()),
+        };
+        UpdateLightSpec {
+            chunk_x: pos.x,
+            chunk_z: pos.z,
+            sky_light: has_skylight
+                .then(|| sections.iter().map(sky_light).collect::<Option<Vec<_>>>())
+                .flatten(),
+            block_light: sections
+                .iter()
+                .map(|(_, light)| light.map_or_else(|| vec![0; LIGHT_ARRAY_LEN], |light| light.block_light().to_vec()))
+                .collect(),
+        }
+    }
+
+    /// The light of a chunk without blocks.
+    pub fn empty(x: i32, z: i32, has_skylight: bool) -> Self {
+        let sections = SECTIONS_NUM as usize + 2;
+        UpdateLightSpec {
+            chunk_x: x,
+            chunk_z: z,
+            sky_light: has_skylight.then(|| vec![full_light(); sections]),
+            block_light: vec![vec![0; LIGHT_ARRAY_LEN]; sections],
+        }
+    }
+}
+
+fn full_light() -> Vec<u8> { vec![0xFF; LIGHT_ARRAY_LEN] }
+
+define_spec! {
+    SpawnEntitySpec {
+        entity_id: i32,
+        uuid: Uuid,
+        entity: Entity,
+    }
+}
+
+define_spec! {
+    BlockChangeSpec {
+        position: BlockPosition,
+        block: Blocks,
+        protocol_version: i32,
+    }
+}
+
+define_spec! {
+    MultiBlockChangeSpec {
+        chunk: ChunkPos,
+        blocks: Vec<(u16, u16, u16, Blocks)>,
+        protocol_version: i32,
+    }
+}
+
+define_spec! {
+    EntityMetadataSpec {
+        entity_id: i32,
+        entity: Entity,
     }
 }
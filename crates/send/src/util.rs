@@ -1,7 +1,13 @@
+use bytes::BufMut;
+use falcon_core::world::bit_storage::BitStorage;
 use falcon_core::world::block_util::blocks_movement;
 use falcon_core::world::blocks::Blocks;
 use falcon_core::world::chunks::ChunkSection;
-use falcon_core::world::palette::PaletteToI32;
+use falcon_core::world::entity::{Entity, EntityKind};
+use falcon_core::world::palette::{Palette, PaletteToI32};
+use falcon_core::world::BlockPosition;
+use falcon_packet_core::{PacketSize, PacketWrite, VarI32, WriteError};
+use fastnbt::Value;
 
 use crate::ChunkSectionDataSpec;
 
@@ -25,7 +31,7 @@ impl HeightMap {
                             for y in (0..16).rev() {
                                 let block = section
                                     .palette
-                                    .at(section.blocks[ChunkSection::calculate_index(x as u16, y as u16, z as u16)] as usize)
+                                    .at(section.blocks.get(ChunkSection::calculate_index(x as u16, y as u16, z as u16)) as usize)
                                     .unwrap();
                                 if to_i32(block).is_some() && blocks_movement(block) {
                                     heightmap.push((top_y + y + 1) as u16);
@@ -48,3 +54,214 @@ impl HeightMap {
 
     pub fn motion_blocking(self) -> Vec<u16> { self.motion_blocking }
 }
+
+/// The protocol ids of a section palette, blocks unknown to the version
+/// become air. Clients don't mind ids that appear twice.
+pub(crate) fn palette_ids(palette: &Palette<Blocks>, to_i32: PaletteToI32<Blocks>) -> Vec<i32> {
+    let air = to_i32(&Blocks::Air).unwrap();
+    palette.items().iter().map(|block| to_i32(block).unwrap_or(air)).collect()
+}
+
+/// The non-air blocks of a section that the version knows.
+pub(crate) fn block_count(palette: &Palette<Blocks>, counts: &[u16], to_i32: PaletteToI32<Blocks>) -> i16 {
+    palette
+        .items()
+        .iter()
+        .zip(counts)
+        .filter(|(block, _)| !matches!(block, Blocks::Air | Blocks::VoidAir | Blocks::CaveAir) && to_i32(block).is_some())
+        .map(|(_, count)| *count as i16)
+        .sum()
+}
+
+/// Section data with at least the 4 bits clients expect.
+pub(crate) fn client_block_data(blocks: BitStorage) -> BitStorage {
+    match blocks.bits() {
+        0 => blocks.resized(4),
+        _ => blocks,
+    }
+}
+
+/// Block entity compounds prefixed with their count, as sent at the end of
+/// chunk packets. They are encoded once so the size doesn't encode them again.
+pub(crate) struct BlockEntityList(Vec<Vec<u8>>);
+
+impl From<Vec<Value>> for BlockEntityList {
+    fn from(block_entities: Vec<Value>) -> Self {
+        BlockEntityList(
+            block_entities
+                .iter()
+                .filter_map(|nbt| match fastnbt::to_bytes(nbt) {
+                    Ok(bytes) => Some(bytes),
+                    Err(error) => {
+                        tracing::warn!(%error, "Could not encode block entity");
+                        None
+                    },
+                })
+                .collect(),
+        )
+    }
+}
+
+impl PacketSize for BlockEntityList {
+    fn size(&self) -> usize { VarI32::from(self.0.len()).size() + self.0.iter().map(Vec::len).sum::<usize>() }
+}
+
+impl PacketWrite for BlockEntityList {
+    fn write<B>(&self, buffer: &mut B) -> Result<(), WriteError>
+    where
+        B: BufMut + ?Sized,
+    {
+        VarI32::from(self.0.len()).write(buffer)?;
+        self.0.iter().try_for_each(|nbt| nbt.write(buffer))
+    }
+}
+
+/// Multi block change records prefixed with their count, in the encoding
+/// used up to 1.16.1. Blocks unknown to the version become air.
+pub(crate) struct BlockRecords(Vec<(u8, u8, VarI32)>);
+
+impl BlockRecords {
+    pub(crate) fn new(blocks: &[(u16, u16, u16, Blocks)], to_i32: PaletteToI32<Blocks>) -> Self {
+        BlockRecords(
+            blocks
+                .iter()
+                .map(|(x, y, z, block)| (((x << 4) | z) as u8, *y as u8, VarI32::from(to_i32(block).unwrap_or(0))))
+                .collect(),
+        )
+    }
+}
+
+impl PacketSize for BlockRecords {
+    fn size(&self) -> usize { VarI32::from(self.0.len()).size() + self.0.iter().map(|(_, _, block)| 2 + block.size()).sum::<usize>() }
+}
+
+impl PacketWrite for BlockRecords {
+    fn write<B>(&self, buffer: &mut B) -> Result<(), WriteError>
+    where
+        B: BufMut + ?Sized,
+    {
+        VarI32::from(self.0.len()).write(buffer)?;
+        self.0.iter().try_for_each(|(horizontal, y, block)| {
+            buffer.put_u8(*horizontal);
+            buffer.put_u8(*y);
+            block.write(buffer)
+        })
+    }
+}
+
+/// Converts degrees to the 256 steps of a full turn packets use.
+pub(crate) fn angle(degrees: f32) -> u8 { (degrees.rem_euclid(360.0) / 360.0 * 256.0) as u32 as u8 }
+
+/// The position encoding used up to 1.13.
+pub(crate) fn position_xyz(pos: BlockPosition) -> i64 {
+    ((pos.get_x() as i64 & 0x3FFFFFF) << 38) | ((pos.get_y() as i64 & 0xFFF) << 26) | (pos.get_z() as i64 & 0x3FFFFFF)
+}
+
+/// The position encoding used since 1.14.
+pub(crate) fn position_xzy(pos: BlockPosition) -> i64 {
+    ((pos.get_x() as i64 & 0x3FFFFFF) << 38) | ((pos.get_z() as i64 & 0x3FFFFFF) << 12) | (pos.get_y() as i64 & 0xFFF)
+}
+
+/// Entity metadata entries terminated by `0xFF`, the value
+/// types used here have the same ids from 1.13 to 1.16.
+#[derive(Default)]
+pub(crate) struct Metadata(Vec<u8>);
+
+impl Metadata {
+    fn entry(&mut self, index: u8, value_type: u8) {
+        self.0.put_u8(index);
+        self.0.put_u8(value_type);
+    }
+
+    fn put_var_u32(&mut self, mut value: u32) {
+        while value & !0x7F != 0 {
+            self.0.put_u8((value & 0x7F) as u8 | 0x80);
+            value >>= 7;
+        }
+        self.0.put_u8(value as u8);
+    }
+
+    pub(crate) fn byte(&mut self, index: u8, value: u8) {
+        self.entry(index, 0);
+        self.0.put_u8(value);
+    }
+
+    pub(crate) fn var_int(&mut self, index: u8, value: i32) {
+        self.entry(index, 1);
+        self.put_var_u32(value as u32);
+    }
+
+    pub(crate) fn opt_chat(&mut self, index: u8, json: Option<&str>) {
+        self.entry(index, 5);
+        self.0.put_u8(json.is_some() as u8);
+        if let Some(json) = json {
+            self.put_var_u32(json.len() as u32);
+            self.0.put_slice(json.as_bytes());
+        }
+    }
+
+    pub(crate) fn bool(&mut self, index: u8, value: bool) {
+        self.entry(index, 7);
+        self.0.put_u8(value as u8);
+    }
+
+    pub(crate) fn rotation(&mut self, index: u8, [x, y, z]: [f32; 3]) {
+        self.entry(index, 8);
+        self.0.put_f32(x);
+        self.0.put_f32(y);
+        self.0.put_f32(z);
+    }
+
+    /// The metadata of a static entity. Only the first indices are shared by
+    /// all versions, the armor stand flags and the item frame item are at
+    /// `armor_stand_index` and `item_frame_index` in the version that is
+    /// written.
+    ///
+    /// Items in item frames are not sent as the server doesn't know item
+    /// ids, the frames show up empty.
+    pub(crate) fn of_entity(entity: &Entity, armor_stand_index: u8, item_frame_index: u8) -> Self {
+        let mut metadata = Metadata::default();
+        metadata.byte(0, (entity.invisible() as u8) << 5 | (entity.glowing() as u8) << 6);
+        if entity.custom_name().is_some() {
+            metadata.opt_chat(2, entity.custom_name());
+            metadata.bool(3, entity.custom_name_visible());
+        }
+        if entity.no_gravity() {
+            metadata.bool(5, true);
+        }
+        match entity.kind() {
+            EntityKind::ArmorStand {
+                small,
+                show_arms,
+                no_base_plate,
+                marker,
+                pose,
+            } => {
+                metadata.byte(armor_stand_index, *small as u8 | (*show_arms as u8) << 2 | (*no_base_plate as u8) << 3 | (*marker as u8) << 4);
+                for (i, rotation) in [pose.head, pose.body, pose.left_arm, pose.right_arm, pose.left_leg, pose.right_leg]
+                    .into_iter()
+                    .enumerate()
+                {
+                    metadata.rotation(armor_stand_index + 1 + i as u8, rotation);
+                }
+            },
+            EntityKind::ItemFrame { item_rotation, .. } => metadata.var_int(item_frame_index + 1, *item_rotation as i32),
+            EntityKind::Painting { .. } => {},
+        }
+        metadata
+    }
+}
+
+impl PacketSize for Metadata {
+    fn size(&self) -> usize { self.0.len() + 1 }
+}
+
+impl PacketWrite for Metadata {
+    fn write<B>(&self, buffer: &mut B) -> Result<(), WriteError>
+    where
+        B: BufMut + ?Sized,
+    {
+        self.0.write(buffer)?;
+        0xFFu8.write(buffer)
+    }
+}
@@ -2,58 +2,88 @@
 mod inner {
     use bytes::BufMut;
     use derive_from_ext::From;
+    use falcon_core::world::bit_storage::BitStorage;
     use falcon_core::world::blocks::Blocks;
-    use falcon_core::world::chunks::{SECTION_HEIGHT, SECTION_LENGTH, SECTION_WIDTH};
+    use falcon_core::world::chunks::{Chunk, BIOME_CELLS, BIOME_CELL_SIZE, SECTION_HEIGHT, SECTION_LENGTH, SECTION_WIDTH};
+    use falcon_core::world::entity::EntityKind;
+    use falcon_core::world::palette::{Palette, PaletteToI32};
     use falcon_packet_core::{PacketArray, PacketIter, PacketSize, PacketVec, PacketWrite, PacketWriteSeed, VarI32, WriteError};
+    use uuid::Uuid;
 
-    use crate::specs::play::{ChunkDataSpec, ChunkSectionDataSpec};
+    use crate::specs::play::{BlockChangeSpec, ChunkDataSpec, ChunkSectionDataSpec, EntityMetadataSpec, MultiBlockChangeSpec, RespawnSpec, SpawnEntitySpec};
+    use crate::util::{angle, client_block_data, palette_ids, position_xyz, BlockEntityList, BlockRecords, Metadata};
 
     const MAX_BITS_PER_BLOCK: u8 = 14;
 
     const BIOME_COUNT: u16 = SECTION_WIDTH * SECTION_LENGTH;
-    const LIGHT_COUNT: usize = ((SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH) / 2) as usize;
-    const BIOMES: [i32; BIOME_COUNT as usize] = [0; BIOME_COUNT as usize];
-    const MAX_LIGHT: [u8; LIGHT_COUNT] = [0xFF; LIGHT_COUNT];
+    /// Column biomes are taken from the cells at sea level.
+    const SEA_LEVEL_CELL: u16 = 64 / BIOME_CELL_SIZE;
 
-    #[derive(PacketSize, PacketWrite, From)]
-    #[from(ChunkDataSpec)]
+    #[derive(PacketSize, PacketWrite)]
     #[falcon_packet(versions = {
         393, 401, 404 = 0x22;
     }, name = "chunk_data")]
     pub struct ChunkDataPacket {
         chunk_x: i32,
         chunk_z: i32,
-        #[from(skip, default = "true")]
-        full_chunk: bool,
+        full_chunk: bool, // default true
         #[falcon(var32)]
         bitmask: i32,
-        #[from(skip)]
         #[falcon(var32)]
-        size: usize, // filled in by sections field
-        #[from(map = "data_map")]
+        size: usize, // filled in by data field
         #[falcon(link = "size with data")]
-        sections: Vec<ChunkSectionData>,
-        #[from(skip)]
-        #[falcon(var32)]
-        block_entity_num: i32, // default 0
+        data: ChunkColumnData,
+        block_entities: BlockEntityList,
     }
 
-    fn data_map(sections: Vec<ChunkSectionDataSpec>) -> Vec<ChunkSectionData> { sections.into_iter().map(|s| s.into()).collect() }
+    struct ChunkColumnData {
+        sections: Vec<ChunkSectionData>,
+        biomes: [i32; BIOME_COUNT as usize],
+    }
 
     #[inline(always)]
-    fn data_value(field: &[ChunkSectionData]) -> usize { data_size(field) }
+    fn data_value(field: &ChunkColumnData) -> usize { data_size(field) }
+
+    fn data_size(field: &ChunkColumnData) -> usize { PacketIter::new(field.sections.iter()).size_ref() + BIOME_COUNT as usize * 4 }
 
-    fn data_size(field: &[ChunkSectionData]) -> usize { PacketIter::new(field.iter()).size_ref() + BIOME_COUNT as usize * 4 }
+    fn data_write<B: BufMut + ?Sized>(field: &ChunkColumnData, buffer: &mut B) -> Result<(), WriteError> {
+        PacketIter::new(field.sections.iter()).write_ref(buffer)?;
+        PacketWriteSeed::write(PacketArray::default(), &field.biomes, buffer)
+    }
+
+    impl From<ChunkDataSpec> for ChunkDataPacket {
+        fn from(spec: ChunkDataSpec) -> Self {
+            ChunkDataPacket {
+                chunk_x: spec.chunk_x,
+                chunk_z: spec.chunk_z,
+                full_chunk: true,
+                bitmask: spec.bitmask,
+                size: 0,
+                data: ChunkColumnData {
+                    sections: spec.sections.into_iter().map(|s| s.into()).collect(),
+                    biomes: column_biomes(&spec.biomes),
+                },
+                block_entities: spec.block_entities.into(),
+            }
+        }
+    }
 
-    fn data_write<B: BufMut + ?Sized>(field: &[ChunkSectionData], buffer: &mut B) -> Result<(), WriteError> {
-        PacketIter::new(field.iter()).write_ref(buffer)?;
-        PacketWriteSeed::write(PacketArray::default(), &BIOMES, buffer)
+    /// Clients before 1.15 only know one biome per block column.
+    pub(crate) fn column_biomes(cells: &[i32; BIOME_CELLS]) -> [i32; BIOME_COUNT as usize] {
+        let mut biomes = [0; BIOME_COUNT as usize];
+        for (i, biome) in biomes.iter_mut().enumerate() {
+            let (x, z) = (i as u16 % SECTION_WIDTH, i as u16 / SECTION_WIDTH);
+            *biome = cells[Chunk::biome_index(x / BIOME_CELL_SIZE, SEA_LEVEL_CELL, z / BIOME_CELL_SIZE)];
+        }
+        biomes
     }
 
     struct ChunkSectionData {
         bits_per_block: u8,
         palette: Option<Vec<i32>>,
         block_data: Vec<u64>,
+        block_light: Vec<u8>,
+        sky_light: Option<Vec<u8>>,
     }
 
     impl PacketSize for ChunkSectionData {
@@ -64,11 +94,11 @@ mod inner {
                 0
             };
             self.bits_per_block.size()
-            + palette_len
-            + VarI32::from(self.block_data.len()).size()
-            + self.block_data.len() * std::mem::size_of::<u64>()
-            + LIGHT_COUNT // block light
-            + LIGHT_COUNT // sky light
+                + palette_len
+                + VarI32::from(self.block_data.len()).size()
+                + self.block_data.len() * std::mem::size_of::<u64>()
+                + self.block_light.len()
+                + self.sky_light.as_ref().map_or(0, Vec::len)
         }
     }
 
@@ -84,8 +114,12 @@ mod inner {
             }
             VarI32::from(self.block_data.len()).write(buffer)?;
             PacketWriteSeed::write(PacketVec::default(), &self.block_data, buffer)?;
-            MAX_LIGHT.write(buffer)?;
-            MAX_LIGHT.write(buffer)
+            buffer.put_slice(&self.block_light);
+            // only sent in dimensions with sky light
+            if let Some(sky_light) = &self.sky_light {
+                buffer.put_slice(sky_light);
+            }
+            Ok(())
         }
     }
 
@@ -95,44 +129,34 @@ mod inner {
                 401 | 404 => Blocks::get_global_id_1631,
                 _ => Blocks::get_global_id_1519,
             };
-            let bits_per_block = {
-                let actual = spec.palette.calculate_bits_per_entry(block_to_int);
-                if actual < 4 {
-                    4u8
-                } else if actual < 9 {
-                    actual as u8
-                } else {
-                    MAX_BITS_PER_BLOCK
-                }
-            };
-
-            let (block_data, palette) = if bits_per_block > 8 {
-                let blocks = spec.palette.build_direct_palette(spec.blocks.into_iter(), block_to_int, Blocks::Air);
-                let block_data = build_compacted_data_array(
-                    MAX_BITS_PER_BLOCK,
-                    (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH * bits_per_block as u16) as u32 / i64::BITS,
-                    blocks,
-                );
-                (block_data, None)
-            } else {
-                let (blocks, palette) = spec
-                    .palette
-                    .build_indirect_palette(spec.blocks.into_iter(), block_to_int, Blocks::Air);
-                let block_data = build_compacted_data_array(
-                    bits_per_block,
-                    (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH * bits_per_block as u16) as u32 / i64::BITS,
-                    blocks,
-                );
-                (block_data, Some(palette))
-            };
+            let (bits_per_block, block_data, palette) = section_block_data(&spec.palette, spec.blocks, block_to_int);
             ChunkSectionData {
                 bits_per_block,
                 palette,
                 block_data,
+                block_light: spec.block_light,
+                sky_light: spec.sky_light,
             }
         }
     }
 
+    /// The bits per block, packed data and palette of a section. Clients up to
+    /// 1.15 expect values to span longs, so the section data is reused when the
+    /// bits divide 64 and repacked otherwise.
+    pub(crate) fn section_block_data(palette: &Palette<Blocks>, blocks: BitStorage, block_to_int: PaletteToI32<Blocks>) -> (u8, Vec<u64>, Option<Vec<i32>>) {
+        let ids = palette_ids(palette, block_to_int);
+        let blocks = client_block_data(blocks);
+        let capacity = |bits: u8| (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH * bits as u16) as u32 / i64::BITS;
+        match blocks.bits() {
+            bits if bits > 8 => {
+                let blocks = blocks.iter().map(|value| ids[value as usize] as u64);
+                (MAX_BITS_PER_BLOCK, build_compacted_data_array(MAX_BITS_PER_BLOCK, capacity(MAX_BITS_PER_BLOCK), blocks), None)
+            },
+            bits if 64 % bits == 0 => (bits, blocks.into_data(), Some(ids)),
+            bits => (bits, build_compacted_data_array(bits, capacity(bits), blocks.iter().map(u64::from)), Some(ids)),
+        }
+    }
+
     pub fn build_compacted_data_array<E: Iterator<Item = u64>>(bits_per_element: u8, capacity: u32, elements: E) -> Vec<u64> {
         let mut compacted_data = Vec::with_capacity(capacity as usize);
         let mut current_long = 0u64;
@@ -159,4 +183,204 @@ mod inner {
 
         compacted_data
     }
+
+    #[derive(PacketSize, PacketWrite, From)]
+    #[from(RespawnSpec)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x38;
+    }, name = "respawn")]
+    pub struct RespawnPacket {
+        dimension: i32,
+        difficulty: u8,
+        game_mode: u8,
+        #[falcon(string = 16)]
+        level_type: String,
+    }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x00;
+    }, name = "spawn_object")]
+    pub struct SpawnObjectPacket {
+        #[falcon(var32)]
+        entity_id: i32,
+        uuid: Uuid,
+        object_type: u8,
+        x: f64,
+        y: f64,
+        z: f64,
+        pitch: u8,
+        yaw: u8,
+        data: i32,
+        velocity_x: i16,
+        velocity_y: i16,
+        velocity_z: i16,
+    }
+
+    impl From<SpawnEntitySpec> for SpawnObjectPacket {
+        fn from(spec: SpawnEntitySpec) -> Self {
+            let entity = &spec.entity;
+            let look = entity.look();
+            // item frames are placed at the block they hang at
+            let (object_type, position, data) = match entity.kind() {
+                EntityKind::ItemFrame {
+                    hanging_at, facing, ..
+                } => (71, (hanging_at.get_x() as f64, hanging_at.get_y() as f64, hanging_at.get_z() as f64), facing.id()),
+                _ => (78, (entity.position().x, entity.position().y, entity.position().z), 0),
+            };
+            SpawnObjectPacket {
+                entity_id: spec.entity_id,
+                uuid: spec.uuid,
+                object_type,
+                x: position.0,
+                y: position.1,
+                z: position.2,
+                pitch: angle(look.pitch),
+                yaw: angle(look.yaw),
+                data,
+                velocity_x: 0,
+                velocity_y: 0,
+                velocity_z: 0,
+            }
+        }
+    }
+
+    /// Armor stands are spawned as objects before 1.14.
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x00;
+    }, name = "spawn_living_entity")]
+    pub struct SpawnArmorStandPacket {
+        object: SpawnObjectPacket,
+    }
+
+    impl From<SpawnEntitySpec> for SpawnArmorStandPacket {
+        fn from(spec: SpawnEntitySpec) -> Self {
+            SpawnArmorStandPacket {
+                object: spec.into(),
+            }
+        }
+    }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x04;
+    }, name = "spawn_painting")]
+    pub struct SpawnPaintingPacket {
+        #[falcon(var32)]
+        entity_id: i32,
+        uuid: Uuid,
+        #[falcon(var32)]
+        motive: i32,
+        location: i64,
+        direction: u8,
+    }
+
+    impl From<SpawnEntitySpec> for SpawnPaintingPacket {
+        fn from(spec: SpawnEntitySpec) -> Self {
+            let (motive, facing) = painting(&spec);
+            SpawnPaintingPacket {
+                entity_id: spec.entity_id,
+                uuid: spec.uuid,
+                motive,
+                location: position_xyz(spec.entity.block_position()),
+                direction: facing,
+            }
+        }
+    }
+
+    /// The motive id and horizontal direction of a painting.
+    pub(crate) fn painting(spec: &SpawnEntitySpec) -> (i32, u8) {
+        match spec.entity.kind() {
+            EntityKind::Painting { facing, motive, .. } => (*motive as i32, facing.horizontal_id().unwrap_or(0) as u8),
+            _ => (0, 0),
+        }
+    }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x3F;
+    }, name = "entity_metadata")]
+    pub struct EntityMetadataPacket {
+        #[falcon(var32)]
+        entity_id: i32,
+        metadata: Metadata,
+    }
+
+    impl From<EntityMetadataSpec> for EntityMetadataPacket {
+        fn from(spec: EntityMetadataSpec) -> Self {
+            EntityMetadataPacket {
+                entity_id: spec.entity_id,
+                metadata: Metadata::of_entity(&spec.entity, 11, 6),
+            }
+        }
+    }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x35;
+        477, 480, 485, 490, 498, 735, 736 = 0x37;
+        573, 575, 578 = 0x38;
+    }, name = "destroy_entities")]
+    pub struct DestroyEntitiesPacket {
+        #[falcon(var32)]
+        count: usize,
+        #[falcon(vec = "count")]
+        entity_ids: Vec<VarI32>,
+    }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x0B;
+    }, name = "block_change")]
+    pub struct BlockChangePacket {
+        location: i64,
+        #[falcon(var32)]
+        block_id: i32,
+    }
+
+    impl From<BlockChangeSpec> for BlockChangePacket {
+        fn from(spec: BlockChangeSpec) -> Self {
+            BlockChangePacket {
+                location: position_xyz(spec.position),
+                block_id: block_to_int(spec.protocol_version)(&spec.block).unwrap_or(0),
+            }
+        }
+    }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        393, 401, 404 = 0x0F;
+    }, name = "multi_block_change")]
+    pub struct MultiBlockChangePacket {
+        chunk_x: i32,
+        chunk_z: i32,
+        records: BlockRecords,
+    }
+
+    impl From<MultiBlockChangeSpec> for MultiBlockChangePacket {
+        fn from(spec: MultiBlockChangeSpec) -> Self {
+            MultiBlockChangePacket {
+                chunk_x: spec.chunk.x,
+                chunk_z: spec.chunk.z,
+                records: BlockRecords::new(&spec.blocks, block_to_int(spec.protocol_version)),
+            }
+        }
+    }
+
+    fn block_to_int(protocol_version: i32) -> PaletteToI32<Blocks> {
+        match protocol_version {
+            401 | 404 => Blocks::get_global_id_1631,
+            _ => Blocks::get_global_id_1519,
+        }
+    }
+
+    impl From<Vec<i32>> for DestroyEntitiesPacket {
+        fn from(entity_ids: Vec<i32>) -> Self {
+            DestroyEntitiesPacket {
+                count: entity_ids.len(),
+                entity_ids: entity_ids.into_iter().map(VarI32::from).collect(),
+            }
+        }
+    }
 }
@@ -0,0 +1,59 @@
+#[falcon_send_derive::falcon_send]
+mod inner {
+    use falcon_core::world::blocks::Blocks;
+    use falcon_core::world::palette::PaletteToI32;
+    use falcon_packet_core::{PacketSize, PacketWrite};
+
+    use crate::specs::play::{BlockChangeSpec, MultiBlockChangeSpec};
+    use crate::util::{position_xzy, BlockRecords};
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        477, 480, 485, 490, 498, 735, 736 = 0x0B;
+        573, 575, 578 = 0x0C;
+    }, name = "block_change")]
+    pub struct BlockChangePacket {
+        location: i64,
+        #[falcon(var32)]
+        block_id: i32,
+    }
+
+    impl From<BlockChangeSpec> for BlockChangePacket {
+        fn from(spec: BlockChangeSpec) -> Self {
+            BlockChangePacket {
+                location: position_xzy(spec.position),
+                block_id: block_to_int(spec.protocol_version)(&spec.block).unwrap_or(0),
+            }
+        }
+    }
+
+    /// Versions from 1.16.2 on send one packet per section instead.
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        477, 480, 485, 490, 498, 735, 736 = 0x0F;
+        573, 575, 578 = 0x10;
+    }, name = "multi_block_change")]
+    pub struct MultiBlockChangePacket {
+        chunk_x: i32,
+        chunk_z: i32,
+        records: BlockRecords,
+    }
+
+    impl From<MultiBlockChangeSpec> for MultiBlockChangePacket {
+        fn from(spec: MultiBlockChangeSpec) -> Self {
+            MultiBlockChangePacket {
+                chunk_x: spec.chunk.x,
+                chunk_z: spec.chunk.z,
+                records: BlockRecords::new(&spec.blocks, block_to_int(spec.protocol_version)),
+            }
+        }
+    }
+
+    fn block_to_int(protocol_version: i32) -> PaletteToI32<Blocks> {
+        match protocol_version {
+            735 | 736 => Blocks::get_global_id_2567,
+            573 | 575 | 578 => Blocks::get_global_id_2230,
+            _ => Blocks::get_global_id_1976,
+        }
+    }
+}
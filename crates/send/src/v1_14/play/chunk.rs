@@ -2,20 +2,17 @@
 mod inner {
     use bytes::BufMut;
     use falcon_core::world::blocks::Blocks;
-    use falcon_core::world::chunks::{SECTION_HEIGHT, SECTION_LENGTH, SECTION_WIDTH};
+    use falcon_core::world::chunks::{SECTION_LENGTH, SECTION_WIDTH};
     use falcon_core::world::palette::PaletteToI32;
     use falcon_packet_core::{PacketArray, PacketIter, PacketSize, PacketVec, PacketWrite, PacketWriteSeed, VarI32, WriteError};
     use fastnbt::LongArray;
     use serde::Serialize;
 
     use crate::specs::play::{ChunkDataSpec, ChunkSectionDataSpec};
-    use crate::util::HeightMap;
-    use crate::v1_13::play::build_compacted_data_array;
-
-    const MAX_BITS_PER_BLOCK: u8 = 14;
+    use crate::util::{block_count, BlockEntityList, HeightMap};
+    use crate::v1_13::play::{build_compacted_data_array, column_biomes, section_block_data};
 
     const BIOME_COUNT: u16 = SECTION_WIDTH * SECTION_LENGTH;
-    const BIOMES: [i32; BIOME_COUNT as usize] = [0; BIOME_COUNT as usize];
 
     #[derive(PacketSize, PacketWrite)]
     #[falcon_packet(versions = {
@@ -32,9 +29,8 @@ mod inner {
         #[falcon(var32)]
         size: usize,
         #[falcon(link = "size with data")]
-        sections: Vec<ChunkSectionData>,
-        #[falcon(var32)]
-        block_entity_num: i32, // default 0
+        data: ChunkColumnData,
+        block_entities: BlockEntityList,
     }
 
     #[derive(Serialize)]
@@ -56,16 +52,19 @@ mod inner {
         }
     }
 
+    struct ChunkColumnData {
+        sections: Vec<ChunkSectionData>,
+        biomes: [i32; BIOME_COUNT as usize],
+    }
+
     #[inline(always)]
-    #[allow(clippy::ptr_arg)]
-    pub(crate) fn data_value(field: &[ChunkSectionData]) -> usize { data_size(field) }
+    fn data_value(field: &ChunkColumnData) -> usize { data_size(field) }
 
-    #[allow(clippy::ptr_arg)]
-    pub(crate) fn data_size(field: &[ChunkSectionData]) -> usize { PacketIter::new(field.iter()).size_ref() + BIOME_COUNT as usize * 4 }
+    fn data_size(field: &ChunkColumnData) -> usize { PacketIter::new(field.sections.iter()).size_ref() + BIOME_COUNT as usize * 4 }
 
-    pub(crate) fn data_write<B: BufMut + ?Sized>(field: &[ChunkSectionData], buffer: &mut B) -> Result<(), WriteError> {
-        PacketIter::new(field.iter()).write_ref(buffer)?;
-        PacketWriteSeed::write(PacketArray::default(), &BIOMES, buffer)
+    fn data_write<B: BufMut + ?Sized>(field: &ChunkColumnData, buffer: &mut B) -> Result<(), WriteError> {
+        PacketIter::new(field.sections.iter()).write_ref(buffer)?;
+        PacketWriteSeed::write(PacketArray::default(), &field.biomes, buffer)
     }
 
     impl From<ChunkDataSpec> for ChunkDataPacket {
@@ -77,12 +76,15 @@ mod inner {
                 bitmask: spec.bitmask,
                 heightmap: HeightMap::from_sections(&spec.sections, Blocks::get_global_id_1976).into(),
                 size: 0,
-                sections: spec
-                    .sections
-                    .into_iter()
-                    .map(|e| into_chunk_section(e, Blocks::get_global_id_1976))
-                    .collect(),
-                block_entity_num: 0,
+                data: ChunkColumnData {
+                    sections: spec
+                        .sections
+                        .into_iter()
+                        .map(|e| into_chunk_section(e, Blocks::get_global_id_1976))
+                        .collect(),
+                    biomes: column_biomes(&spec.biomes),
+                },
+                block_entities: spec.block_entities.into(),
             }
         }
     }
@@ -126,41 +128,8 @@ mod inner {
     }
 
     pub(crate) fn into_chunk_section(spec: ChunkSectionDataSpec, block_to_int: PaletteToI32<Blocks>) -> ChunkSectionData {
-        let bits_per_block = {
-            let actual = spec.palette.calculate_bits_per_entry(block_to_int);
-            if actual < 4 {
-                4u8
-            } else if actual < 9 {
-                actual as u8
-            } else {
-                MAX_BITS_PER_BLOCK
-            }
-        };
-
-        let mut block_count = 0;
-        let block_iterator = spec.blocks.into_iter().inspect(|v| {
-            let block = spec.palette.at(*v as usize).unwrap();
-            if !matches!(block, Blocks::Air | Blocks::VoidAir | Blocks::CaveAir) && block_to_int(block).is_some() {
-                block_count += 1;
-            }
-        });
-        let (block_data, palette) = if bits_per_block > 8 {
-            let blocks = spec.palette.build_direct_palette(block_iterator, block_to_int, Blocks::Air);
-            let block_data = build_compacted_data_array(
-                MAX_BITS_PER_BLOCK,
-                (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH * bits_per_block as u16) as u32 / i64::BITS,
-                blocks,
-            );
-            (block_data, None)
-        } else {
-            let (blocks, palette) = spec.palette.build_indirect_palette(block_iterator, block_to_int, Blocks::Air);
-            let block_data = build_compacted_data_array(
-                bits_per_block,
-                (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH * bits_per_block as u16) as u32 / i64::BITS,
-                blocks,
-            );
-            (block_data, Some(palette))
-        };
+        let block_count = block_count(&spec.palette, &spec.counts, block_to_int);
+        let (bits_per_block, block_data, palette) = section_block_data(&spec.palette, spec.blocks, block_to_int);
         ChunkSectionData {
             block_count,
             bits_per_block,
@@ -0,0 +1,158 @@
+#[falcon_send_derive::falcon_send]
+mod inner {
+    use falcon_core::world::entity::EntityKind;
+    use falcon_packet_core::{PacketSize, PacketWrite};
+    use uuid::Uuid;
+
+    use crate::specs::play::{EntityMetadataSpec, SpawnEntitySpec};
+    use crate::util::{angle, position_xzy, Metadata};
+    use crate::v1_13::play::painting;
+
+    /// Entity type ids of 1.14.
+    const ARMOR_STAND: i32 = 1;
+    const ITEM_FRAME: i32 = 35;
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        477, 480, 485, 490, 498 = 0x00;
+    }, name = "spawn_object")]
+    pub struct SpawnObjectPacket {
+        #[falcon(var32)]
+        entity_id: i32,
+        uuid: Uuid,
+        #[falcon(var32)]
+        entity_type: i32,
+        x: f64,
+        y: f64,
+        z: f64,
+        pitch: u8,
+        yaw: u8,
+        data: i32,
+        velocity_x: i16,
+        velocity_y: i16,
+        velocity_z: i16,
+    }
+
+    impl From<SpawnEntitySpec> for SpawnObjectPacket {
+        fn from(spec: SpawnEntitySpec) -> Self { SpawnObjectPacket::new(spec, ITEM_FRAME) }
+    }
+
+    impl SpawnObjectPacket {
+        /// Item frames are placed at the block they hang at.
+        pub(crate) fn new(spec: SpawnEntitySpec, item_frame_type: i32) -> Self {
+            let entity = &spec.entity;
+            let look = entity.look();
+            let (position, data) = match entity.kind() {
+                EntityKind::ItemFrame {
+                    hanging_at, facing, ..
+                } => ((hanging_at.get_x() as f64, hanging_at.get_y() as f64, hanging_at.get_z() as f64), facing.id()),
+                _ => ((entity.position().x, entity.position().y, entity.position().z), 0),
+            };
+            SpawnObjectPacket {
+                entity_id: spec.entity_id,
+                uuid: spec.uuid,
+                entity_type: item_frame_type,
+                x: position.0,
+                y: position.1,
+                z: position.2,
+                pitch: angle(look.pitch),
+                yaw: angle(look.yaw),
+                data,
+                velocity_x: 0,
+                velocity_y: 0,
+                velocity_z: 0,
+            }
+        }
+    }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        477, 480, 485, 490, 498 = 0x03;
+    }, name = "spawn_living_entity")]
+    pub struct SpawnLivingEntityPacket {
+        #[falcon(var32)]
+        entity_id: i32,
+        uuid: Uuid,
+        #[falcon(var32)]
+        entity_type: i32,
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: u8,
+        pitch: u8,
+        head_yaw: u8,
+        velocity_x: i16,
+        velocity_y: i16,
+        velocity_z: i16,
+        /// Sent in a separate packet.
+        metadata: Metadata,
+    }
+
+    impl From<SpawnEntitySpec> for SpawnLivingEntityPacket {
+        fn from(spec: SpawnEntitySpec) -> Self {
+            let (position, look) = (spec.entity.position(), spec.entity.look());
+            SpawnLivingEntityPacket {
+                entity_id: spec.entity_id,
+                uuid: spec.uuid,
+                entity_type: ARMOR_STAND,
+                x: position.x,
+                y: position.y,
+                z: position.z,
+                yaw: angle(look.yaw),
+                pitch: angle(look.pitch),
+                head_yaw: angle(look.yaw),
+                velocity_x: 0,
+                velocity_y: 0,
+                velocity_z: 0,
+                metadata: Metadata::default(),
+            }
+        }
+    }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        477, 480, 485, 490, 498, 573, 575, 578 = 0x04;
+        735, 736 = 0x03;
+    }, name = "spawn_painting")]
+    pub struct SpawnPaintingPacket {
+        #[falcon(var32)]
+        entity_id: i32,
+        uuid: Uuid,
+        #[falcon(var32)]
+        motive: i32,
+        location: i64,
+        direction: u8,
+    }
+
+    impl From<SpawnEntitySpec> for SpawnPaintingPacket {
+        fn from(spec: SpawnEntitySpec) -> Self {
+            let (motive, facing) = painting(&spec);
+            SpawnPaintingPacket {
+                entity_id: spec.entity_id,
+                uuid: spec.uuid,
+                motive,
+                location: position_xzy(spec.entity.block_position()),
+                direction: facing,
+            }
+        }
+    }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        477, 480, 485, 490, 498 = 0x43;
+    }, name = "entity_metadata")]
+    pub struct EntityMetadataPacket {
+        #[falcon(var32)]
+        entity_id: i32,
+        metadata: Metadata,
+    }
+
+    impl From<EntityMetadataSpec> for EntityMetadataPacket {
+        fn from(spec: EntityMetadataSpec) -> Self {
+            EntityMetadataPacket {
+                entity_id: spec.entity_id,
+                metadata: Metadata::of_entity(&spec.entity, 13, 7),
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+#[falcon_send_derive::falcon_send]
+mod inner {
+    use bytes::BufMut;
+    use falcon_packet_core::{PacketSize, PacketWrite, VarI32, WriteError};
+
+    use crate::specs::play::UpdateLightSpec;
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        477, 480, 485, 490, 498 = 0x24;
+        573, 575, 578 = 0x25;
+    }, name = "update_light")]
+    pub struct UpdateLightPacket {
+        #[falcon(var32)]
+        chunk_x: i32,
+        #[falcon(var32)]
+        chunk_z: i32,
+        #[falcon(var32)]
+        sky_mask: i32,
+        #[falcon(var32)]
+        block_mask: i32,
+        #[falcon(var32)]
+        empty_sky_mask: i32,
+        #[falcon(var32)]
+        empty_block_mask: i32,
+        sky_light: LightArrays,
+        block_light: LightArrays,
+    }
+
+    impl From<UpdateLightSpec> for UpdateLightPacket {
+        fn from(spec: UpdateLightSpec) -> Self {
+            let (sky_mask, empty_sky_mask, sky_light) = LightArrays::with_masks(spec.sky_light.unwrap_or_default());
+            let (block_mask, empty_block_mask, block_light) = LightArrays::with_masks(spec.block_light);
+            UpdateLightPacket {
+                chunk_x: spec.chunk_x,
+                chunk_z: spec.chunk_z,
+                sky_mask,
+                block_mask,
+                empty_sky_mask,
+                empty_block_mask,
+                sky_light,
+                block_light,
+            }
+        }
+    }
+
+    /// Light arrays prefixed by their length, without a count.
+    pub(crate) struct LightArrays(Vec<Vec<u8>>);
+
+    impl LightArrays {
+        /// Splits the light of every section into a mask of the sections that
+        /// are sent and a mask of the sections without any light.
+        pub(crate) fn with_masks(sections: Vec<Vec<u8>>) -> (i32, i32, LightArrays) {
+            let (mut mask, mut empty_mask) = (0, 0);
+            let mut arrays = Vec::with_capacity(sections.len());
+            for (i, section) in sections.into_iter().enumerate() {
+                if section.iter().all(|light| *light == 0) {
+                    empty_mask |= 1 << i;
+                } else {
+                    mask |= 1 << i;
+                    arrays.push(section);
+                }
+            }
+            (mask, empty_mask, LightArrays(arrays))
+        }
+    }
+
+    impl PacketSize for LightArrays {
+        fn size(&self) -> usize { self.0.iter().map(|array| VarI32::from(array.len()).size() + array.len()).sum() }
+    }
+
+    impl PacketWrite for LightArrays {
+        fn write<B>(&self, buffer: &mut B) -> Result<(), WriteError>
+        where
+            B: BufMut + ?Sized,
+        {
+            for array in &self.0 {
+                VarI32::from(array.len()).write(buffer)?;
+                buffer.put_slice(array);
+            }
+            Ok(())
+        }
+    }
+}
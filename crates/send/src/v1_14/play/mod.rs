@@ -1,13 +1,19 @@
+mod block;
 mod chunk;
+mod entity;
+mod light;
 
+pub use block::*;
 pub use chunk::*;
+pub use entity::*;
+pub use light::*;
 
 #[falcon_send_derive::falcon_send]
 mod inner {
     use derive_from_ext::From;
     use falcon_packet_core::{PacketSize, PacketWrite};
 
-    use crate::{JoinGameSpec, ServerDifficultySpec};
+    use crate::{JoinGameSpec, RespawnSpec, ServerDifficultySpec};
 
     #[derive(PacketSize, PacketWrite, From)]
     #[from(JoinGameSpec)]
@@ -52,4 +58,16 @@ mod inner {
     impl From<(i32, i32)> for UpdateViewPosition {
         fn from((chunk_x, chunk_z): (i32, i32)) -> Self { UpdateViewPosition { chunk_x, chunk_z } }
     }
+
+    #[derive(PacketSize, PacketWrite, From)]
+    #[from(RespawnSpec)]
+    #[falcon_packet(versions = {
+        477, 480, 485, 490, 498 = 0x3A;
+    }, name = "respawn")]
+    pub struct RespawnPacket {
+        dimension: i32,
+        game_mode: u8,
+        #[falcon(string = 16)]
+        level_type: String,
+    }
 }
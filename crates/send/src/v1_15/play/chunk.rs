@@ -2,15 +2,13 @@
 mod inner {
     use bytes::BufMut;
     use falcon_core::world::blocks::Blocks;
+    use falcon_core::world::chunks::BIOME_CELLS;
     use falcon_packet_core::{PacketIter, PacketSize, PacketWrite, WriteError};
 
     use crate::specs::play::ChunkDataSpec;
-    use crate::util::HeightMap;
+    use crate::util::{BlockEntityList, HeightMap};
     use crate::v1_14::play::{into_chunk_section, ChunkSectionData, PacketHeightMap};
 
-    const BIOME_COUNT: u16 = 1024;
-    const BIOMES: [i32; BIOME_COUNT as usize] = [0; BIOME_COUNT as usize];
-
     #[derive(PacketSize, PacketWrite)]
     #[falcon_packet(versions = {
         573, 575, 578 = 0x22;
@@ -24,13 +22,12 @@ mod inner {
         #[falcon(nbt)]
         heightmap: PacketHeightMap,
         #[falcon(array)]
-        biomes: [i32; BIOME_COUNT as usize],
+        biomes: [i32; BIOME_CELLS],
         #[falcon(var32)]
         size: usize,
         #[falcon(link = "size with data")]
         sections: Vec<ChunkSectionData>,
-        #[falcon(var32)]
-        block_entity_num: i32, // default 0
+        block_entities: BlockEntityList,
     }
 
     #[inline(always)]
@@ -52,14 +49,14 @@ mod inner {
                 full_chunk: true,
                 bitmask: spec.bitmask,
                 heightmap: HeightMap::from_sections(&spec.sections, Blocks::get_global_id_2230).into(),
-                biomes: BIOMES,
+                biomes: spec.biomes,
                 size: 0,
                 sections: spec
                     .sections
                     .into_iter()
                     .map(|e| into_chunk_section(e, Blocks::get_global_id_2230))
                     .collect(),
-                block_entity_num: 0,
+                block_entities: spec.block_entities.into(),
             }
         }
     }
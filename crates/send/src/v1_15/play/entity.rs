@@ -0,0 +1,91 @@
+#[falcon_send_derive::falcon_send]
+mod inner {
+    use falcon_packet_core::{PacketSize, PacketWrite};
+    use uuid::Uuid;
+
+    use crate::specs::play::{EntityMetadataSpec, SpawnEntitySpec};
+    use crate::util::{angle, Metadata};
+    use crate::v1_14::play::SpawnObjectPacket as SpawnObjectPacket1_14;
+
+    /// Entity type ids of 1.15.
+    const ARMOR_STAND: i32 = 1;
+    const ITEM_FRAME: i32 = 36;
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        573, 575, 578 = 0x00;
+    }, name = "spawn_object")]
+    pub struct SpawnObjectPacket {
+        object: SpawnObjectPacket1_14,
+    }
+
+    impl From<SpawnEntitySpec> for SpawnObjectPacket {
+        fn from(spec: SpawnEntitySpec) -> Self {
+            SpawnObjectPacket {
+                object: SpawnObjectPacket1_14::new(spec, ITEM_FRAME),
+            }
+        }
+    }
+
+    /// The metadata is no longer part of this packet.
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        573, 575, 578 = 0x03;
+        735, 736 = 0x02;
+    }, name = "spawn_living_entity")]
+    pub struct SpawnLivingEntityPacket {
+        #[falcon(var32)]
+        entity_id: i32,
+        uuid: Uuid,
+        #[falcon(var32)]
+        entity_type: i32,
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: u8,
+        pitch: u8,
+        head_yaw: u8,
+        velocity_x: i16,
+        velocity_y: i16,
+        velocity_z: i16,
+    }
+
+    impl From<SpawnEntitySpec> for SpawnLivingEntityPacket {
+        fn from(spec: SpawnEntitySpec) -> Self {
+            let (position, look) = (spec.entity.position(), spec.entity.look());
+            SpawnLivingEntityPacket {
+                entity_id: spec.entity_id,
+                uuid: spec.uuid,
+                entity_type: ARMOR_STAND,
+                x: position.x,
+                y: position.y,
+                z: position.z,
+                yaw: angle(look.yaw),
+                pitch: angle(look.pitch),
+                head_yaw: angle(look.yaw),
+                velocity_x: 0,
+                velocity_y: 0,
+                velocity_z: 0,
+            }
+        }
+    }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        573, 575, 578, 735, 736 = 0x44;
+    }, name = "entity_metadata")]
+    pub struct EntityMetadataPacket {
+        #[falcon(var32)]
+        entity_id: i32,
+        metadata: Metadata,
+    }
+
+    impl From<EntityMetadataSpec> for EntityMetadataPacket {
+        fn from(spec: EntityMetadataSpec) -> Self {
+            EntityMetadataPacket {
+                entity_id: spec.entity_id,
+                metadata: Metadata::of_entity(&spec.entity, 14, 7),
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
 mod chunk;
+mod entity;
 
 pub use chunk::*;
+pub use entity::*;
 #[falcon_send_derive::falcon_send]
 mod inner {
     use derive_from_ext::From;
     use falcon_packet_core::{PacketSize, PacketWrite};
 
-    use crate::JoinGameSpec;
+    use crate::{JoinGameSpec, RespawnSpec};
 
     #[derive(PacketSize, PacketWrite, From)]
     #[from(JoinGameSpec)]
@@ -26,4 +28,17 @@ mod inner {
         reduced_debug: bool,
         enable_respawn_screen: bool,
     }
+
+    #[derive(PacketSize, PacketWrite, From)]
+    #[from(RespawnSpec)]
+    #[falcon_packet(versions = {
+        573, 575, 578 = 0x3B;
+    }, name = "respawn")]
+    pub struct RespawnPacket {
+        dimension: i32,
+        hashed_seed: i64,
+        game_mode: u8,
+        #[falcon(string = 16)]
+        level_type: String,
+    }
 }
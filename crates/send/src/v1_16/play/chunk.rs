@@ -2,21 +2,18 @@
 mod inner {
     use bytes::BufMut;
     use falcon_core::world::blocks::Blocks;
-    use falcon_core::world::chunks::{SECTION_HEIGHT, SECTION_LENGTH, SECTION_WIDTH};
+    use falcon_core::world::chunks::{BIOME_CELLS, SECTION_HEIGHT, SECTION_LENGTH, SECTION_WIDTH};
     use falcon_core::world::palette::PaletteToI32;
     use falcon_packet_core::{PacketIter, PacketSize, PacketWrite, WriteError};
     use fastnbt::LongArray;
     use serde::Serialize;
 
-    use crate::util::HeightMap;
+    use crate::util::{block_count, client_block_data, palette_ids, BlockEntityList, HeightMap};
     use crate::v1_14::play::ChunkSectionData;
     use crate::{ChunkDataSpec, ChunkSectionDataSpec};
 
     const MAX_BITS_PER_BLOCK: u8 = 15;
 
-    const BIOME_COUNT: u16 = 1024;
-    const BIOMES: [i32; BIOME_COUNT as usize] = [0; BIOME_COUNT as usize];
-
     #[derive(PacketSize, PacketWrite)]
     #[falcon_packet(versions = {
         735, 736 = 0x21;
@@ -31,13 +28,12 @@ mod inner {
         #[falcon(nbt)]
         heightmap: PacketHeightMap,
         #[falcon(array)]
-        biomes: [i32; BIOME_COUNT as usize],
+        biomes: [i32; BIOME_CELLS],
         #[falcon(var32)]
         size: usize,
         #[falcon(link = "size with data")]
         sections: Vec<ChunkSectionData>,
-        #[falcon(var32)]
-        block_entity_num: i32,
+        block_entities: BlockEntityList,
     }
 
     #[derive(Serialize)]
@@ -79,53 +75,33 @@ mod inner {
                 ignore_old: false,
                 bitmask: spec.bitmask,
                 heightmap: HeightMap::from_sections(&spec.sections, Blocks::get_global_id_2567).into(),
-                biomes: BIOMES,
+                biomes: spec.biomes,
                 size: 0,
                 sections: spec
                     .sections
                     .into_iter()
                     .map(|e| into_chunk_section(e, Blocks::get_global_id_2567))
                     .collect(),
-                block_entity_num: 0,
+                block_entities: spec.block_entities.into(),
             }
         }
     }
 
     pub(crate) fn into_chunk_section(spec: ChunkSectionDataSpec, block_to_int: PaletteToI32<Blocks>) -> ChunkSectionData {
-        let bits_per_block = {
-            let actual = spec.palette.calculate_bits_per_entry(block_to_int);
-            if actual < 4 {
-                4u8
-            } else if actual < 9 {
-                actual as u8
-            } else {
-                MAX_BITS_PER_BLOCK
-            }
-        };
-
-        let mut block_count = 0;
-        let block_iterator = spec.blocks.into_iter().inspect(|v| {
-            let block = spec.palette.at(*v as usize).unwrap();
-            if !matches!(block, Blocks::Air | Blocks::VoidAir | Blocks::CaveAir) && block_to_int(block).is_some() {
-                block_count += 1;
-            }
-        });
-        let (block_data, palette) = if bits_per_block > 8 {
-            let blocks = spec.palette.build_direct_palette(block_iterator, block_to_int, Blocks::Air);
+        let block_count = block_count(&spec.palette, &spec.counts, block_to_int);
+        let ids = palette_ids(&spec.palette, block_to_int);
+        let blocks = client_block_data(spec.blocks);
+        // the section data is packed like 1.16 expects it
+        let (bits_per_block, block_data, palette) = if blocks.bits() > 8 {
+            let blocks = blocks.iter().map(|value| ids[value as usize] as u64);
             let block_data = build_compacted_data_array(
                 MAX_BITS_PER_BLOCK,
-                (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH * bits_per_block as u16) as u32 / i64::BITS,
+                (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH * MAX_BITS_PER_BLOCK as u16) as u32 / i64::BITS,
                 blocks,
             );
-            (block_data, None)
+            (MAX_BITS_PER_BLOCK, block_data, None)
         } else {
-            let (blocks, palette) = spec.palette.build_indirect_palette(block_iterator, block_to_int, Blocks::Air);
-            let block_data = build_compacted_data_array(
-                bits_per_block,
-                (SECTION_WIDTH * SECTION_HEIGHT * SECTION_LENGTH * bits_per_block as u16) as u32 / i64::BITS,
-                blocks,
-            );
-            (block_data, Some(palette))
+            (blocks.bits(), blocks.into_data(), Some(ids))
         };
         ChunkSectionData {
             block_count,
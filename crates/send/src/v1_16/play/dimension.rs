@@ -11,6 +11,10 @@ impl Codec {
     pub fn new(dimension: Vec<DimensionData>) -> Self { Codec { dimension } }
 }
 
+impl From<Vec<Dimension>> for Codec {
+    fn from(dimensions: Vec<Dimension>) -> Self { Codec::new(dimensions.into_iter().map(DimensionData::new).collect()) }
+}
+
 pub struct DimensionData {
     dimension: Dimension,
 }
@@ -24,20 +28,25 @@ impl Serialize for DimensionData {
     where
         S: Serializer,
     {
-        let mut serializer = serializer.serialize_struct("dimension", 13)?;
-        serializer.serialize_field("name", self.dimension.name())?;
-        serializer.serialize_field("natural", &true)?;
-        serializer.serialize_field("ambient_light", &0.5f32)?;
-        serializer.serialize_field("has_ceiling", &false)?;
-        serializer.serialize_field("has_skylight", &true)?;
-        serializer.serialize_field("shrunk", &false)?;
-        serializer.serialize_field("ultrawarm", &false)?;
-        serializer.serialize_field("has_raids", &true)?;
-        serializer.serialize_field("respawn_anchor_works", &true)?;
-        serializer.serialize_field("bed_works", &true)?;
-        serializer.serialize_field("piglin_safe", &true)?;
-        serializer.serialize_field("logical_height", &256i32)?;
-        serializer.serialize_field("infiniburn", "minecraft:infiniburn_overworld")?;
+        let dimension = &self.dimension;
+        let mut serializer = serializer.serialize_struct("dimension", 14)?;
+        serializer.serialize_field("name", &dimension.name().to_string())?;
+        serializer.serialize_field("natural", &dimension.natural())?;
+        serializer.serialize_field("ambient_light", &dimension.ambient_light())?;
+        match dimension.fixed_time() {
+            Some(time) => serializer.serialize_field("fixed_time", &time)?,
+            None => serializer.skip_field("fixed_time")?,
+        }
+        serializer.serialize_field("has_ceiling", &dimension.has_ceiling())?;
+        serializer.serialize_field("has_skylight", &dimension.has_skylight())?;
+        serializer.serialize_field("shrunk", &dimension.shrunk())?;
+        serializer.serialize_field("ultrawarm", &dimension.ultrawarm())?;
+        serializer.serialize_field("has_raids", &dimension.has_raids())?;
+        serializer.serialize_field("respawn_anchor_works", &dimension.respawn_anchor_works())?;
+        serializer.serialize_field("bed_works", &dimension.bed_works())?;
+        serializer.serialize_field("piglin_safe", &dimension.piglin_safe())?;
+        serializer.serialize_field("logical_height", &dimension.logical_height())?;
+        serializer.serialize_field("infiniburn", &dimension.infiniburn().to_string())?;
         serializer.end()
     }
 }
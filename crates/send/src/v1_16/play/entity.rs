@@ -0,0 +1,26 @@
+#[falcon_send_derive::falcon_send]
+mod inner {
+    use falcon_packet_core::{PacketSize, PacketWrite};
+
+    use crate::specs::play::SpawnEntitySpec;
+    use crate::v1_14::play::SpawnObjectPacket as SpawnObjectPacket1_14;
+
+    /// The item frame entity type id of 1.16.
+    const ITEM_FRAME: i32 = 38;
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        735, 736 = 0x00;
+    }, name = "spawn_object")]
+    pub struct SpawnObjectPacket {
+        object: SpawnObjectPacket1_14,
+    }
+
+    impl From<SpawnEntitySpec> for SpawnObjectPacket {
+        fn from(spec: SpawnEntitySpec) -> Self {
+            SpawnObjectPacket {
+                object: SpawnObjectPacket1_14::new(spec, ITEM_FRAME),
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+#[falcon_send_derive::falcon_send]
+mod inner {
+    use falcon_packet_core::{PacketSize, PacketWrite};
+
+    use crate::specs::play::UpdateLightSpec;
+    use crate::v1_14::play::LightArrays;
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        735, 736 = 0x24;
+    }, name = "update_light")]
+    pub struct UpdateLightPacket {
+        #[falcon(var32)]
+        chunk_x: i32,
+        #[falcon(var32)]
+        chunk_z: i32,
+        trust_edges: bool,
+        #[falcon(var32)]
+        sky_mask: i32,
+        #[falcon(var32)]
+        block_mask: i32,
+        #[falcon(var32)]
+        empty_sky_mask: i32,
+        #[falcon(var32)]
+        empty_block_mask: i32,
+        sky_light: LightArrays,
+        block_light: LightArrays,
+    }
+
+    impl From<UpdateLightSpec> for UpdateLightPacket {
+        fn from(spec: UpdateLightSpec) -> Self {
+            let (sky_mask, empty_sky_mask, sky_light) = LightArrays::with_masks(spec.sky_light.unwrap_or_default());
+            let (block_mask, empty_block_mask, block_light) = LightArrays::with_masks(spec.block_light);
+            UpdateLightPacket {
+                chunk_x: spec.chunk_x,
+                chunk_z: spec.chunk_z,
+                trust_edges: true,
+                sky_mask,
+                block_mask,
+                empty_sky_mask,
+                empty_block_mask,
+                sky_light,
+                block_light,
+            }
+        }
+    }
+}
@@ -1,18 +1,23 @@
 mod chunk;
 pub mod dimension;
+mod entity;
+mod light;
 
 pub use chunk::*;
+pub use entity::*;
+pub use light::*;
 
 #[falcon_send_derive::falcon_send]
 mod inner {
     use bytes::BufMut;
     use derive_from_ext::From;
     use falcon_core::data::Identifier;
-    use falcon_core::world::dimension::Dimension;
     use falcon_packet_core::{PacketSize, PacketSizeSeed, PacketString, PacketWrite, PacketWriteSeed, WriteError};
+    use mc_chat::ChatComponent;
+    use uuid::Uuid;
 
-    use crate::v1_16::play::dimension::{Codec, DimensionData};
-    use crate::JoinGameSpec;
+    use crate::v1_16::play::dimension::Codec;
+    use crate::{JoinGameSpec, RespawnSpec};
 
     #[derive(PacketSize, PacketWrite, From)]
     #[from(JoinGameSpec)]
@@ -27,16 +32,15 @@ mod inner {
         #[from(skip)]
         #[falcon(var32)]
         world_count: usize,
-        #[from(skip, default = "init_worlds()")]
+        #[from(rename = "world_names")]
         #[falcon(link = "world_count with worlds")]
         worlds: Vec<Identifier>,
-        #[from(skip, default = "init_dimension_codec()")]
+        #[from(rename = "dimensions", map = "Codec::from")]
         #[falcon(nbt)]
         dimension_codec: Codec,
-        #[from(skip, default = "init_dimension()")]
+        #[from(rename = "dimension_name")]
         #[falcon(to_string)]
         dimention: Identifier,
-        #[from(skip, default = "init_world()")]
         #[falcon(to_string)]
         world_name: Identifier,
         hashed_seed: i64,
@@ -70,11 +74,47 @@ mod inner {
         Ok(())
     }
 
-    fn init_worlds() -> Vec<Identifier> { vec![init_world()] }
-
-    fn init_world() -> Identifier { Identifier::from_static("falcon", "world") }
+    #[derive(PacketSize, PacketWrite, From)]
+    #[from(RespawnSpec)]
+    #[falcon_packet(versions = {
+        735, 736 = 0x3A;
+    }, name = "respawn")]
+    pub struct RespawnPacket {
+        #[from(rename = "dimension_name")]
+        #[falcon(to_string)]
+        dimension: Identifier,
+        #[falcon(to_string)]
+        world_name: Identifier,
+        hashed_seed: i64,
+        game_mode: u8,
+        #[from(rename = "game_mode")]
+        prev_gamemode: u8,
+        #[from(skip)]
+        is_debug: bool,
+        #[from(skip)]
+        is_flat: bool,
+        #[from(skip)]
+        copy_metadata: bool,
+    }
 
-    fn init_dimension_codec() -> Codec { Codec::new(vec![DimensionData::new(Dimension::new("minecraft:overworld", 0))]) }
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        735, 736 = 0x0E;
+    }, name = "system_message")]
+    pub struct SystemMessagePacket {
+        #[falcon(string = 262144)]
+        message: String,
+        position: u8,
+        sender: Uuid,
+    }
 
-    fn init_dimension() -> Identifier { Identifier::from_static("minecraft", "overworld") }
+    impl From<ChatComponent> for SystemMessagePacket {
+        fn from(message: ChatComponent) -> Self {
+            SystemMessagePacket {
+                message: serde_json::to_string(&message).expect("Invalid message data"),
+                position: 1,
+                sender: Uuid::nil(),
+            }
+        }
+    }
 }
@@ -71,4 +71,25 @@ mod inner {
     pub struct ServerDifficultyPacket {
         difficulty: u8,
     }
+
+    #[derive(PacketSize, PacketWrite)]
+    #[falcon_packet(versions = {
+        47 = 0x02;
+        107, 108, 109, 110, 210, 315, 316, 335, 338, 340, 573, 575, 578 = 0x0F;
+        393, 401, 404, 477, 480, 485, 490, 498 = 0x0E;
+    }, name = "system_message")]
+    pub struct SystemMessagePacket {
+        #[falcon(string = 262144)]
+        message: String,
+        position: u8,
+    }
+
+    impl From<ChatComponent> for SystemMessagePacket {
+        fn from(message: ChatComponent) -> Self {
+            SystemMessagePacket {
+                message: serde_json::to_string(&message).expect("Invalid message data"),
+                position: 1,
+            }
+        }
+    }
 }